@@ -0,0 +1,177 @@
+//! Graphviz DOT export
+//!
+//! [`Dot`] builds a graph description one statement at a time, quoting ids
+//! and attribute values, for rendering with the Graphviz tools
+//! (`dot -Tsvg graph.dot > graph.svg`). [`digraph`] writes a whole
+//! [`Graph`] with attributes chosen per node.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::dot::Dot;
+//!
+//! let mut dot = Dot::digraph("reactor");
+//! dot.node("you", &[("color", "green")]);
+//! dot.edge("you", "out", &[]);
+//! assert_eq!(
+//!     dot.finish(),
+//!     "digraph \"reactor\" {\n  \"you\" [color=\"green\"];\n  \"you\" -> \"out\";\n}\n"
+//! );
+//! ```
+
+use super::graph::Graph;
+use std::fmt::{Display, Write};
+
+/// A DOT attribute, such as `("color", "red")`
+pub type Attr<'a> = (&'a str, &'a str);
+
+/// Builder for a DOT graph
+#[derive(Debug, Clone)]
+pub struct Dot {
+    directed: bool,
+    body: String,
+}
+
+impl Dot {
+    /// Start a directed graph, whose edges are drawn as arrows
+    pub fn digraph(name: &str) -> Self {
+        Self::start("digraph", name, true)
+    }
+
+    /// Start an undirected graph
+    pub fn graph(name: &str) -> Self {
+        Self::start("graph", name, false)
+    }
+
+    fn start(keyword: &str, name: &str, directed: bool) -> Self {
+        Self {
+            directed,
+            body: format!("{} {} {{\n", keyword, quote(name)),
+        }
+    }
+
+    /// Set attributes for the whole graph (`graph`), or defaults for every
+    /// later `node` or `edge`
+    pub fn defaults(&mut self, kind: &str, attrs: &[Attr]) -> &mut Self {
+        self.statement(kind, attrs)
+    }
+
+    /// Add a node, or set attributes on one already mentioned
+    pub fn node(&mut self, id: &str, attrs: &[Attr]) -> &mut Self {
+        self.statement(&quote(id), attrs)
+    }
+
+    /// Add an edge, adding either node if it is new
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[Attr]) -> &mut Self {
+        let arrow = if self.directed { "->" } else { "--" };
+        let edge = format!("{} {} {}", quote(from), arrow, quote(to));
+        self.statement(&edge, attrs)
+    }
+
+    fn statement(&mut self, target: &str, attrs: &[Attr]) -> &mut Self {
+        self.body.push_str("  ");
+        self.body.push_str(target);
+        if !attrs.is_empty() {
+            let list: Vec<String> = attrs
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, quote(value)))
+                .collect();
+            let _ = write!(self.body, " [{}]", list.join(", "));
+        }
+        self.body.push_str(";\n");
+        self
+    }
+
+    /// Close the graph and return the DOT source
+    pub fn finish(mut self) -> String {
+        self.body.push_str("}\n");
+        self.body
+    }
+}
+
+/// Quote a DOT id, escaping quotes and backslashes
+fn quote(id: &str) -> String {
+    let mut quoted = String::with_capacity(id.len() + 2);
+    quoted.push('"');
+    for c in id.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Write every node and edge of `graph` as a DOT digraph
+///
+/// Nodes are named by their labels. `style` gives the attributes of each node
+/// id; nodes it returns none for are listed only through their edges.
+pub fn digraph<'a, N: Display>(
+    name: &str,
+    graph: &Graph<N>,
+    mut style: impl FnMut(usize) -> Vec<Attr<'a>>,
+) -> String {
+    let mut dot = Dot::digraph(name);
+    let labels: Vec<String> = (0..graph.len())
+        .map(|id| graph.label(id).to_string())
+        .collect();
+    for (id, label) in labels.iter().enumerate() {
+        let attrs = style(id);
+        if !attrs.is_empty() {
+            dot.node(label, &attrs);
+        }
+    }
+    for (id, label) in labels.iter().enumerate() {
+        for next in graph.neighbors(id) {
+            dot.edge(label, &labels[next], &[]);
+        }
+    }
+    dot.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote("a\\b"), "\"a\\\\b\"");
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut dot = Dot::graph("boxes");
+        dot.defaults("node", &[("shape", "point")]).edge(
+            "0",
+            "1",
+            &[("penwidth", "3"), ("color", "red")],
+        );
+        assert_eq!(
+            dot.finish(),
+            "graph \"boxes\" {\n  node [shape=\"point\"];\n  \"0\" -- \"1\" [penwidth=\"3\", color=\"red\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_digraph_from_graph() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("a", "c");
+        graph.add_edge("b", "c");
+
+        let dot = digraph("g", &graph, |id| {
+            if *graph.label(id) == "a" {
+                vec![("shape", "box")]
+            } else {
+                Vec::new()
+            }
+        });
+        assert_eq!(
+            dot,
+            "digraph \"g\" {\n  \"a\" [shape=\"box\"];\n  \"a\" -> \"b\";\n  \"a\" -> \"c\";\n  \"b\" -> \"c\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,511 @@
+//! Exact cover by Knuth's Algorithm X with dancing links
+//!
+//! An [`ExactCover`] problem has primary columns, which every solution must
+//! cover exactly once, and secondary columns, which may be covered at most
+//! once. Rows are subsets of columns; a solution is a set of rows covering each
+//! column as required. The rows and columns are kept in circular doubly linked
+//! lists, so covering a column and undoing it later are both cheap pointer
+//! updates.
+//!
+//! Primary columns may also require a multiplicity: a column that must be
+//! covered `k` times gets `k` rows chosen from it, in increasing row order so
+//! each combination is only tried once. This suits packing identical pieces
+//! without a column per copy.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::exact_cover::ExactCover;
+//!
+//! // Knuth's example: columns A-G, rows as in "Dancing Links"
+//! let mut problem = ExactCover::new(7, 0);
+//! problem.add_row(&[2, 4, 5]);
+//! problem.add_row(&[0, 3, 6]);
+//! problem.add_row(&[1, 2, 5]);
+//! problem.add_row(&[0, 3]);
+//! problem.add_row(&[1, 6]);
+//! problem.add_row(&[3, 4, 6]);
+//!
+//! let mut solution = problem.solve().unwrap();
+//! solution.sort_unstable();
+//! assert_eq!(solution, vec![0, 3, 4]);
+//! assert_eq!(problem.count_solutions(), 1);
+//! ```
+
+use std::ops::ControlFlow;
+
+/// Root of the list of primary columns still to cover
+const ROOT: usize = 0;
+
+/// Work done by a search, for comparing solvers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Partial solutions visited
+    pub nodes: u64,
+    /// Choices undone because they led to no (further) solution
+    pub backtracks: u64,
+}
+
+impl SearchStats {
+    /// The counters by name, as reported by [`crate::runner::Day::stats`]
+    pub fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![("nodes", self.nodes), ("backtracks", self.backtracks)]
+    }
+}
+
+impl std::ops::AddAssign for SearchStats {
+    fn add_assign(&mut self, other: Self) {
+        self.nodes += other.nodes;
+        self.backtracks += other.backtracks;
+    }
+}
+
+/// A cell of the matrix, linked to its neighbours in both directions
+///
+/// Links are `u32` node indices so a node fits in half a cache line.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    left: u32,
+    right: u32,
+    up: u32,
+    down: u32,
+    /// Column the node is in
+    column: u32,
+    /// Row the node is in (unused for headers)
+    row: u32,
+}
+
+/// An exact cover problem with primary and secondary columns
+///
+/// Node 0 is the root, nodes `1..=columns` are column headers, and the rest
+/// are the cells of the rows.
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    nodes: Vec<Node>,
+    /// Rows currently in each column
+    size: Vec<usize>,
+    /// Times each primary column still has to be covered
+    remaining: Vec<usize>,
+    /// Lowest row that may still be chosen for each column
+    next_row: Vec<usize>,
+    primary: usize,
+    rows: usize,
+    stats: SearchStats,
+}
+
+impl ExactCover {
+    /// Create a problem with `primary` columns to cover exactly once and
+    /// `secondary` columns to cover at most once
+    ///
+    /// Primary columns are numbered `0..primary`, secondary ones after them.
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        Self::with_multiplicities(&vec![1; primary], secondary)
+    }
+
+    /// Like [`ExactCover::new`], but primary column `c` must be covered exactly
+    /// `multiplicities[c]` times
+    pub fn with_multiplicities(multiplicities: &[usize], secondary: usize) -> Self {
+        let primary = multiplicities.len();
+        let columns = primary + secondary;
+
+        // Primary headers form the root's circular list; secondary headers
+        // link to themselves so the search never needs to cover them
+        let nodes = (0..=columns as u32)
+            .map(|node| {
+                let col = node as usize;
+                let (left, right) = if node == ROOT as u32 {
+                    (primary as u32, if primary == 0 { node } else { 1 })
+                } else if col <= primary {
+                    (node - 1, ((col + 1) % (primary + 1)) as u32)
+                } else {
+                    (node, node)
+                };
+                Node {
+                    left,
+                    right,
+                    up: node,
+                    down: node,
+                    column: node.wrapping_sub(1),
+                    row: u32::MAX,
+                }
+            })
+            .collect();
+
+        Self {
+            nodes,
+            size: vec![0; columns],
+            remaining: multiplicities.to_vec(),
+            next_row: vec![0; columns],
+            primary,
+            rows: 0,
+            stats: SearchStats::default(),
+        }
+    }
+
+    /// Number of columns, primary and secondary
+    pub fn columns(&self) -> usize {
+        self.size.len()
+    }
+
+    /// Number of rows added
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Add a row covering `columns` and return its index
+    ///
+    /// The search only chooses rows through their primary columns, so a row
+    /// with none is never part of a solution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column is out of range.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.rows;
+        self.rows += 1;
+
+        let first = self.nodes.len() as u32;
+        for (offset, &col) in columns.iter().enumerate() {
+            assert!(col < self.columns(), "Column {} out of range", col);
+            let node = first + offset as u32;
+            let header = col + 1;
+
+            // Append to the bottom of the column and close the row's circle
+            let above = self.nodes[header].up;
+            self.nodes.push(Node {
+                left: if offset == 0 { node } else { node - 1 },
+                right: first,
+                up: above,
+                down: header as u32,
+                column: col as u32,
+                row: row as u32,
+            });
+            self.nodes[above as usize].down = node;
+            self.nodes[header].up = node;
+            self.size[col] += 1;
+            if offset > 0 {
+                self.nodes[node as usize - 1].right = node;
+                self.nodes[first as usize].left = node;
+            }
+        }
+        row
+    }
+
+    /// Counters from the searches run so far
+    pub fn stats(&self) -> SearchStats {
+        self.stats
+    }
+
+    /// Find one solution, as row indices in the order they were chosen
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        let mut found = None;
+        self.for_each_solution(|rows| {
+            found = Some(rows.to_vec());
+            ControlFlow::Break(())
+        });
+        found
+    }
+
+    /// Count every solution
+    pub fn count_solutions(&mut self) -> u64 {
+        let mut count = 0;
+        self.for_each_solution(|_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        count
+    }
+
+    /// Call `visit` with each solution until it returns `Break`
+    pub fn for_each_solution(&mut self, mut visit: impl FnMut(&[usize]) -> ControlFlow<()>) {
+        // Columns needed zero times rule out all of their rows up front
+        let unused: Vec<usize> = (0..self.primary)
+            .filter(|&col| self.remaining[col] == 0)
+            .collect();
+        for &col in &unused {
+            self.cover(col);
+        }
+
+        let mut solution = Vec::new();
+        let _ = self.search(&mut solution, &mut visit);
+
+        for &col in unused.iter().rev() {
+            self.uncover(col);
+        }
+    }
+
+    fn search(
+        &mut self,
+        solution: &mut Vec<usize>,
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.stats.nodes += 1;
+        if self.nodes[ROOT].right as usize == ROOT {
+            return visit(solution);
+        }
+
+        // Branch on the column with the fewest rows, failing early if one
+        // has fewer rows than covers still needed
+        let mut col = usize::MAX;
+        let mut header = self.nodes[ROOT].right as usize;
+        while header != ROOT {
+            let c = header - 1;
+            if self.size[c] < self.remaining[c] {
+                return ControlFlow::Continue(());
+            }
+            if col == usize::MAX || self.size[c] < self.size[col] {
+                col = c;
+            }
+            header = self.nodes[header].right as usize;
+        }
+
+        // The last cover needed removes the column and its other rows
+        let last = self.remaining[col] == 1;
+        if last {
+            self.cover(col);
+        }
+
+        let header = col + 1;
+        let mut node = self.nodes[header].down as usize;
+        while node != header {
+            let row = self.nodes[node].row as usize;
+            if row >= self.next_row[col] {
+                solution.push(row);
+                self.cover_row(node);
+                let saved = (self.remaining[col], self.next_row[col]);
+                self.remaining[col] -= 1;
+                self.next_row[col] = row + 1;
+
+                let flow = self.search(solution, visit);
+
+                (self.remaining[col], self.next_row[col]) = saved;
+                self.uncover_row(node);
+                solution.pop();
+
+                if flow.is_break() {
+                    if last {
+                        self.uncover(col);
+                    }
+                    return flow;
+                }
+                self.stats.backtracks += 1;
+            }
+            node = self.nodes[node].down as usize;
+        }
+
+        if last {
+            self.uncover(col);
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Cover every other column of the row containing `node`
+    fn cover_row(&mut self, node: usize) {
+        let mut cell = self.nodes[node].right as usize;
+        while cell != node {
+            self.cover(self.nodes[cell].column as usize);
+            cell = self.nodes[cell].right as usize;
+        }
+    }
+
+    /// Undo [`Self::cover_row`]
+    fn uncover_row(&mut self, node: usize) {
+        let mut cell = self.nodes[node].left as usize;
+        while cell != node {
+            self.uncover(self.nodes[cell].column as usize);
+            cell = self.nodes[cell].left as usize;
+        }
+    }
+
+    /// Remove a column from the header list and its rows from other columns
+    fn cover(&mut self, col: usize) {
+        let header = col + 1;
+        let Node { left, right, .. } = self.nodes[header];
+        self.nodes[left as usize].right = right;
+        self.nodes[right as usize].left = left;
+
+        let mut row = self.nodes[header].down as usize;
+        while row != header {
+            let mut cell = self.nodes[row].right as usize;
+            while cell != row {
+                let Node {
+                    up, down, column, ..
+                } = self.nodes[cell];
+                self.nodes[up as usize].down = down;
+                self.nodes[down as usize].up = up;
+                self.size[column as usize] -= 1;
+                cell = self.nodes[cell].right as usize;
+            }
+            row = self.nodes[row].down as usize;
+        }
+    }
+
+    /// Undo [`Self::cover`], relinking in exactly the reverse order
+    fn uncover(&mut self, col: usize) {
+        let header = col + 1;
+        let mut row = self.nodes[header].up as usize;
+        while row != header {
+            let mut cell = self.nodes[row].left as usize;
+            while cell != row {
+                let Node {
+                    up, down, column, ..
+                } = self.nodes[cell];
+                self.size[column as usize] += 1;
+                self.nodes[up as usize].down = cell as u32;
+                self.nodes[down as usize].up = cell as u32;
+                cell = self.nodes[cell].left as usize;
+            }
+            row = self.nodes[row].up as usize;
+        }
+
+        let Node { left, right, .. } = self.nodes[header];
+        self.nodes[left as usize].right = header as u32;
+        self.nodes[right as usize].left = header as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rows of every solution, each sorted, in sorted order
+    fn all_solutions(problem: &mut ExactCover) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        problem.for_each_solution(|rows| {
+            let mut rows = rows.to_vec();
+            rows.sort_unstable();
+            solutions.push(rows);
+            ControlFlow::Continue(())
+        });
+        solutions.sort();
+        solutions
+    }
+
+    #[test]
+    fn test_knuth_example() {
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row);
+        }
+        assert_eq!(all_solutions(&mut problem), vec![vec![0, 3, 4]]);
+
+        // The matrix is restored after searching
+        assert_eq!(problem.solve().map(|rows| rows.len()), Some(3));
+        assert!(problem.stats().nodes > 0);
+    }
+
+    #[test]
+    fn test_secondary_columns() {
+        // Primary 0 and 1; secondary 2 may be used at most once
+        let mut problem = ExactCover::new(2, 1);
+        problem.add_row(&[0, 2]); // 0
+        problem.add_row(&[1, 2]); // 1
+        problem.add_row(&[0]); // 2
+        problem.add_row(&[1]); // 3
+        assert_eq!(
+            all_solutions(&mut problem),
+            vec![vec![0, 3], vec![1, 2], vec![2, 3]]
+        );
+
+        // A row made only of secondary columns is never needed
+        let mut problem = ExactCover::new(1, 1);
+        problem.add_row(&[1]);
+        problem.add_row(&[0]);
+        assert_eq!(all_solutions(&mut problem), vec![vec![1]]);
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let mut problem = ExactCover::new(3, 0);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[1, 2]);
+        assert_eq!(problem.solve(), None);
+        assert_eq!(problem.count_solutions(), 0);
+
+        // Nothing to cover has exactly one (empty) solution
+        let mut problem = ExactCover::new(0, 2);
+        problem.add_row(&[0, 1]);
+        assert_eq!(all_solutions(&mut problem), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        // Tile a 1x4 strip with two dominoes or any mix of monominoes, using
+        // column 0 for dominoes and column 1 for monominoes; cells are 2..6
+        let mut problem = ExactCover::with_multiplicities(&[1, 2], 4);
+        let domino = |x: usize| vec![0, 2 + x, 3 + x];
+        let monomino = |x: usize| vec![1, 2 + x];
+        for x in 0..3 {
+            problem.add_row(&domino(x)); // rows 0, 2, 4
+            problem.add_row(&monomino(x)); // rows 1, 3, 5
+        }
+        problem.add_row(&monomino(3)); // row 6
+
+        // One domino and two monominoes: domino at 0, 1 or 2
+        assert_eq!(
+            all_solutions(&mut problem),
+            vec![vec![0, 5, 6], vec![1, 2, 6], vec![1, 3, 4]]
+        );
+
+        // Needing no monominoes forbids them
+        let mut problem = ExactCover::with_multiplicities(&[2, 0], 4);
+        for x in 0..3 {
+            problem.add_row(&domino(x));
+            problem.add_row(&monomino(x));
+        }
+        assert_eq!(all_solutions(&mut problem), vec![vec![0, 4]]);
+    }
+
+    #[test]
+    fn test_count_matches_brute_force() {
+        // Pseudo-random small problems checked against every subset of rows
+        let mut state = 3u64;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        for _ in 0..50 {
+            let (primary, secondary) = (1 + next(5) as usize, next(3) as usize);
+            let columns = primary + secondary;
+            let rows: Vec<Vec<usize>> = (0..1 + next(10))
+                .map(|_| (0..columns).filter(|_| next(3) == 0).collect())
+                .collect();
+
+            let mut problem = ExactCover::new(primary, secondary);
+            for row in &rows {
+                problem.add_row(row);
+            }
+
+            let expected = (0u32..1 << rows.len())
+                .filter(|subset| {
+                    // Rows without a primary column are never picked
+                    let picks_optional = rows
+                        .iter()
+                        .enumerate()
+                        .any(|(i, row)| subset >> i & 1 == 1 && row.iter().all(|&c| c >= primary));
+                    if picks_optional {
+                        return false;
+                    }
+                    let mut covered = vec![0; columns];
+                    for (i, row) in rows.iter().enumerate() {
+                        if subset >> i & 1 == 1 {
+                            row.iter().for_each(|&c| covered[c] += 1);
+                        }
+                    }
+                    covered[..primary].iter().all(|&n| n == 1)
+                        && covered[primary..].iter().all(|&n| n <= 1)
+                })
+                .count() as u64;
+            assert_eq!(problem.count_solutions(), expected, "{:?}", rows);
+        }
+    }
+}
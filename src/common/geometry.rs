@@ -0,0 +1,386 @@
+//! Integer points and polygons
+//!
+//! [`Point2`] and [`Point3`] are small `Copy` vectors with the usual
+//! arithmetic and the metrics puzzles ask for. [`Polygon`] answers exact
+//! point-in-polygon queries with the winding number, and for rectilinear
+//! polygons (every edge horizontal or vertical) whether every integer point of
+//! an axis-aligned rectangle lies inside.
+//!
+//! Polygons are closed regions: points on the boundary count as inside.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::geometry::{Point2, Polygon};
+//!
+//! // An L shape
+//! let polygon = Polygon::new(vec![
+//!     Point2::new(0, 0),
+//!     Point2::new(4, 0),
+//!     Point2::new(4, 2),
+//!     Point2::new(2, 2),
+//!     Point2::new(2, 4),
+//!     Point2::new(0, 4),
+//! ]);
+//! assert!(polygon.contains(Point2::new(1, 3)));
+//! assert!(!polygon.contains(Point2::new(3, 3)));
+//! assert!(polygon.contains_rect(Point2::new(0, 0), Point2::new(4, 2)));
+//! assert!(!polygon.contains_rect(Point2::new(0, 0), Point2::new(3, 3)));
+//! ```
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or vector on the integer plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Sum of the absolute coordinate differences
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Square of the Euclidean distance, which is exact in integers
+    pub fn distance_squared(self, other: Self) -> i64 {
+        let d = self - other;
+        d.dot(d)
+    }
+
+    pub fn dot(self, other: Self) -> i64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the 3D cross product: positive when `other` is
+    /// counter-clockwise from `self`
+    pub fn cross(self, other: Self) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+/// A point or vector in integer 3D space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Sum of the absolute coordinate differences
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Square of the Euclidean distance, which is exact in integers
+    pub fn distance_squared(self, other: Self) -> i64 {
+        let d = self - other;
+        d.dot(d)
+    }
+
+    pub fn dot(self, other: Self) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+macro_rules! impl_point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2 { x, y });
+impl_point_ops!(Point3 { x, y, z });
+
+/// A simple polygon given by its vertices in order
+///
+/// The last vertex connects back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point2>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point2] {
+        &self.vertices
+    }
+
+    /// Edges as `(start, end)` pairs, including the closing edge
+    pub fn edges(&self) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Whether every edge is horizontal or vertical
+    pub fn is_rectilinear(&self) -> bool {
+        self.edges().all(|(a, b)| a.x == b.x || a.y == b.y)
+    }
+
+    /// Smallest and largest corner of the axis-aligned bounding box
+    pub fn bounding_box(&self) -> Option<(Point2, Point2)> {
+        let first = *self.vertices.first()?;
+        Some(self.vertices.iter().fold((first, first), |(lo, hi), p| {
+            (
+                Point2::new(lo.x.min(p.x), lo.y.min(p.y)),
+                Point2::new(hi.x.max(p.x), hi.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Whether `p` lies on an edge
+    pub fn on_boundary(&self, p: Point2) -> bool {
+        self.edges().any(|(a, b)| on_segment(p, a, b))
+    }
+
+    /// Number of times the boundary winds counter-clockwise around `p`
+    ///
+    /// Zero for points outside. The result for points on the boundary is
+    /// unspecified; use [`Polygon::contains`] to include them.
+    pub fn winding_number(&self, p: Point2) -> i32 {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = (b - a).cross(p - a);
+            if a.y <= p.y {
+                // Upward crossing with p strictly left of the edge
+                if b.y > p.y && side > 0 {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && side < 0 {
+                // Downward crossing with p strictly right of the edge
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Whether `p` is inside or on the boundary
+    pub fn contains(&self, p: Point2) -> bool {
+        self.on_boundary(p) || self.winding_number(p) != 0
+    }
+
+    /// Whether every integer point of the rectangle with corners `a` and `b`
+    /// is inside or on the boundary
+    ///
+    /// Exact for rectilinear polygons, which is all this supports. The vertex
+    /// coordinates split the rectangle into cells whose interiors no edge
+    /// passes through, so each row, column and open cell of that grid is
+    /// tested at a single representative point. This costs O(V² × E) for a
+    /// rectangle spanning every vertex, so callers asking many queries should
+    /// precompute instead.
+    pub fn contains_rect(&self, a: Point2, b: Point2) -> bool {
+        debug_assert!(self.is_rectilinear(), "Polygon is not rectilinear");
+        let xs = self.representatives(a.x.min(b.x), a.x.max(b.x), |p| p.x);
+        let ys = self.representatives(a.y.min(b.y), a.y.max(b.y), |p| p.y);
+        ys.iter()
+            .all(|&y| xs.iter().all(|&x| self.contains(Point2::new(x, y))))
+    }
+
+    /// One integer per vertex coordinate in `lo..=hi`, plus one for each gap
+    /// between them that holds an integer
+    fn representatives(&self, lo: i64, hi: i64, axis: impl Fn(Point2) -> i64) -> Vec<i64> {
+        let mut stops: Vec<i64> = self
+            .vertices
+            .iter()
+            .map(|&v| axis(v))
+            .filter(|&c| lo < c && c < hi)
+            .chain([lo, hi])
+            .collect();
+        stops.sort_unstable();
+        stops.dedup();
+
+        let mut points = Vec::with_capacity(stops.len() * 2);
+        for pair in stops.windows(2) {
+            points.push(pair[0]);
+            if pair[1] - pair[0] > 1 {
+                points.push(pair[0] + 1);
+            }
+        }
+        points.push(hi);
+        points
+    }
+}
+
+/// Whether `p` lies on the closed segment from `a` to `b`
+fn on_segment(p: Point2, a: Point2, b: Point2) -> bool {
+    let in_box =
+        a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y);
+    in_box && (b - a).cross(p - a) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The Day 9 example polygon
+    fn example() -> Polygon {
+        let corners = [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ];
+        Polygon::new(corners.iter().map(|&(x, y)| Point2::new(x, y)).collect())
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, -4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(Point2::new(1, 0).cross(Point2::new(0, 1)), 1);
+
+        let mut p = Point3::new(1, 2, 3);
+        p += Point3::new(1, 1, 1);
+        p -= Point3::new(0, 0, 4);
+        assert_eq!(p, Point3::new(2, 3, 0));
+        assert_eq!(p.manhattan(Point3::ORIGIN), 5);
+        assert_eq!(p.distance_squared(Point3::new(2, 0, 4)), 25);
+    }
+
+    #[test]
+    fn test_winding_number() {
+        let square = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(4, 0),
+            Point2::new(4, 4),
+            Point2::new(0, 4),
+        ]);
+        assert_eq!(square.winding_number(Point2::new(2, 2)), 1);
+        assert_eq!(square.winding_number(Point2::new(5, 2)), 0);
+
+        let clockwise = Polygon::new(square.vertices().iter().rev().copied().collect());
+        assert_eq!(clockwise.winding_number(Point2::new(2, 2)), -1);
+        assert!(clockwise.contains(Point2::new(2, 2)));
+    }
+
+    #[test]
+    fn test_contains_points() {
+        let polygon = example();
+        assert!(polygon.is_rectilinear());
+        assert_eq!(
+            polygon.bounding_box(),
+            Some((Point2::new(2, 1), Point2::new(11, 7)))
+        );
+
+        // Vertices, edge points, interior and exterior
+        assert!(polygon.contains(Point2::new(7, 1)));
+        assert!(polygon.contains(Point2::new(5, 3)));
+        assert!(polygon.on_boundary(Point2::new(5, 3)));
+        assert!(polygon.contains(Point2::new(8, 4)));
+        assert!(!polygon.on_boundary(Point2::new(8, 4)));
+        assert!(!polygon.contains(Point2::new(5, 2)));
+        assert!(!polygon.contains(Point2::new(3, 6)));
+        assert!(!polygon.contains(Point2::new(10, 8)));
+    }
+
+    #[test]
+    fn test_contains_rect_matches_brute_force() {
+        let polygon = example();
+        let (lo, hi) = polygon.bounding_box().unwrap();
+        let points: Vec<Point2> = (lo.y - 1..=hi.y + 1)
+            .flat_map(|y| (lo.x - 1..=hi.x + 1).map(move |x| Point2::new(x, y)))
+            .collect();
+
+        for &a in &points {
+            for &b in &points {
+                let expected = (a.x.min(b.x)..=a.x.max(b.x)).all(|x| {
+                    (a.y.min(b.y)..=a.y.max(b.y)).all(|y| polygon.contains(Point2::new(x, y)))
+                });
+                assert_eq!(polygon.contains_rect(a, b), expected, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contains_rect_narrow_notch() {
+        // A wide rectangle with notches cut into the top edge: the first is
+        // one unit wide so holds no integer points, the second holds a column
+        let polygon = Polygon::new(vec![
+            Point2::new(0, 0),
+            Point2::new(1000, 0),
+            Point2::new(1000, 100),
+            Point2::new(702, 100),
+            Point2::new(702, 50),
+            Point2::new(700, 50),
+            Point2::new(700, 100),
+            Point2::new(501, 100),
+            Point2::new(501, 50),
+            Point2::new(500, 50),
+            Point2::new(500, 100),
+            Point2::new(0, 100),
+        ]);
+        assert!(polygon.contains_rect(Point2::new(0, 0), Point2::new(1000, 50)));
+        assert!(polygon.contains_rect(Point2::new(0, 0), Point2::new(699, 100)));
+        assert!(!polygon.contains_rect(Point2::new(0, 0), Point2::new(1000, 51)));
+        assert!(!polygon.contains_rect(Point2::new(701, 100), Point2::new(701, 100)));
+        assert!(polygon.contains_rect(Point2::new(702, 100), Point2::new(1000, 100)));
+    }
+}
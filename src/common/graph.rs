@@ -0,0 +1,589 @@
+//! Graph algorithms: union-find, interned graphs, traversal and path counting
+//!
+//! [`Graph`] stores a directed, weighted graph over dense `usize` ids and
+//! interns the labels nodes are known by in the puzzle text, so algorithms can
+//! work on plain vectors instead of hash maps keyed by strings.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::graph::Graph;
+//!
+//! let mut graph = Graph::new();
+//! graph.add_edge("svr", "aaa");
+//! graph.add_edge("svr", "bbb");
+//! graph.add_edge("aaa", "out");
+//! graph.add_edge("bbb", "out");
+//!
+//! let svr = graph.id("svr").unwrap();
+//! let out = graph.id("out").unwrap();
+//! let aaa = graph.id("aaa").unwrap();
+//! assert_eq!(graph.count_paths(svr, out, &[]), Ok(2));
+//! assert_eq!(graph.count_paths(svr, out, &[aaa]), Ok(1));
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+/// Disjoint-set forest with path compression and union by size
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Create `n` singleton sets
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Representative of the set containing `x`
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Path compression
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+
+        root
+    }
+
+    /// Merge the sets containing `x` and `y`
+    ///
+    /// Returns `false` if they were already in the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+
+        if root_x == root_y {
+            return false;
+        }
+
+        // Union by size
+        let (small, large) = if self.size[root_x] < self.size[root_y] {
+            (root_x, root_y)
+        } else {
+            (root_y, root_x)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+
+        self.components -= 1;
+        true
+    }
+
+    /// Whether `x` and `y` are in the same set
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Size of the set containing `x`
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of all sets, largest first
+    pub fn component_sizes(&self) -> Vec<usize> {
+        // Roots are exactly the elements that are their own parent
+        let mut sizes: Vec<usize> = (0..self.parent.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// A directed edge to node `to`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub to: usize,
+    pub weight: u64,
+}
+
+/// Error returned when an algorithm that needs a DAG finds a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError {
+    /// A node that lies on a cycle
+    pub node: usize,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Graph has a cycle through node {}", self.node)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Directed graph whose nodes are labelled with values of type `N`
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    ids: HashMap<N, usize>,
+    labels: Vec<N>,
+    adjacency: Vec<Vec<Edge>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            labels: Vec::new(),
+            adjacency: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `label`, adding a node for it if it is new
+    pub fn intern(&mut self, label: N) -> usize {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.adjacency.push(Vec::new());
+        id
+    }
+
+    /// Id of `label`, if it has been added
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    /// Add an edge of weight 1, adding either node if it is new
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_weighted_edge(from, to, 1);
+    }
+
+    /// Add a weighted edge, adding either node if it is new
+    pub fn add_weighted_edge(&mut self, from: N, to: N, weight: u64) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.adjacency[from].push(Edge { to, weight });
+    }
+}
+
+impl<N> Graph<N> {
+    /// Label of node `id`
+    pub fn label(&self, id: usize) -> &N {
+        &self.labels[id]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Whether the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Outgoing edges of node `id`
+    pub fn edges(&self, id: usize) -> &[Edge] {
+        &self.adjacency[id]
+    }
+
+    /// Direct successors of node `id`
+    pub fn neighbors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency[id].iter().map(|edge| edge.to)
+    }
+
+    /// Number of edges from `start` to every node, ignoring weights
+    ///
+    /// Unreachable nodes are `None`.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        dist[start] = Some(0);
+        queue.push_back(start);
+
+        while let Some(node) = queue.pop_front() {
+            let next = dist[node].map(|d| d + 1);
+            for neighbor in self.neighbors(node) {
+                if dist[neighbor].is_none() {
+                    dist[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut seen[node], true) {
+                continue;
+            }
+            order.push(node);
+            // Push in reverse so neighbours are visited in insertion order
+            for edge in self.adjacency[node].iter().rev() {
+                if !seen[edge.to] {
+                    stack.push(edge.to);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// All nodes ordered so every edge points forwards (Kahn's algorithm)
+    pub fn topological_order(&self) -> Result<Vec<usize>, CycleError> {
+        self.topological_order_of(&vec![true; self.len()])
+    }
+
+    /// Topological order of the nodes with `included[node]` set
+    fn topological_order_of(&self, included: &[bool]) -> Result<Vec<usize>, CycleError> {
+        let mut in_degree = vec![0usize; self.len()];
+        for node in (0..self.len()).filter(|&n| included[n]) {
+            for neighbor in self.neighbors(node).filter(|&n| included[n]) {
+                in_degree[neighbor] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len())
+            .filter(|&n| included[n] && in_degree[n] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for neighbor in self.neighbors(node).filter(|&n| included[n]) {
+                in_degree[neighbor] -= 1;
+                if in_degree[neighbor] == 0 {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        match (0..self.len()).find(|&n| included[n] && in_degree[n] > 0) {
+            Some(node) => Err(CycleError { node }),
+            None => Ok(order),
+        }
+    }
+
+    /// Count paths from `start` to `end` that visit every node in `waypoints`
+    ///
+    /// Only nodes on some walk from `start` to `end` matter. If they form a
+    /// DAG, the waypoints a path visits appear in topological order, so the
+    /// count is the product of the path counts between consecutive stops
+    /// `start, w₁, …, wₖ, end`, each found by dynamic programming over the
+    /// topological order. The cost is O((V + E) × waypoints) however many
+    /// paths there are. Fails if a cycle lies on a walk from `start` to `end`,
+    /// since the count would then be infinite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count overflows `u128`.
+    pub fn count_paths(
+        &self,
+        start: usize,
+        end: usize,
+        waypoints: &[usize],
+    ) -> Result<u128, CycleError> {
+        let mut relevant = vec![false; self.len()];
+        for node in self.dfs(start) {
+            relevant[node] = true;
+        }
+        let reaches_end = self.reaching(end);
+        for (node, keep) in relevant.iter_mut().enumerate() {
+            *keep &= reaches_end[node];
+        }
+        if !relevant[start] || waypoints.iter().any(|&w| !relevant[w]) {
+            return Ok(0);
+        }
+
+        let order = self.topological_order_of(&relevant)?;
+        let mut position = vec![usize::MAX; self.len()];
+        for (pos, &node) in order.iter().enumerate() {
+            position[node] = pos;
+        }
+
+        let mut stops = waypoints.to_vec();
+        stops.sort_unstable_by_key(|&w| position[w]);
+        stops.dedup();
+
+        let mut counts = vec![0u128; self.len()];
+        let mut total = 1u128;
+        let mut from = start;
+        for to in stops.into_iter().chain([end]) {
+            let segment = self.count_segment(&order, &position, from, to, &mut counts);
+            total = total
+                .checked_mul(segment)
+                .expect("Path count overflows u128");
+            from = to;
+        }
+        Ok(total)
+    }
+
+    /// Nodes from which `target` can be reached
+    fn reaching(&self, target: usize) -> Vec<bool> {
+        let mut reverse = vec![Vec::new(); self.len()];
+        for (node, edges) in self.adjacency.iter().enumerate() {
+            for edge in edges {
+                reverse[edge.to].push(node);
+            }
+        }
+
+        let mut seen = vec![false; self.len()];
+        seen[target] = true;
+        let mut stack = vec![target];
+        while let Some(node) = stack.pop() {
+            for &prev in &reverse[node] {
+                if !std::mem::replace(&mut seen[prev], true) {
+                    stack.push(prev);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Paths from `from` to `to` within a topological `order`
+    ///
+    /// `position` maps nodes to their place in `order`, with `usize::MAX` for
+    /// nodes outside it. `counts` is scratch space of one slot per node.
+    fn count_segment(
+        &self,
+        order: &[usize],
+        position: &[usize],
+        from: usize,
+        to: usize,
+        counts: &mut [u128],
+    ) -> u128 {
+        if position[to] < position[from] {
+            return 0;
+        }
+        let span = &order[position[from]..=position[to]];
+        for &node in span {
+            counts[node] = 0;
+        }
+        counts[from] = 1;
+
+        for &node in &span[..span.len() - 1] {
+            let count = counts[node];
+            if count == 0 {
+                continue;
+            }
+            for neighbor in self.neighbors(node) {
+                if position[neighbor] <= position[to] {
+                    counts[neighbor] = counts[neighbor]
+                        .checked_add(count)
+                        .expect("Path count overflows u128");
+                }
+            }
+        }
+        counts[to]
+    }
+
+    /// Shortest weighted distance from `start` to every node
+    ///
+    /// Unreachable nodes are `None`.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<u64>> {
+        let mut dist = vec![None; self.len()];
+        let mut heap = BinaryHeap::new();
+        dist[start] = Some(0);
+        heap.push(Reverse((0u64, start)));
+
+        while let Some(Reverse((d, node))) = heap.pop() {
+            if dist[node].is_some_and(|best| d > best) {
+                continue;
+            }
+            for edge in &self.adjacency[node] {
+                let next = d + edge.weight;
+                if dist[edge.to].is_none_or(|best| next < best) {
+                    dist[edge.to] = Some(next);
+                    heap.push(Reverse((next, edge.to)));
+                }
+            }
+        }
+
+        dist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Diamond with a tail: a → {b, c} → d → e
+    fn diamond() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("a", "c");
+        graph.add_edge("b", "d");
+        graph.add_edge("c", "d");
+        graph.add_edge("d", "e");
+        graph
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(1, 2));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(3, 4));
+
+        assert!(uf.connected(0, 2));
+        assert!(!uf.connected(0, 3));
+        assert_eq!(uf.size(2), 3);
+        assert_eq!(uf.components(), 3);
+        assert_eq!(uf.component_sizes(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_interning() {
+        let graph = diamond();
+        assert_eq!(graph.len(), 5);
+        let d = graph.id("d").unwrap();
+        assert_eq!(*graph.label(d), "d");
+        assert_eq!(graph.id("zzz"), None);
+    }
+
+    #[test]
+    fn test_traversals() {
+        let graph = diamond();
+        let id = |label| graph.id(label).unwrap();
+
+        let dist = graph.bfs(id("a"));
+        assert_eq!(dist[id("d")], Some(2));
+        assert_eq!(graph.bfs(id("d"))[id("a")], None);
+
+        let order: Vec<_> = graph
+            .dfs(id("a"))
+            .iter()
+            .map(|&n| *graph.label(n))
+            .collect();
+        assert_eq!(order, vec!["a", "b", "d", "e", "c"]);
+
+        let topo = graph.topological_order().unwrap();
+        let position = |label| topo.iter().position(|&n| n == id(label)).unwrap();
+        assert!(position("a") < position("b"));
+        assert!(position("c") < position("d"));
+        assert!(position("d") < position("e"));
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let mut graph = diamond();
+        graph.add_edge("e", "b");
+        assert!(graph.topological_order().is_err());
+
+        let a = graph.id("a").unwrap();
+        let e = graph.id("e").unwrap();
+        assert!(graph.count_paths(a, e, &[]).is_err());
+
+        // A cycle that is unreachable from the start does not matter
+        let mut graph = diamond();
+        graph.add_edge("x", "y");
+        graph.add_edge("y", "x");
+        let c = graph.id("c").unwrap();
+        let e = graph.id("e").unwrap();
+        assert_eq!(graph.count_paths(c, e, &[]), Ok(1));
+
+        // Nor does one that is reachable but can't lead to the end
+        let mut graph = diamond();
+        graph.add_edge("b", "x");
+        graph.add_edge("x", "b");
+        graph.add_edge("x", "y");
+        graph.add_edge("y", "x");
+        let id = |label| graph.id(label).unwrap();
+        assert!(graph.count_paths(id("a"), id("e"), &[]).is_err());
+        assert_eq!(graph.count_paths(id("c"), id("e"), &[]), Ok(1));
+        assert_eq!(graph.count_paths(id("a"), id("c"), &[]), Ok(1));
+    }
+
+    #[test]
+    fn test_count_paths_with_waypoints() {
+        let graph = diamond();
+        let id = |label| graph.id(label).unwrap();
+
+        assert_eq!(graph.count_paths(id("a"), id("e"), &[]), Ok(2));
+        assert_eq!(graph.count_paths(id("a"), id("e"), &[id("b")]), Ok(1));
+        assert_eq!(
+            graph.count_paths(id("a"), id("e"), &[id("b"), id("c")]),
+            Ok(0)
+        );
+        assert_eq!(
+            graph.count_paths(id("a"), id("e"), &[id("a"), id("e")]),
+            Ok(2)
+        );
+        assert_eq!(graph.count_paths(id("e"), id("a"), &[]), Ok(0));
+        assert_eq!(graph.count_paths(id("b"), id("b"), &[]), Ok(1));
+    }
+
+    #[test]
+    fn test_count_paths_many_waypoints() {
+        // A chain of 40 diamonds has 2^40 paths, each passing every joint
+        let mut graph = Graph::new();
+        for i in 0..40 {
+            graph.add_edge(format!("j{}", i), format!("l{}", i));
+            graph.add_edge(format!("j{}", i), format!("r{}", i));
+            graph.add_edge(format!("l{}", i), format!("j{}", i + 1));
+            graph.add_edge(format!("r{}", i), format!("j{}", i + 1));
+        }
+        let id = |label: String| graph.id(&label).unwrap();
+        let (start, end) = (id("j0".to_string()), id("j40".to_string()));
+
+        let joints: Vec<usize> = (0..=40).rev().map(|i| id(format!("j{}", i))).collect();
+        assert_eq!(graph.count_paths(start, end, &joints), Ok(1 << 40));
+
+        let lefts: Vec<usize> = (0..20).map(|i| id(format!("l{}", i))).collect();
+        assert_eq!(graph.count_paths(start, end, &lefts), Ok(1 << 20));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let mut graph = Graph::new();
+        graph.add_weighted_edge("a", "b", 7);
+        graph.add_weighted_edge("a", "c", 2);
+        graph.add_weighted_edge("c", "b", 3);
+        graph.add_weighted_edge("b", "d", 1);
+        graph.add_weighted_edge("e", "a", 1);
+
+        let dist = graph.dijkstra(graph.id("a").unwrap());
+        let at = |label| dist[graph.id(label).unwrap()];
+        assert_eq!(at("b"), Some(5));
+        assert_eq!(at("d"), Some(6));
+        assert_eq!(at("e"), None);
+    }
+}
@@ -0,0 +1,528 @@
+//! Generic 2D grid stored as a flat, row-major `Vec`
+//!
+//! Positions are `(x, y)` pairs with `x` the column and `y` the row, both
+//! counted from the top-left corner. Hot loops can work with flat indices
+//! instead (`y * width + x`); [`Grid::idx`] and [`Grid::pos`] convert between
+//! the two, and the neighbour iterators yield flat indices.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::grid::Grid;
+//!
+//! let grid = Grid::parse("..@\n@@.\n");
+//! assert_eq!((grid.width(), grid.height()), (3, 2));
+//! assert_eq!(grid[(2, 0)], b'@');
+//! assert_eq!(grid.get(3, 0), None);
+//!
+//! let idx = grid.idx(1, 0);
+//! let rolls = grid.neighbors8(idx).filter(|&n| grid[n] == b'@').count();
+//! assert_eq!(rolls, 3);
+//!
+//! assert_eq!(grid.rotated_cw().to_string(), "@.\n@.\n.@\n");
+//! ```
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4 orthogonal neighbours, in reading order
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of all 8 neighbours, in reading order
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from row-major cells
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells.len() != width * height`.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Grid of {}x{} needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parse one row per line, converting each character with `f`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows have different widths.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            check_row_width(&mut width, cells.len() - before, height);
+            height += 1;
+        }
+
+        Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        }
+    }
+
+    /// Number of columns
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Total number of cells
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the grid has no cells
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Flat index of `(x, y)`
+    #[inline]
+    pub fn idx(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Position `(x, y)` of a flat index
+    #[inline]
+    pub fn pos(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+
+    /// Whether a possibly negative position lies inside the grid
+    #[inline]
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Cell at `(x, y)`, or `None` if out of bounds
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Mutable cell at `(x, y)`, or `None` if out of bounds
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Cell at `(x, y)` without bounds checking
+    ///
+    /// # Safety
+    ///
+    /// `x` must be less than `width` and `y` less than `height`.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> &T {
+        // SAFETY: the caller guarantees the position is in bounds
+        unsafe { self.cells.get_unchecked(y * self.width + x) }
+    }
+
+    /// Mutable cell at `(x, y)` without bounds checking
+    ///
+    /// # Safety
+    ///
+    /// `x` must be less than `width` and `y` less than `height`.
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, x: usize, y: usize) -> &mut T {
+        // SAFETY: the caller guarantees the position is in bounds
+        unsafe { self.cells.get_unchecked_mut(y * self.width + x) }
+    }
+
+    /// All cells in row-major order
+    #[inline]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// All cells in row-major order, mutably
+    #[inline]
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Cells paired with their positions, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    /// Position of the first cell (in reading order) matching `pred`
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells.iter().position(pred).map(|idx| self.pos(idx))
+    }
+
+    /// Row `y` as a slice
+    #[inline]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Row `y` as a mutable slice
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows from top to bottom
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        // chunks_exact panics on a zero width, which only an empty grid has
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// Column `x` from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Flat indices of the up to 4 orthogonal neighbours of `idx`
+    #[inline]
+    pub fn neighbors4(&self, idx: usize) -> Neighbors {
+        self.neighbors(idx, &OFFSETS_4)
+    }
+
+    /// Flat indices of the up to 8 surrounding neighbours of `idx`
+    #[inline]
+    pub fn neighbors8(&self, idx: usize) -> Neighbors {
+        self.neighbors(idx, &OFFSETS_8)
+    }
+
+    fn neighbors(&self, idx: usize, offsets: &'static [(isize, isize)]) -> Neighbors {
+        let (x, y) = self.pos(idx);
+        Neighbors {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
+            offsets: offsets.iter(),
+        }
+    }
+
+    /// Apply `f` to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Swap rows and columns
+    pub fn transposed(&self) -> Self {
+        self.remapped(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotated_cw(&self) -> Self {
+        let height = self.height;
+        self.remapped(height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotate a quarter turn counter-clockwise
+    pub fn rotated_ccw(&self) -> Self {
+        let width = self.width;
+        self.remapped(self.height, width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirror left to right
+    pub fn flipped_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remapped(width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Build a `width` × `height` grid whose cell `(x, y)` is this grid's
+    /// cell at `source(x, y)`
+    fn remapped(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse one row of bytes per line
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows have different widths.
+    pub fn parse(input: &str) -> Self {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            cells.extend_from_slice(line.as_bytes());
+            check_row_width(&mut width, line.len(), height);
+            height += 1;
+        }
+
+        Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        }
+    }
+}
+
+/// Record the first row's width and panic if a later row differs
+fn check_row_width(width: &mut Option<usize>, row_width: usize, row: usize) {
+    match *width {
+        None => *width = Some(row_width),
+        Some(w) if w != row_width => panic!(
+            "Grid row {} has width {}, expected {}",
+            row + 1,
+            row_width,
+            w
+        ),
+        Some(_) => {}
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: usize) -> &T {
+        &self.cells[idx]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        &mut self.cells[idx]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Cell at `(x, y)`
+    ///
+    /// Panics if `x` is out of bounds even when the flat index would not be.
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "Column {} out of bounds", x);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "Column {} out of bounds", x);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Iterator over the in-bounds neighbours of a cell, as flat indices
+pub struct Neighbors {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    offsets: std::slice::Iter<'static, (isize, isize)>,
+}
+
+impl Iterator for Neighbors {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        for &(dx, dy) in self.offsets.by_ref() {
+            // Stepping left of column 0 wraps to a huge value and fails the check
+            let x = self.x.wrapping_add_signed(dx);
+            let y = self.y.wrapping_add_signed(dy);
+            if x < self.width && y < self.height {
+                return Some(y * self.width + x);
+            }
+        }
+        None
+    }
+}
+
+/// Cells that render as a single character
+pub trait Tile {
+    fn to_char(&self) -> char;
+}
+
+impl Tile for u8 {
+    fn to_char(&self) -> char {
+        *self as char
+    }
+}
+
+impl Tile for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+impl Tile for bool {
+    /// `#` for set cells, `.` for clear ones
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl<T: Tile> fmt::Display for Grid<T> {
+    /// One line per row, each terminated by a newline
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(Tile::to_char).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse("abc\ndef\n");
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[(0, 1)], b'd');
+        assert_eq!(grid[grid.idx(2, 1)], b'f');
+        assert_eq!(grid.pos(4), (1, 1));
+        assert_eq!(grid.get(1, 2), None);
+        assert_eq!(unsafe { *grid.get_unchecked(1, 0) }, b'b');
+        assert!(grid.contains(2, 1));
+        assert!(!grid.contains(-1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Grid row 2 has width 2, expected 3")]
+    fn test_parse_ragged() {
+        Grid::parse("abc\nde\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 3 out of bounds")]
+    fn test_index_column_out_of_bounds() {
+        // (3, 0) would otherwise alias (0, 1)
+        let grid = Grid::parse("abc\ndef");
+        let _ = grid[(3, 0)];
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0u8);
+        let corner: Vec<_> = grid.neighbors8(0).collect();
+        assert_eq!(corner, vec![1, 3, 4]);
+        let centre: Vec<_> = grid.neighbors8(4).collect();
+        assert_eq!(centre, vec![0, 1, 2, 3, 5, 6, 7, 8]);
+        let edge: Vec<_> = grid.neighbors4(5).collect();
+        assert_eq!(edge, vec![2, 4, 8]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse_with("ab\ncd\nef", |c| c);
+        assert_eq!(grid.row(1), &['c', 'd']);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+        assert_eq!(grid.position(|&c| c == 'e'), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = Grid::parse_with("ab\ncd\nef", |c| c);
+        assert_eq!(grid.transposed().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotated_cw().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotated_ccw().to_string(), "bdf\nace\n");
+        assert_eq!(grid.flipped_horizontal().to_string(), "ba\ndc\nfe\n");
+        assert_eq!(grid.rotated_cw().rotated_ccw(), grid);
+    }
+
+    #[test]
+    fn test_display_bool() {
+        let mut grid = Grid::new(2, 2, false);
+        grid[(1, 0)] = true;
+        assert_eq!(grid.to_string(), ".#\n..\n");
+    }
+}
@@ -27,10 +27,53 @@
 //! pub mod math;    // Number theory
 //! ```
 
-// Modules will be added here as common patterns emerge during daily implementations
-// Example:
-// pub mod grid;
-// pub mod parse;
+pub mod dot;
+pub mod exact_cover;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod ranges;
+pub mod rng;
+pub mod spatial;
+pub mod validate;
+pub mod viz;
+
+use std::fmt;
+
+/// A line rejected by the parser passed to [`parse_lines_strict`] or
+/// [`parse_lines_lenient`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// The trimmed text of the rejected line
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid input on line {}: {:?}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line a streaming parser rejected, as an
+/// [`InvalidData`](std::io::ErrorKind::InvalidData) error from its `io::Result`
+impl From<ParseError> for std::io::Error {
+    fn from(err: ParseError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Non-empty, trimmed lines paired with their 1-based line numbers
+fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
 
 /// Parse non-empty, trimmed lines from input and apply a transformation function.
 ///
@@ -40,6 +83,10 @@
 /// 3. Skipping empty lines
 /// 4. Parsing/transforming each line
 ///
+/// Lines the parser rejects are silently dropped. Use [`parse_lines_strict`]
+/// to fail on them instead, or [`parse_lines_lenient`] to find out which were
+/// skipped.
+///
 /// # Examples
 ///
 /// ```
@@ -57,19 +104,70 @@ pub fn parse_lines<T, F>(input: &str, mut parser: F) -> Vec<T>
 where
     F: FnMut(&str) -> Option<T>,
 {
-    input
-        .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                None
-            } else {
-                parser(line)
-            }
+    numbered_lines(input)
+        .filter_map(|(_, line)| parser(line))
+        .collect()
+}
+
+/// Like [`parse_lines`], but fail on the first line the parser rejects.
+///
+/// # Examples
+///
+/// ```
+/// use aoc2025::common::parse_lines_strict;
+///
+/// let parse = |line: &str| line.parse::<u32>().ok();
+/// assert_eq!(parse_lines_strict("1\n\n2\n", parse), Ok(vec![1, 2]));
+///
+/// let err = parse_lines_strict("1\n2x\n3\n", parse).unwrap_err();
+/// assert_eq!((err.line, err.text.as_str()), (2, "2x"));
+/// ```
+pub fn parse_lines_strict<T, F>(input: &str, mut parser: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Option<T>,
+{
+    numbered_lines(input)
+        .map(|(line_no, line)| {
+            parser(line).ok_or_else(|| ParseError {
+                line: line_no,
+                text: line.to_string(),
+            })
         })
         .collect()
 }
 
+/// Like [`parse_lines`], but also return every line the parser rejected.
+///
+/// # Examples
+///
+/// ```
+/// use aoc2025::common::parse_lines_lenient;
+///
+/// let (values, skipped) = parse_lines_lenient("1\n2x\n3\n", |line| line.parse::<u32>().ok());
+/// assert_eq!(values, vec![1, 3]);
+/// assert_eq!(skipped.len(), 1);
+/// assert_eq!(skipped[0].line, 2);
+/// ```
+pub fn parse_lines_lenient<T, F>(input: &str, mut parser: F) -> (Vec<T>, Vec<ParseError>)
+where
+    F: FnMut(&str) -> Option<T>,
+{
+    let mut values = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (line_no, line) in numbered_lines(input) {
+        match parser(line) {
+            Some(value) => values.push(value),
+            None => skipped.push(ParseError {
+                line: line_no,
+                text: line.to_string(),
+            }),
+        }
+    }
+
+    (values, skipped)
+}
+
 /// Macro to define standard benchmarks for a day's solution.
 ///
 /// This macro creates two benchmark functions:
@@ -134,3 +232,26 @@ macro_rules! define_day_benches {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines_variants_agree() {
+        let input = "  1 \n\nx\n2\n";
+        let parse = |line: &str| line.parse::<u32>().ok();
+
+        let (values, skipped) = parse_lines_lenient(input, parse);
+        assert_eq!(values, parse_lines(input, parse));
+        assert_eq!(
+            skipped,
+            vec![ParseError {
+                line: 3,
+                text: "x".to_string()
+            }]
+        );
+        assert_eq!(parse_lines_strict(input, parse), Err(skipped[0].clone()));
+        assert_eq!(skipped[0].to_string(), "Invalid input on line 3: \"x\"");
+    }
+}
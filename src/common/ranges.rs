@@ -0,0 +1,359 @@
+//! Sets of integers stored as sorted, disjoint inclusive intervals
+//!
+//! [`IntervalSet`] keeps its intervals normalised: sorted by start, with
+//! overlapping and adjacent intervals merged. Membership is a binary search
+//! and set algebra is a linear merge of the two interval lists.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::ranges::IntervalSet;
+//!
+//! let fresh: IntervalSet<u64> = [(3, 5), (10, 14), (16, 20), (12, 18)].into_iter().collect();
+//! assert_eq!(fresh.intervals(), &[(3, 5), (10, 20)]);
+//! assert!(fresh.contains(17));
+//! assert!(!fresh.contains(8));
+//! assert_eq!(fresh.total_len(), 14);
+//! ```
+
+/// Integer types that can bound an [`IntervalSet`]
+pub trait Discrete: Copy + Ord {
+    /// The next value, or `None` at the type's maximum
+    fn successor(self) -> Option<Self>;
+
+    /// The previous value, or `None` at the type's minimum
+    fn predecessor(self) -> Option<Self>;
+
+    /// Number of values in `start..=end`, which never overflows `u128`
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(start: Self, end: Self) -> u128 {
+                    // Widen through i128 so signed ranges crossing zero work
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers stored as normalised inclusive intervals
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a set from intervals that are already sorted and disjoint
+    fn from_sorted(sorted: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut set = Self::new();
+        for (start, end) in sorted {
+            set.push_merging(start, end);
+        }
+        set
+    }
+
+    /// Append an interval starting at or after the last one, merging if they
+    /// overlap or touch
+    fn push_merging(&mut self, start: T, end: T) {
+        if let Some(last) = self.intervals.last_mut() {
+            if last.1.successor().is_none_or(|next| start <= next) {
+                last.1 = last.1.max(end);
+                return;
+            }
+        }
+        self.intervals.push((start, end));
+    }
+
+    /// Add the inclusive interval `start..=end`
+    ///
+    /// Empty intervals (`start > end`) are ignored.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        // Intervals that end before `start - 1` are untouched on the left, and
+        // those starting after `end + 1` on the right
+        let left = self
+            .intervals
+            .partition_point(|&(_, e)| e.successor().is_some_and(|next| next < start));
+        let right = self
+            .intervals
+            .partition_point(|&(s, _)| s.predecessor().is_none_or(|prev| prev <= end));
+
+        let (mut start, mut end) = (start, end);
+        if left < right {
+            start = start.min(self.intervals[left].0);
+            end = end.max(self.intervals[right - 1].1);
+        }
+        self.intervals.splice(left..right, [(start, end)]);
+    }
+
+    /// Whether `value` is in the set
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// The normalised intervals, sorted by start
+    pub fn intervals(&self) -> &[(T, T)] {
+        &self.intervals
+    }
+
+    /// Iterate over the normalised intervals
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    /// Whether the set has no members
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set
+    pub fn total_len(&self) -> u128 {
+        self.iter().map(|(start, end)| T::span(start, end)).sum()
+    }
+
+    /// Integers in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut merged = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.0 <= y.0 => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            match next {
+                Some(interval) => merged.push(interval),
+                None => break,
+            }
+        }
+        Self::from_sorted(merged)
+    }
+
+    /// Integers in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a_start, a_end) = self.intervals[i];
+            let (b_start, b_end) = other.intervals[j];
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            if start <= end {
+                result.intervals.push((start, end));
+            }
+            // Advance whichever interval finishes first
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        result
+    }
+
+    /// Integers in `self` but not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let mut j = 0;
+        for (start, end) in self.iter() {
+            let mut start = Some(start);
+            // Skip intervals of `other` that end before this one starts
+            while j < other.intervals.len() && start.is_some_and(|s| other.intervals[j].1 < s) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(s) = start {
+                match other.intervals.get(k) {
+                    Some(&(cut_start, cut_end)) if cut_start <= end => {
+                        if s < cut_start {
+                            // cut_start > s, so it has a predecessor
+                            result.intervals.push((s, cut_start.predecessor().unwrap()));
+                        }
+                        start = cut_end.successor().filter(|&next| next <= end);
+                        k += 1;
+                    }
+                    _ => {
+                        result.intervals.push((s, end));
+                        break;
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<T: Discrete> FromIterator<(T, T)> for IntervalSet<T> {
+    /// Collect arbitrary inclusive intervals, sorting and merging them
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut intervals: Vec<(T, T)> = iter.into_iter().filter(|&(s, e)| s <= e).collect();
+        intervals.sort_unstable();
+        Self::from_sorted(intervals)
+    }
+}
+
+impl<T: Discrete> Extend<(T, T)> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = (T, T)>>(&mut self, iter: I) {
+        for (start, end) in iter {
+            self.insert(start, end);
+        }
+    }
+}
+
+impl<'a, T: Discrete> IntoIterator for &'a IntervalSet<T> {
+    type Item = (T, T);
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, (T, T)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_normalise() {
+        let s = set(&[(10, 14), (3, 5), (16, 20), (12, 18), (6, 6), (9, 8)]);
+        assert_eq!(s.intervals(), &[(3, 6), (10, 20)]);
+        assert_eq!(s.total_len(), 4 + 11);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = IntervalSet::new();
+        s.insert(10, 20);
+        s.insert(30, 40);
+        s.insert(50, 60);
+        assert_eq!(s.intervals(), &[(10, 20), (30, 40), (50, 60)]);
+
+        // Adjacent on the left, overlapping on the right
+        s.insert(21, 35);
+        assert_eq!(s.intervals(), &[(10, 40), (50, 60)]);
+
+        // Inside an existing interval
+        s.insert(52, 55);
+        assert_eq!(s.intervals(), &[(10, 40), (50, 60)]);
+
+        // In a gap, touching nothing
+        s.insert(45, 47);
+        assert_eq!(s.intervals(), &[(10, 40), (45, 47), (50, 60)]);
+
+        // Spanning everything
+        s.insert(0, 100);
+        assert_eq!(s.intervals(), &[(0, 100)]);
+
+        // Reversed bounds are empty
+        s.insert(200, 150);
+        assert_eq!(s.intervals(), &[(0, 100)]);
+    }
+
+    #[test]
+    fn test_insert_matches_collect() {
+        let raw = [
+            (5, 9),
+            (1, 2),
+            (11, 11),
+            (3, 4),
+            (20, 25),
+            (10, 10),
+            (15, 30),
+        ];
+        let mut inserted = IntervalSet::new();
+        inserted.extend(raw);
+        assert_eq!(inserted, set(&raw));
+        assert_eq!(inserted.intervals(), &[(1, 11), (15, 30)]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let s = set(&[(-5, -1), (3, 5), (10, 10)]);
+        let members: Vec<i64> = (-7..12).filter(|&x| s.contains(x)).collect();
+        assert_eq!(members, vec![-5, -4, -3, -2, -1, 3, 4, 5, 10]);
+        assert!(!IntervalSet::<i64>::new().contains(0));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set(&[(1, 5), (10, 20), (30, 30)]);
+        let b = set(&[(4, 12), (18, 25), (40, 50)]);
+
+        assert_eq!(a.union(&b).intervals(), &[(1, 25), (30, 30), (40, 50)]);
+        assert_eq!(
+            a.intersection(&b).intervals(),
+            &[(4, 5), (10, 12), (18, 20)]
+        );
+        assert_eq!(a.difference(&b).intervals(), &[(1, 3), (13, 17), (30, 30)]);
+        assert_eq!(b.difference(&a).intervals(), &[(6, 9), (21, 25), (40, 50)]);
+
+        // Check against brute force membership
+        for x in 0..55 {
+            assert_eq!(a.union(&b).contains(x), a.contains(x) || b.contains(x));
+            assert_eq!(
+                a.intersection(&b).contains(x),
+                a.contains(x) && b.contains(x)
+            );
+            assert_eq!(
+                a.difference(&b).contains(x),
+                a.contains(x) && !b.contains(x)
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_extremes() {
+        let mut s: IntervalSet<u64> = [(0, 10), (u64::MAX - 1, u64::MAX)].into_iter().collect();
+        assert_eq!(s.total_len(), 13);
+        s.insert(5, u64::MAX - 2);
+        assert_eq!(s.intervals(), &[(0, u64::MAX)]);
+        assert_eq!(s.total_len(), u64::MAX as u128 + 1);
+
+        let all: IntervalSet<u8> = [(0, 255)].into_iter().collect();
+        let top: IntervalSet<u8> = [(200, 255)].into_iter().collect();
+        assert_eq!(all.difference(&top).intervals(), &[(0, 199)]);
+        assert_eq!(top.difference(&all).intervals(), &[]);
+
+        let signed: IntervalSet<i8> = [(i8::MIN, i8::MAX)].into_iter().collect();
+        assert_eq!(signed.total_len(), 256);
+    }
+}
@@ -0,0 +1,151 @@
+//! Small seeded pseudo-random number generator
+//!
+//! [`Rng`] is PCG32 (XSH-RR): the 64-bit LCG the randomised tests already
+//! step by hand, with an output permutation that hides its weak low bits. It
+//! is fast, reproducible from a seed on every platform, and good enough for
+//! generating puzzle inputs; it is not for anything secret.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::rng::Rng;
+//!
+//! let mut a = Rng::new(7);
+//! let mut b = Rng::new(7);
+//! assert_eq!(a.next_u64(), b.next_u64());
+//!
+//! let roll = a.range(1, 6);
+//! assert!((1..=6).contains(&roll));
+//! ```
+
+const MULTIPLIER: u64 = 6364136223846793005;
+const INCREMENT: u64 = 1442695040888963407;
+
+/// Seeded PCG32 generator
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator; equal seeds give equal sequences
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    /// Next 32 random bits
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    /// Uniform value in `0..bound`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Empty range");
+        // Reject the top partial copy of 0..bound so every value is equally likely
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Uniform value in `low..=high`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low > high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "Empty range {}..={}", low, high);
+        let span = high.abs_diff(low);
+        match span.checked_add(1) {
+            Some(count) => low.wrapping_add(self.below(count) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// Uniform index into a collection of `len` items
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// `true` with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    /// Shuffle `items` in place (Fisher–Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_sequence() {
+        // The reference implementation's `pcg32_srandom_r(42, 721347520444481703)`,
+        // whose increment is the one used here
+        let mut rng = Rng::new(42);
+        let first: Vec<u32> = (0..3).map(|_| rng.next_u32()).collect();
+        assert_eq!(first, vec![0xc2f5_7bd6, 0x6b07_c4a9, 0x72b7_b29b]);
+        assert_ne!(Rng::new(43).next_u32(), first[0]);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [0usize; 6];
+        for _ in 0..6000 {
+            let roll = rng.range(1, 6);
+            seen[(roll - 1) as usize] += 1;
+        }
+        // Each face within 20% of its expected 1000
+        assert!(seen.iter().all(|&n| (800..1200).contains(&n)), "{:?}", seen);
+
+        assert_eq!(rng.range(-3, -3), -3);
+        rng.range(i64::MIN, i64::MAX);
+        assert!((0..10).all(|_| rng.below(1) == 0));
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        let mut rng = Rng::new(9);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_chance() {
+        let mut rng = Rng::new(3);
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2200..2800).contains(&hits), "{}", hits);
+        assert!(!(0..100).any(|_| rng.chance(0.0)));
+        assert!((0..100).all(|_| rng.chance(1.0)));
+    }
+}
@@ -0,0 +1,393 @@
+//! Spatial index for nearest-neighbour queries over integer points
+//!
+//! [`KdTree`] is a static k-d tree built once over a slice of points. Queries
+//! return indices into that slice with exact squared Euclidean distances, and
+//! ties are broken by the smaller index so results are deterministic.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::geometry::Point3;
+//! use aoc2025::common::spatial::KdTree;
+//!
+//! let points = [
+//!     Point3::new(0, 0, 0),
+//!     Point3::new(10, 0, 0),
+//!     Point3::new(1, 1, 0),
+//!     Point3::new(10, 2, 0),
+//! ];
+//! let tree = KdTree::new(&points);
+//! assert_eq!(tree.nearest(Point3::new(9, 1, 0), |_| true), Some((2, 1)));
+//!
+//! // Closest pairs, streamed in order of distance
+//! let pairs: Vec<_> = tree.pairs_by_distance().take(2).collect();
+//! assert_eq!(pairs, vec![(2, 0, 2), (4, 1, 3)]);
+//! ```
+
+use crate::common::geometry::{Point2, Point3};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A point type the tree can split on
+pub trait KdPoint: Copy {
+    /// Number of coordinates
+    const DIMS: usize;
+
+    /// The coordinate along `axis`, which is below `DIMS`
+    fn coord(&self, axis: usize) -> i64;
+
+    /// Squared Euclidean distance
+    fn distance_squared(self, other: Self) -> i64;
+}
+
+impl KdPoint for Point2 {
+    const DIMS: usize = 2;
+
+    fn coord(&self, axis: usize) -> i64 {
+        [self.x, self.y][axis]
+    }
+
+    fn distance_squared(self, other: Self) -> i64 {
+        Point2::distance_squared(self, other)
+    }
+}
+
+impl KdPoint for Point3 {
+    const DIMS: usize = 3;
+
+    fn coord(&self, axis: usize) -> i64 {
+        [self.x, self.y, self.z][axis]
+    }
+
+    fn distance_squared(self, other: Self) -> i64 {
+        Point3::distance_squared(self, other)
+    }
+}
+
+/// A k-d tree over a fixed set of points
+///
+/// The tree is implicit: `order` is a permutation of point indices in which
+/// every subtree is a contiguous range with its splitting point in the middle.
+pub struct KdTree<'a, P> {
+    points: &'a [P],
+    order: Vec<usize>,
+}
+
+impl<'a, P: KdPoint> KdTree<'a, P> {
+    /// Build a tree over `points` in O(n log n)
+    pub fn new(points: &'a [P]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self { points, order }
+    }
+
+    /// The indexed points
+    pub fn points(&self) -> &'a [P] {
+        self.points
+    }
+
+    /// Number of indexed points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the tree indexes no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Nearest point for which `accept` returns true, as `(distance², index)`
+    pub fn nearest(&self, query: P, accept: impl Fn(usize) -> bool) -> Option<(i64, usize)> {
+        self.nearest_within(query, i64::MAX, accept)
+    }
+
+    /// Like [`KdTree::nearest`], but only considering points at most `bound`
+    /// (squared) away
+    ///
+    /// A tight bound lets the search skip most of the tree, which is what makes
+    /// filtered queries cheap when nearby points are rejected.
+    pub fn nearest_within(
+        &self,
+        query: P,
+        bound: i64,
+        accept: impl Fn(usize) -> bool,
+    ) -> Option<(i64, usize)> {
+        let mut best = None;
+        let mut search = Search {
+            tree: self,
+            query,
+            bound,
+            accept: &accept,
+        };
+        search.nearest(0, self.order.len(), 0, &mut best);
+        best
+    }
+
+    /// The `k` nearest accepted points, closest first, as `(distance², index)`
+    pub fn k_nearest(
+        &self,
+        query: P,
+        k: usize,
+        accept: impl Fn(usize) -> bool,
+    ) -> Vec<(i64, usize)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        let mut search = Search {
+            tree: self,
+            query,
+            bound: i64::MAX,
+            accept: &accept,
+        };
+        search.k_nearest(0, self.order.len(), 0, k, &mut heap);
+        heap.into_sorted_vec()
+    }
+
+    /// Every pair of distinct points in increasing order of distance, as
+    /// `(distance², i, j)` with `i < j`
+    ///
+    /// Pairs are generated lazily from growing per-point neighbour lists, so
+    /// taking the first k pairs costs about O((n + k) log n) rather than
+    /// materialising all n² pairs.
+    pub fn pairs_by_distance(&self) -> ClosestPairs<'_, 'a, P> {
+        let mut pairs = ClosestPairs {
+            tree: self,
+            neighbors: vec![Vec::new(); self.len()],
+            requested: vec![0; self.len()],
+            heap: BinaryHeap::with_capacity(self.len()),
+        };
+        for i in 0..self.len() {
+            pairs.refill(i, INITIAL_NEIGHBORS);
+            pairs.push_next(i, 0);
+        }
+        pairs
+    }
+}
+
+/// Recursively arrange `order` so each range is split at its median
+fn build<P: KdPoint>(points: &[P], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % P::DIMS;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].coord(axis));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/// State shared by the recursive queries
+struct Search<'t, 'a, P, F> {
+    tree: &'t KdTree<'a, P>,
+    query: P,
+    bound: i64,
+    accept: &'t F,
+}
+
+impl<P: KdPoint, F: Fn(usize) -> bool> Search<'_, '_, P, F> {
+    /// Split the subtree `order[lo..hi]` at `depth`
+    ///
+    /// Returns the position of the splitting point, the child ranges with the
+    /// one on the query's side first, and the squared distance from the query
+    /// to the splitting plane.
+    fn split(&self, lo: usize, hi: usize, depth: usize) -> (usize, [(usize, usize); 2], i64) {
+        let mid = lo + (hi - lo) / 2;
+        let point = self.tree.points[self.tree.order[mid]];
+        let axis = depth % P::DIMS;
+        let diff = self.query.coord(axis) - point.coord(axis);
+        let children = if diff < 0 {
+            [(lo, mid), (mid + 1, hi)]
+        } else {
+            [(mid + 1, hi), (lo, mid)]
+        };
+        (mid, children, diff.saturating_mul(diff))
+    }
+
+    fn nearest(&mut self, lo: usize, hi: usize, depth: usize, best: &mut Option<(i64, usize)>) {
+        if lo >= hi {
+            return;
+        }
+        let (mid, [near, far], plane) = self.split(lo, hi, depth);
+
+        let index = self.tree.order[mid];
+        if (self.accept)(index) {
+            let candidate = (self.query.distance_squared(self.tree.points[index]), index);
+            if candidate.0 <= self.bound && best.is_none_or(|b| candidate < b) {
+                *best = Some(candidate);
+            }
+        }
+
+        self.nearest(near.0, near.1, depth + 1, best);
+        let limit = best.map_or(self.bound, |b| b.0);
+        if plane <= limit {
+            self.nearest(far.0, far.1, depth + 1, best);
+        }
+    }
+
+    fn k_nearest(
+        &mut self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        k: usize,
+        heap: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let (mid, [near, far], plane) = self.split(lo, hi, depth);
+
+        let index = self.tree.order[mid];
+        if (self.accept)(index) {
+            let candidate = (self.query.distance_squared(self.tree.points[index]), index);
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if heap.peek().is_some_and(|&worst| candidate < worst) {
+                heap.pop();
+                heap.push(candidate);
+            }
+        }
+
+        self.k_nearest(near.0, near.1, depth + 1, k, heap);
+        let full = heap.len() == k;
+        if !full || heap.peek().is_some_and(|&(worst, _)| plane <= worst) {
+            self.k_nearest(far.0, far.1, depth + 1, k, heap);
+        }
+    }
+}
+
+/// Neighbours fetched per point before the first refill
+const INITIAL_NEIGHBORS: usize = 8;
+
+/// Iterator returned by [`KdTree::pairs_by_distance`]
+pub struct ClosestPairs<'t, 'a, P> {
+    tree: &'t KdTree<'a, P>,
+    /// Each point's nearest neighbours found so far, closest first
+    neighbors: Vec<Vec<(i64, usize)>>,
+    /// How many neighbours were last asked for per point
+    requested: Vec<usize>,
+    /// Next unconsumed neighbour of each point, as `(distance², point, position)`
+    heap: BinaryHeap<Reverse<(i64, usize, usize)>>,
+}
+
+impl<P: KdPoint> ClosestPairs<'_, '_, P> {
+    /// Replace point `i`'s neighbour list with its `k` nearest neighbours
+    ///
+    /// Ties are broken by index, so a longer list extends the shorter one.
+    fn refill(&mut self, i: usize, k: usize) {
+        let query = self.tree.points[i];
+        self.requested[i] = k;
+        self.neighbors[i] = self.tree.k_nearest(query, k, |j| j != i);
+    }
+
+    /// Queue neighbour `pos` of point `i`, fetching more neighbours if needed
+    fn push_next(&mut self, i: usize, pos: usize) {
+        if pos == self.neighbors[i].len() {
+            // A list shorter than requested already holds every other point
+            if pos < self.requested[i] {
+                return;
+            }
+            self.refill(i, pos * 2);
+        }
+        let (dist, _) = self.neighbors[i][pos];
+        self.heap.push(Reverse((dist, i, pos)));
+    }
+}
+
+impl<P: KdPoint> Iterator for ClosestPairs<'_, '_, P> {
+    type Item = (i64, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every pair is queued from both ends; only yield it from the lower one
+        while let Some(Reverse((dist, i, pos))) = self.heap.pop() {
+            let j = self.neighbors[i][pos].1;
+            self.push_next(i, pos + 1);
+            if i < j {
+                return Some((dist, i, j));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random points in a small cube, with duplicates
+    fn scattered(n: usize, seed: u64) -> Vec<Point3> {
+        let mut state = seed;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 50) as i64 - 25
+        };
+        (0..n)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    fn brute_force_sorted(points: &[Point3], query: Point3) -> Vec<(i64, usize)> {
+        let mut all: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (query.distance_squared(p), i))
+            .collect();
+        all.sort_unstable();
+        all
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let points = scattered(300, 1);
+        let tree = KdTree::new(&points);
+        for query in scattered(50, 2) {
+            let expected = brute_force_sorted(&points, query);
+            assert_eq!(tree.nearest(query, |_| true), Some(expected[0]));
+
+            // Filtered: only even indices
+            let even = expected.iter().copied().find(|&(_, i)| i % 2 == 0);
+            assert_eq!(tree.nearest(query, |i| i % 2 == 0), even);
+
+            // Bounded: nothing closer than the bound means no result
+            let bound = expected[0].0 - 1;
+            assert_eq!(tree.nearest_within(query, bound, |_| true), None);
+
+            assert_eq!(tree.k_nearest(query, 7, |_| true), expected[..7].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_pairs_by_distance_matches_brute_force() {
+        let points = scattered(120, 3);
+        let tree = KdTree::new(&points);
+
+        let mut expected = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                expected.push((points[i].distance_squared(points[j]), i, j));
+            }
+        }
+        expected.sort_unstable();
+
+        let pairs: Vec<_> = tree.pairs_by_distance().collect();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn test_small_trees() {
+        let empty: [Point2; 0] = [];
+        let tree = KdTree::new(&empty);
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(Point2::ORIGIN, |_| true), None);
+        assert_eq!(tree.pairs_by_distance().next(), None);
+
+        let single = [Point2::new(3, 4)];
+        let tree = KdTree::new(&single);
+        assert_eq!(tree.nearest(Point2::ORIGIN, |_| true), Some((25, 0)));
+        assert_eq!(tree.pairs_by_distance().next(), None);
+    }
+}
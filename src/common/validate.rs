@@ -0,0 +1,401 @@
+//! Input format validation with compiler-style diagnostics
+//!
+//! Day parsers assume well-formed input and panic on anything else. Validators
+//! check the format up front and point at the first problem:
+//!
+//! ```text
+//! error: expected `L` or `R`, found `X`
+//!  --> day01.txt:3:1
+//!   |
+//! 3 | X42
+//!   | ^ expected `L` or `R`
+//! ```
+//!
+//! Validators are written against [`Cursor`], which walks a single line and
+//! turns every failed expectation into a [`Diagnostic`] at the current column.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::validate::{Cursor, Diagnostic};
+//!
+//! fn validate(input: &str) -> Result<(), Diagnostic> {
+//!     for (line_no, line) in input.lines().enumerate() {
+//!         let mut cursor = Cursor::new(line_no + 1, line);
+//!         cursor.number()?;
+//!         cursor.finish()?;
+//!     }
+//!     Ok(())
+//! }
+//!
+//! assert!(validate("12\n34\n").is_ok());
+//! let err = validate("12\n3x\n").unwrap_err();
+//! assert_eq!((err.line, err.column), (2, 2));
+//! ```
+
+use std::fmt;
+
+/// A format error at a specific line and column of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// Number of characters to underline (at least 1)
+    pub width: usize,
+    /// What the validator expected at this position
+    pub expected: String,
+    /// Description of what was found instead
+    pub found: String,
+    /// The offending line
+    pub source: String,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic at byte offset `pos` of `text`
+    ///
+    /// The "found" description is derived from the character at `pos`.
+    pub fn at(line: usize, text: &str, pos: usize, expected: impl Into<String>) -> Self {
+        let found = match text[pos..].chars().next() {
+            Some(' ') => "a space".to_string(),
+            Some('\t') => "a tab".to_string(),
+            Some(ch) => format!("`{}`", ch),
+            None => "end of line".to_string(),
+        };
+        Self {
+            line,
+            column: text[..pos].chars().count() + 1,
+            width: 1,
+            expected: expected.into(),
+            found,
+            source: text.to_string(),
+        }
+    }
+
+    /// Create a diagnostic underlining `len` bytes of `text` starting at `pos`
+    pub fn span(
+        line: usize,
+        text: &str,
+        pos: usize,
+        len: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column: text[..pos].chars().count() + 1,
+            width: text[pos..pos + len].chars().count().max(1),
+            expected: expected.into(),
+            found: found.into(),
+            source: text.to_string(),
+        }
+    }
+
+    /// Create a diagnostic just past the last line of `input`
+    ///
+    /// Used when the input ends before a required section.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self {
+            line: input.lines().count() + 1,
+            column: 1,
+            width: 1,
+            expected: expected.into(),
+            found: "end of input".to_string(),
+            source: String::new(),
+        }
+    }
+
+    /// Render the diagnostic with the input's name, e.g. a file path
+    pub fn render(&self, origin: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let padding: String = self
+            .source
+            .chars()
+            .take(self.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{} expected {}\n",
+            self,
+            gutter,
+            origin,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.source,
+            gutter,
+            padding,
+            "^".repeat(self.width),
+            self.expected
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Scanner over a single input line
+///
+/// Every `expect_*` method either consumes what it expects or returns a
+/// [`Diagnostic`] pointing at the current position.
+pub struct Cursor<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Start scanning `text`, which is line `line` (1-based) of the input
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Self { line, text, pos: 0 }
+    }
+
+    /// Byte offset of the cursor within the line
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Next character without consuming it
+    pub fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Whether the whole line has been consumed
+    pub fn is_done(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// Diagnostic at the current position
+    pub fn error(&self, expected: impl Into<String>) -> Diagnostic {
+        Diagnostic::at(self.line, self.text, self.pos, expected)
+    }
+
+    /// Diagnostic underlining the bytes from `start` to the current position
+    pub fn error_since(
+        &self,
+        start: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Diagnostic {
+        Diagnostic::span(
+            self.line,
+            self.text,
+            start,
+            self.pos - start,
+            expected,
+            found,
+        )
+    }
+
+    /// Consume `ch` if it is next
+    pub fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += ch.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `ch` or fail
+    pub fn expect(&mut self, ch: char) -> Result<(), Diagnostic> {
+        if self.eat(ch) {
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", ch)))
+        }
+    }
+
+    /// Consume a literal string or fail
+    pub fn expect_str(&mut self, literal: &str) -> Result<(), Diagnostic> {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", literal)))
+        }
+    }
+
+    /// Consume one of `allowed` and return it, or fail with `expected`
+    pub fn one_of(&mut self, allowed: &[char], expected: &str) -> Result<char, Diagnostic> {
+        match self.peek() {
+            Some(ch) if allowed.contains(&ch) => {
+                self.pos += ch.len_utf8();
+                Ok(ch)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// Consume characters while `pred` holds and return them
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while let Some(ch) = self.peek() {
+            if !pred(ch) {
+                break;
+            }
+            self.pos += ch.len_utf8();
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Consume an unsigned decimal number that fits in a `u64`
+    pub fn number(&mut self) -> Result<u64, Diagnostic> {
+        let start = self.pos;
+        let digits = self.take_while(|ch| ch.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("a number"));
+        }
+        digits
+            .parse()
+            .map_err(|_| self.error_since(start, "a number that fits in 64 bits", "an overflow"))
+    }
+
+    /// Consume an unsigned decimal number no greater than `max`
+    ///
+    /// For parsers that store values in narrower types such as `i32`.
+    pub fn number_at_most(&mut self, max: u64) -> Result<u64, Diagnostic> {
+        let start = self.pos;
+        let value = self.number()?;
+        if value > max {
+            return Err(self.error_since(
+                start,
+                format!("a number no greater than {}", max),
+                format!("{}", value),
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Consume an optionally negative decimal number that fits in an `i64`
+    pub fn signed(&mut self) -> Result<i64, Diagnostic> {
+        let start = self.pos;
+        let negative = self.eat('-');
+        let magnitude = self.number()?;
+        let value = i64::try_from(magnitude)
+            .map_err(|_| self.error_since(start, "a number that fits in 64 bits", "an overflow"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Skip any spaces
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|ch| ch == ' ');
+    }
+
+    /// Fail unless the whole line has been consumed
+    pub fn finish(&self) -> Result<(), Diagnostic> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Check that every line is non-empty and uses only `allowed` characters
+///
+/// Rows must all have the same width as the first. Trailing blank lines are
+/// permitted; blank lines inside the grid are not.
+pub fn grid(input: &str, allowed: &[char], expected: &str) -> Result<(), Diagnostic> {
+    let mut width = None;
+    let mut lines = input.lines().enumerate().peekable();
+
+    while let Some((idx, line)) = lines.next() {
+        let line_no = idx + 1;
+        if line.is_empty() {
+            if lines.clone().all(|(_, rest)| rest.is_empty()) {
+                break;
+            }
+            return Err(Diagnostic::at(line_no, line, 0, "a grid row"));
+        }
+
+        let mut cursor = Cursor::new(line_no, line);
+        while !cursor.is_done() {
+            cursor.one_of(allowed, expected)?;
+        }
+
+        let len = line.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(w) if w != len => {
+                let pos = line.char_indices().nth(w).map_or(line.len(), |(i, _)| i);
+                return Err(Diagnostic::at(
+                    line_no,
+                    line,
+                    pos,
+                    format!("a row of width {}", w),
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    if width.is_none() {
+        return Err(Diagnostic::end_of_input(input, "a grid row"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_numbers() {
+        let mut cursor = Cursor::new(1, "12,-34");
+        assert_eq!(cursor.number(), Ok(12));
+        assert!(cursor.expect(',').is_ok());
+        assert_eq!(cursor.signed(), Ok(-34));
+        assert!(cursor.finish().is_ok());
+    }
+
+    #[test]
+    fn test_cursor_errors() {
+        let mut cursor = Cursor::new(4, "12;3");
+        cursor.number().unwrap();
+        let err = cursor.expect(',').unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+        assert_eq!(err.to_string(), "expected `,`, found `;`");
+
+        let mut cursor = Cursor::new(1, "99999999999999999999");
+        let err = cursor.number().unwrap_err();
+        assert_eq!((err.column, err.width), (1, 20));
+    }
+
+    #[test]
+    fn test_render() {
+        let err = Diagnostic::at(3, "R1x", 2, "a digit");
+        let expected = "\
+error: expected a digit, found `x`
+ --> input.txt:3:3
+  |
+3 | R1x
+  |   ^ expected a digit
+";
+        assert_eq!(err.render("input.txt"), expected);
+    }
+
+    #[test]
+    fn test_grid() {
+        assert!(grid("..#\n#..\n\n", &['.', '#'], "`.` or `#`").is_ok());
+
+        let err = grid("..#\n#.\n", &['.', '#'], "`.` or `#`").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a row of width 3");
+
+        let err = grid("..#\n\n#..\n", &['.', '#'], "`.` or `#`").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = grid("..#\n.x.\n", &['.', '#'], "`.` or `#`").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+}
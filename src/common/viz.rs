@@ -0,0 +1,756 @@
+//! Rendering grid frames for the terminal and as images
+//!
+//! Days that can show their work draw each step as a [`Frame`], a
+//! [`Grid`] of colours, and hand it to an [`Observer`]. A [`Recorder`]
+//! collects the frames, which can then be printed with ANSI colours or
+//! written as PPM or PNG images, or as an animated GIF. All three encoders are
+//! implemented here, so no image crates are needed:
+//!
+//! - PPM is the raw binary `P6` format
+//! - PNG stores the pixels in uncompressed deflate blocks, trading file size
+//!   for a few lines of code
+//! - GIF uses LZW, as the format requires, with one palette for all frames
+//!
+//! Days whose answers are geometry rather than grids draw vector images
+//! instead, with the [`Svg`] builder.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::common::grid::Grid;
+//! use aoc2025::common::viz::{self, Observer, Recorder, Rgb};
+//!
+//! let mut recorder = Recorder::new();
+//! let mut frame = Grid::new(2, 2, Rgb::BLACK);
+//! recorder.frame(frame.clone());
+//! frame[(1, 1)] = Rgb(255, 0, 0);
+//! recorder.frame(frame);
+//!
+//! let mut gif = Vec::new();
+//! viz::write_gif(&mut gif, &recorder.frames, 10).unwrap();
+//! assert!(gif.starts_with(b"GIF89a"));
+//! ```
+
+use crate::common::grid::Grid;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// An 8-bit RGB colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+}
+
+/// One picture of a solve, a colour per cell
+pub type Frame = Grid<Rgb>;
+
+/// Receives the frames a solver draws
+pub trait Observer {
+    /// Take the next frame
+    fn frame(&mut self, frame: Frame);
+
+    /// Whether another frame is wanted
+    ///
+    /// Solvers check this before drawing, so observers that have seen enough
+    /// save them the work.
+    fn wants_frame(&self) -> bool {
+        true
+    }
+}
+
+/// Draw a frame with `render` and send it, if there is an observer wanting one
+///
+/// Solvers take an `Option<&mut dyn Observer>` and call this at each step, so
+/// nothing is drawn when no one is watching.
+pub fn emit(observer: &mut Option<&mut dyn Observer>, render: impl FnOnce() -> Frame) {
+    if let Some(observer) = observer {
+        if observer.wants_frame() {
+            observer.frame(render());
+        }
+    }
+}
+
+/// Observer keeping every frame, optionally up to a limit
+#[derive(Debug, Default)]
+pub struct Recorder {
+    pub frames: Vec<Frame>,
+    limit: Option<usize>,
+}
+
+impl Recorder {
+    /// Create a recorder keeping every frame
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a recorder keeping at most `limit` frames
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            frames: Vec::new(),
+            limit: Some(limit),
+        }
+    }
+}
+
+impl Observer for Recorder {
+    fn frame(&mut self, frame: Frame) {
+        if self.wants_frame() {
+            self.frames.push(frame);
+        }
+    }
+
+    fn wants_frame(&self) -> bool {
+        self.limit.is_none_or(|limit| self.frames.len() < limit)
+    }
+}
+
+/// Draw a grid, colouring each cell with `colour`
+pub fn render<T>(grid: &Grid<T>, colour: impl FnMut(&T) -> Rgb) -> Frame {
+    grid.map(colour)
+}
+
+/// Enlarge a frame so each cell becomes a `scale` × `scale` block of pixels
+pub fn upscale(frame: &Frame, scale: usize) -> Frame {
+    let scale = scale.max(1);
+    let mut pixels = Vec::with_capacity(frame.len() * scale * scale);
+    for row in frame.rows() {
+        let line: Vec<Rgb> = row
+            .iter()
+            .flat_map(|&colour| std::iter::repeat_n(colour, scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    Grid::from_vec(frame.width() * scale, frame.height() * scale, pixels)
+}
+
+/// Render a frame for a true-colour terminal
+///
+/// Each character is an upper half block coloured with two cells, the top one
+/// as foreground and the bottom one as background, so cells come out roughly
+/// square. An odd last row is drawn over black.
+pub fn ansi(frame: &Frame) -> String {
+    let mut out = String::new();
+    for y in (0..frame.height()).step_by(2) {
+        for x in 0..frame.width() {
+            let Rgb(r, g, b) = frame[(x, y)];
+            let Rgb(br, bg, bb) = frame.get(x, y + 1).copied().unwrap_or(Rgb::BLACK);
+            out.push_str(&format!(
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                r, g, b, br, bg, bb
+            ));
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Builder for an SVG image in puzzle coordinates
+///
+/// The view box is fitted to the given bounds, so shapes are added in the
+/// coordinates the puzzle uses, with `y` growing downwards. Stroke widths are
+/// in screen pixels whatever the scale.
+///
+/// # Examples
+///
+/// ```
+/// use aoc2025::common::viz::Svg;
+///
+/// let mut svg = Svg::new((0, 0), (100, 50), 400);
+/// svg.polygon(&[(0, 0), (100, 0), (100, 50)], "fill:#cfc;stroke:#393");
+/// svg.circle((100, 50), 2.0, "fill:red");
+/// let text = svg.finish();
+/// assert!(text.starts_with("<svg"));
+/// assert!(text.contains("points=\"0,0 100,0 100,50\""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Svg {
+    min: (i64, i64),
+    max: (i64, i64),
+    width: u32,
+    body: String,
+}
+
+impl Svg {
+    /// Create an image covering `min` to `max`, `width` pixels wide
+    pub fn new(min: (i64, i64), max: (i64, i64), width: u32) -> Self {
+        Self {
+            min,
+            max,
+            width,
+            body: String::new(),
+        }
+    }
+
+    /// Largest side of the bounds, for sizing markers relative to the drawing
+    pub fn extent(&self) -> i64 {
+        (self.max.0 - self.min.0)
+            .max(self.max.1 - self.min.1)
+            .max(1)
+    }
+
+    /// Add a closed polygon through `points`
+    pub fn polygon(&mut self, points: &[(i64, i64)], style: &str) {
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        self.element(format!("<polygon points=\"{}\"", points.join(" ")), style);
+    }
+
+    /// Add a rectangle with top-left corner `corner`
+    pub fn rect(&mut self, corner: (i64, i64), width: i64, height: i64, style: &str) {
+        self.element(
+            format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                corner.0, corner.1, width, height
+            ),
+            style,
+        );
+    }
+
+    /// Add a circle around `centre`
+    pub fn circle(&mut self, centre: (i64, i64), radius: f64, style: &str) {
+        self.element(
+            format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"",
+                centre.0, centre.1, radius
+            ),
+            style,
+        );
+    }
+
+    fn element(&mut self, open: String, style: &str) {
+        self.body.push_str(&format!(
+            "  {} style=\"vector-effect:non-scaling-stroke;{}\"/>\n",
+            open, style
+        ));
+    }
+
+    /// The finished document, with a margin around the bounds
+    pub fn finish(self) -> String {
+        let margin = self.extent() / 50 + 1;
+        let (x, y) = (self.min.0 - margin, self.min.1 - margin);
+        let view_width = self.max.0 - self.min.0 + 2 * margin;
+        let view_height = self.max.1 - self.min.1 + 2 * margin;
+        let height = (self.width as i64 * view_height / view_width).max(1);
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"{} {} {} {}\">\n{}</svg>\n",
+            self.width, height, x, y, view_width, view_height, self.body
+        )
+    }
+}
+
+/// Image formats a frame sequence can be saved in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+/// Write each frame to `dir` as `frame0000.ext`, `frame0001.ext`, ...
+///
+/// Creates `dir` if needed and returns the paths written.
+pub fn save_frames(dir: &Path, frames: &[Frame], format: ImageFormat) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    frames
+        .iter()
+        .enumerate()
+        .map(|(idx, frame)| {
+            let path = dir.join(format!("frame{:04}.{}", idx, format.extension()));
+            let mut out = BufWriter::new(File::create(&path)?);
+            match format {
+                ImageFormat::Ppm => write_ppm(&mut out, frame)?,
+                ImageFormat::Png => write_png(&mut out, frame)?,
+            }
+            out.flush()?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Write a frame as a binary PPM (`P6`) image
+pub fn write_ppm(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", frame.width(), frame.height())?;
+    let bytes: Vec<u8> = frame
+        .cells()
+        .iter()
+        .flat_map(|&Rgb(r, g, b)| [r, g, b])
+        .collect();
+    out.write_all(&bytes)
+}
+
+// PNG
+
+/// Write a frame as an 8-bit RGB PNG image
+///
+/// The image data is zlib-wrapped deflate in stored (uncompressed) blocks, with
+/// no filtering on any row.
+pub fn write_png(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(frame.width() as u32).to_be_bytes());
+    header.extend_from_slice(&(frame.height() as u32).to_be_bytes());
+    // 8 bits per channel, RGB, deflate, adaptive filtering, no interlace
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_png_chunk(out, b"IHDR", &header)?;
+
+    let mut raw = Vec::with_capacity(frame.height() * (frame.width() * 3 + 1));
+    for row in frame.rows() {
+        raw.push(0); // Filter type: none
+        raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+    }
+    write_png_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+    write_png_chunk(out, b"IEND", &[])
+}
+
+/// Write one chunk: length, type, data and the CRC of type and data
+fn write_png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32_update(crc32_update(!0, kind), data);
+    out.write_all(&(!crc).to_be_bytes())
+}
+
+/// Wrap `data` in a zlib stream of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65_535;
+
+    // CMF/FLG: deflate with a 32K window, no dictionary, checksum multiple of 31
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Feed `data` into a running CRC-32 (IEEE, reflected), without the final
+/// inversion
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+/// Adler-32 checksum, as zlib streams end with
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65_521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 bytes is the most that can be summed before `b` could overflow
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+// GIF
+
+/// Write frames as a looping animated GIF, `delay` hundredths of a second apart
+///
+/// Frames may differ in size; each is drawn at the top-left of a screen as
+/// large as the largest, with the rest black. All frames share one palette, so
+/// together they may use at most 256 colours (black included).
+pub fn write_gif(out: &mut impl Write, frames: &[Frame], delay: u16) -> io::Result<()> {
+    let width = frames.iter().map(Grid::width).max().unwrap_or(0);
+    let height = frames.iter().map(Grid::height).max().unwrap_or(0);
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "GIF frames are limited to 65535 pixels a side",
+        ));
+    }
+
+    // Shared palette, black first so padding is index 0
+    let mut palette = vec![Rgb::BLACK];
+    let mut index = HashMap::from([(Rgb::BLACK, 0u8)]);
+    for frame in frames {
+        for &colour in frame.cells() {
+            if let Entry::Vacant(entry) = index.entry(colour) {
+                if palette.len() == 256 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "GIF frames use more than 256 colours",
+                    ));
+                }
+                entry.insert(palette.len() as u8);
+                palette.push(colour);
+            }
+        }
+    }
+    let bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1);
+    palette.resize(1 << bits, Rgb::BLACK);
+
+    out.write_all(b"GIF89a")?;
+    out.write_all(&(width as u16).to_le_bytes())?;
+    out.write_all(&(height as u16).to_le_bytes())?;
+    // Global colour table present, 8-bit source colour, table size 2^bits
+    out.write_all(&[0xf0 | (bits as u8 - 1), 0, 0])?;
+    for &Rgb(r, g, b) in &palette {
+        out.write_all(&[r, g, b])?;
+    }
+
+    // Loop forever
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    let mut pixels = vec![0u8; width * height];
+    for frame in frames {
+        pixels.fill(0);
+        for (y, row) in frame.rows().enumerate() {
+            for (x, colour) in row.iter().enumerate() {
+                pixels[y * width + x] = index[colour];
+            }
+        }
+
+        // Graphic control: no disposal, the delay, no transparency
+        out.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+
+        // Image descriptor covering the whole screen, no local colour table
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&(width as u16).to_le_bytes())?;
+        out.write_all(&(height as u16).to_le_bytes())?;
+        out.write_all(&[0])?;
+
+        let min_code_size = bits.max(2) as u8;
+        out.write_all(&[min_code_size])?;
+        for block in lzw_encode(&pixels, min_code_size).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+
+    out.write_all(&[0x3b])
+}
+
+/// Compress palette indices with GIF's variable-width LZW
+///
+/// Codes start one bit wider than `min_code_size` and grow as the table
+/// fills, up to 12 bits, when a clear code resets it.
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter::default();
+    let mut width = min_code_size as u32 + 1;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+
+    out.write(clear, width);
+    let mut current: Option<u16> = None;
+    for &index in indices {
+        let Some(prefix) = current else {
+            current = Some(index as u16);
+            continue;
+        };
+        if let Some(&code) = table.get(&(prefix, index)) {
+            current = Some(code);
+            continue;
+        }
+
+        out.write(prefix, width);
+        if next < MAX_CODES {
+            table.insert((prefix, index), next);
+            // The decoder widens once the code it would assign next needs it
+            if next == 1 << width && width < 12 {
+                width += 1;
+            }
+            next += 1;
+        } else {
+            out.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = min_code_size as u32 + 1;
+        }
+        current = Some(index as u16);
+    }
+    if let Some(prefix) = current {
+        out.write(prefix, width);
+    }
+    out.write(end, width);
+    out.finish()
+}
+
+/// Packs codes least significant bit first, as GIF expects
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    used: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.buffer |= (code as u32) << self.used;
+        self.used += width;
+        while self.used >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.used -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.used > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// GIF LZW decoder, to check the encoder round-trips
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut width = min_code_size as u32 + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.push(Vec::new());
+            table.push(Vec::new());
+        };
+        reset(&mut table);
+
+        let (mut buffer, mut used, mut pos) = (0u32, 0u32, 0);
+        let mut out = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            while used < width {
+                buffer |= (data[pos] as u32) << used;
+                pos += 1;
+                used += 8;
+            }
+            let code = (buffer & ((1 << width) - 1)) as usize;
+            buffer >>= width;
+            used -= width;
+
+            if code == clear {
+                reset(&mut table);
+                width = min_code_size as u32 + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = prev.clone();
+                    entry.push(prev[0]);
+                    entry
+                }
+                (None, None) => panic!("Code {} before any output", code),
+            };
+            out.extend_from_slice(&entry);
+            if let Some(mut prev) = previous.take() {
+                prev.push(entry[0]);
+                table.push(prev);
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let mut state = 3u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as u8
+        };
+
+        // Runs and noise, long enough to fill the table and clear it
+        let runs: Vec<u8> = (0..20_000).map(|i| ((i / 37) % 3) as u8).collect();
+        let noise: Vec<u8> = (0..20_000).map(|_| next() % 16).collect();
+        for (indices, min_code_size) in [(&runs, 2), (&noise, 4), (&vec![1u8], 2), (&vec![], 2)] {
+            let encoded = lzw_encode(indices, min_code_size);
+            assert_eq!(&lzw_decode(&encoded, min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(!crc32_update(!0, b"IEND"), 0xAE42_6082);
+        assert_eq!(!crc32_update(!0, b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(adler32(&[0xff; 20_000]), 0x9F51_D664);
+    }
+
+    #[test]
+    fn test_zlib_stored_blocks() {
+        let data = vec![7u8; 70_000];
+        let stream = zlib_stored(&data);
+        assert_eq!(&stream[..2], &[0x78, 0x01]);
+        assert_eq!((0x78u16 << 8 | 0x01) % 31, 0);
+
+        // Two blocks: a full one, then the final 4465 bytes
+        assert_eq!(&stream[2..7], &[0, 0xff, 0xff, 0, 0]);
+        let second = 7 + 65_535;
+        assert_eq!(stream[second], 1);
+        assert_eq!(
+            u16::from_le_bytes([stream[second + 1], stream[second + 2]]),
+            4465
+        );
+        assert_eq!(stream.len(), 2 + 5 * 2 + data.len() + 4);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let frame = Grid::from_vec(2, 1, vec![Rgb(1, 2, 3), Rgb(4, 5, 6)]);
+        let mut out = Vec::new();
+        write_ppm(&mut out, &frame).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[test]
+    fn test_write_png() {
+        let frame = Grid::from_vec(2, 1, vec![Rgb(255, 0, 0), Rgb(0, 0, 255)]);
+        let mut out = Vec::new();
+        write_png(&mut out, &frame).unwrap();
+
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&out[12..16], b"IHDR");
+        assert_eq!(&out[16..24], &[0, 0, 0, 2, 0, 0, 0, 1]);
+        assert!(out.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        // Every chunk's CRC matches its type and data
+        let mut pos = 8;
+        while pos < out.len() {
+            let len = u32::from_be_bytes(out[pos..pos + 4].try_into().unwrap()) as usize;
+            let body = &out[pos + 4..pos + 8 + len];
+            let crc = u32::from_be_bytes(out[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(!crc32_update(!0, body), crc);
+            pos += 12 + len;
+        }
+        assert_eq!(pos, out.len());
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let small = Grid::new(1, 1, Rgb(9, 9, 9));
+        let large = Grid::from_vec(3, 2, vec![Rgb(1, 1, 1); 6]);
+        let mut out = Vec::new();
+        write_gif(&mut out, &[small, large], 5).unwrap();
+
+        assert_eq!(&out[..6], b"GIF89a");
+        assert_eq!(&out[6..10], &[3, 0, 2, 0]);
+        // Three colours need a 2-bit table
+        assert_eq!(out[10], 0xf1);
+        assert_eq!(&out[13..25], &[0, 0, 0, 9, 9, 9, 1, 1, 1, 0, 0, 0]);
+        assert_eq!(*out.last().unwrap(), 0x3b);
+
+        // The first frame is padded with black to the screen size
+        let image = out.windows(2).position(|w| w == [0x2c, 0]).unwrap();
+        let data_start = image + 10;
+        assert_eq!(out[data_start], 2);
+        let len = out[data_start + 1] as usize;
+        let data = &out[data_start + 2..data_start + 2 + len];
+        assert_eq!(lzw_decode(data, 2), vec![1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_write_gif_too_many_colours() {
+        let frame = Grid::from_vec(
+            300,
+            1,
+            (0..300).map(|i| Rgb(i as u8, (i / 256) as u8, 1)).collect(),
+        );
+        let err = write_gif(&mut Vec::new(), &[frame], 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_ansi_and_upscale() {
+        let frame = Grid::from_vec(1, 3, vec![Rgb(1, 2, 3), Rgb(4, 5, 6), Rgb(7, 8, 9)]);
+        assert_eq!(
+            ansi(&frame),
+            "\x1b[38;2;1;2;3m\x1b[48;2;4;5;6m▀\x1b[0m\n\
+             \x1b[38;2;7;8;9m\x1b[48;2;0;0;0m▀\x1b[0m\n"
+        );
+
+        let big = upscale(&frame, 2);
+        assert_eq!((big.width(), big.height()), (2, 6));
+        assert_eq!(big[(1, 3)], Rgb(4, 5, 6));
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new((10, 0), (110, 50), 200);
+        assert_eq!(svg.extent(), 100);
+        svg.rect((10, 0), 5, 6, "fill:none");
+        svg.circle((1, 2), 0.5, "fill:red");
+        assert_eq!(
+            svg.finish(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"105\" \
+             viewBox=\"7 -3 106 56\">\n  \
+             <rect x=\"10\" y=\"0\" width=\"5\" height=\"6\" \
+             style=\"vector-effect:non-scaling-stroke;fill:none\"/>\n  \
+             <circle cx=\"1\" cy=\"2\" r=\"0.5\" \
+             style=\"vector-effect:non-scaling-stroke;fill:red\"/>\n</svg>\n"
+        );
+    }
+
+    #[test]
+    fn test_recorder_limit() {
+        let mut recorder = Recorder::with_limit(2);
+        let mut observer: Option<&mut dyn Observer> = Some(&mut recorder);
+        let mut drawn = 0;
+        for _ in 0..5 {
+            emit(&mut observer, || {
+                drawn += 1;
+                Grid::new(1, 1, Rgb::BLACK)
+            });
+        }
+        assert_eq!(drawn, 2);
+        assert_eq!(recorder.frames.len(), 2);
+    }
+}
@@ -7,109 +7,240 @@
 //! Part 1: Count how many times the dial lands on position 0 after any rotation.
 //! Part 2: Count how many times the dial passes through 0 during any rotation (including at the end).
 
-use crate::common::parse_lines;
-use crate::runner::Day;
+use crate::common::rng::Rng;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::common::{parse_lines_strict, ParseError};
+use crate::runner::{Day, InputGenerator, Param, ParamKind, SolveContext, StreamingDay};
+use crate::stream::LineReader;
+use std::io::{self, BufRead};
 
 /// Solver for Day 1
 pub struct Day01;
 
+/// Tunable parameters for Day 1
+const PARAMS: &[Param] = &[
+    Param {
+        name: "dial_size",
+        default: "100",
+        description: "Number of positions on the dial",
+        kind: ParamKind::Integer {
+            min: 1,
+            max: i32::MAX as u64,
+        },
+    },
+    Param {
+        name: "start",
+        default: "50",
+        description: "Starting dial position, taken modulo the dial size",
+        kind: ParamKind::Integer {
+            min: 0,
+            max: i32::MAX as u64,
+        },
+    },
+];
+
 impl Day for Day01 {
     fn part1(&self, input: &str) -> String {
-        count_zeros(input).to_string()
+        self.part1_with(input, &SolveContext::from_defaults(PARAMS))
     }
 
     fn part2(&self, input: &str) -> String {
-        count_zeros_during_rotations(input).to_string()
+        self.part2_with(input, &SolveContext::from_defaults(PARAMS))
     }
-}
 
-// Helper functions
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
 
-/// Parse and execute rotations, counting how many times we land on 0
-fn count_zeros(input: &str) -> usize {
-    const DIAL_SIZE: i32 = 100;
-    const START_POS: i32 = 50;
-
-    let rotations = parse_lines(input, |line| {
-        let direction = line.chars().next()?;
-        let distance: i32 = line[1..].parse().ok()?;
-        Some((direction, distance))
-    });
-
-    let mut position = START_POS;
-    let mut zero_count = 0;
-
-    for (direction, distance) in rotations {
-        // Apply rotation
-        position = match direction {
-            'L' => (position - distance).rem_euclid(DIAL_SIZE),
-            'R' => (position + distance).rem_euclid(DIAL_SIZE),
-            _ => panic!("Invalid direction: {}", direction),
-        };
+    fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+        count_zeros(input, ctx.get("dial_size"), ctx.get("start")).to_string()
+    }
 
-        // Count if we landed on 0
-        if position == 0 {
-            zero_count += 1;
+    fn part2_with(&self, input: &str, ctx: &SolveContext) -> String {
+        count_zeros_during_rotations(input, ctx.get("dial_size"), ctx.get("start")).to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn streaming(&self) -> Option<&dyn StreamingDay> {
+        Some(self)
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+}
+
+/// Generates `size` rotations of 1 to 999 clicks in either direction
+impl InputGenerator for Day01 {
+    fn default_size(&self) -> usize {
+        4000
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::with_capacity(size * 5);
+        for _ in 0..size {
+            let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+            input.push(direction);
+            input.push_str(&rng.range(1, 999).to_string());
+            input.push('\n');
         }
+        input
+    }
+}
+
+impl StreamingDay for Day01 {
+    fn part1_stream(&self, input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<String> {
+        Ok(turn_dial_stream(input, ctx)?.landed.to_string())
     }
 
-    zero_count
+    fn part2_stream(&self, input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<String> {
+        Ok(turn_dial_stream(input, ctx)?.passed.to_string())
+    }
 }
 
-/// Count how many times the dial passes through 0 during all rotations.
-/// This includes both landing on 0 at the end of a rotation AND passing through 0 during the rotation.
-///
-/// For a right rotation from position `start` by `distance`:
-///   - We pass through 0: floor((start + distance) / 100) - floor(start / 100) times
+// Helper functions
+
+/// Parse a rotation such as `L68`
+fn parse_rotation(line: &str) -> Option<(char, i32)> {
+    let direction = line.chars().next().filter(|c| matches!(c, 'L' | 'R'))?;
+    let distance: i32 = line[1..].parse().ok()?;
+    Some((direction, distance))
+}
+
+/// Parse rotations such as `L68`, panicking on the first malformed line
 ///
-/// For a left rotation from position `start` by `distance`:
-///   - If start == 0: floor(distance / 100) times (we only hit 0 at multiples of 100 steps)
-///   - If start > 0 and distance >= start: 1 + floor((distance - start) / 100) times
-///   - If start > 0 and distance < start: 0 times
-fn count_zeros_during_rotations(input: &str) -> usize {
-    const DIAL_SIZE: i32 = 100;
-    const START_POS: i32 = 50;
-
-    let rotations = parse_lines(input, |line| {
-        let direction = line.chars().next()?;
-        let distance: i32 = line[1..].parse().ok()?;
-        Some((direction, distance))
-    });
-
-    let mut position = START_POS;
-    let mut zero_count = 0;
-
-    for (direction, distance) in rotations {
+/// A mistyped rotation would otherwise be dropped and silently change the
+/// answer.
+fn parse_rotations(input: &str) -> Vec<(char, i32)> {
+    parse_lines_strict(input, parse_rotation).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// The dial's position and how often it has reached 0
+struct Dial {
+    size: i32,
+    position: i32,
+    /// Rotations that ended on 0
+    landed: usize,
+    /// Clicks onto 0, during rotations or at their end
+    passed: usize,
+}
+
+impl Dial {
+    fn new(size: i32, start: i32) -> Self {
+        Self {
+            size,
+            position: start.rem_euclid(size),
+            landed: 0,
+            passed: 0,
+        }
+    }
+
+    /// Apply one rotation, counting the times it passes through 0
+    ///
+    /// For a right rotation from position `start` by `distance` on a dial of size `n`:
+    ///   - We pass through 0: floor((start + distance) / n) - floor(start / n) times
+    ///
+    /// For a left rotation from position `start` by `distance`:
+    ///   - If start == 0: floor(distance / n) times (we only hit 0 at multiples of n steps)
+    ///   - If start > 0 and distance >= start: 1 + floor((distance - start) / n) times
+    ///   - If start > 0 and distance < start: 0 times
+    ///
+    /// The sums are taken in `i64`, since `start + distance` can exceed
+    /// `i32::MAX` on a large dial.
+    fn turn(&mut self, direction: char, distance: i32) {
+        let (position, size) = (i64::from(self.position), i64::from(self.size));
+        let distance = i64::from(distance);
+
         // Count zeros crossed during this rotation
-        zero_count += match direction {
+        self.passed += match direction {
             'L' => {
                 if position == 0 {
-                    // Starting at 0, going left: hit 0 every 100 clicks
-                    distance / DIAL_SIZE
+                    // Starting at 0, going left: hit 0 every dial_size clicks
+                    distance / size
                 } else if distance >= position {
-                    // We'll hit 0 after 'position' clicks, then every 100 after that
-                    1 + (distance - position) / DIAL_SIZE
+                    // We'll hit 0 after 'position' clicks, then every dial_size after that
+                    1 + (distance - position) / size
                 } else {
                     // We don't reach 0
                     0
                 }
             }
             'R' => {
-                // Going right: count how many multiples of 100 we cross
-                (position + distance) / DIAL_SIZE - position / DIAL_SIZE
+                // Going right: count how many multiples of dial_size we cross
+                (position + distance) / size - position / size
             }
             _ => panic!("Invalid direction: {}", direction),
-        };
+        } as usize;
 
         // Update position
-        position = match direction {
-            'L' => (position - distance).rem_euclid(DIAL_SIZE),
-            'R' => (position + distance).rem_euclid(DIAL_SIZE),
+        self.position = match direction {
+            'L' => (position - distance).rem_euclid(size),
+            'R' => (position + distance).rem_euclid(size),
             _ => panic!("Invalid direction: {}", direction),
-        };
+        } as i32;
+
+        // Count if we landed on 0
+        if self.position == 0 {
+            self.landed += 1;
+        }
     }
+}
 
-    zero_count as usize
+/// Parse and execute rotations, counting how many times we land on 0
+fn count_zeros(input: &str, dial_size: i32, start_pos: i32) -> usize {
+    turn_dial(input, dial_size, start_pos).landed
+}
+
+/// Count how many times the dial passes through 0 during all rotations.
+/// This includes both landing on 0 at the end of a rotation AND passing through 0 during the rotation.
+fn count_zeros_during_rotations(input: &str, dial_size: i32, start_pos: i32) -> usize {
+    turn_dial(input, dial_size, start_pos).passed
+}
+
+/// Apply every rotation in the input to a fresh dial
+fn turn_dial(input: &str, dial_size: i32, start_pos: i32) -> Dial {
+    let mut dial = Dial::new(dial_size, start_pos);
+    for (direction, distance) in parse_rotations(input) {
+        dial.turn(direction, distance);
+    }
+    dial
+}
+
+/// Apply rotations read one line at a time, as [`turn_dial`] would
+fn turn_dial_stream(input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<Dial> {
+    let mut dial = Dial::new(ctx.get("dial_size"), ctx.get("start"));
+    let mut lines = LineReader::new(input);
+    while let Some((line_no, line)) = lines.next_line()? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (direction, distance) = parse_rotation(line).ok_or_else(|| ParseError {
+            line: line_no,
+            text: line.to_string(),
+        })?;
+        dial.turn(direction, distance);
+    }
+    Ok(dial)
+}
+
+/// Check that every non-blank line is a rotation such as `L68`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.skip_spaces();
+        cursor.one_of(&['L', 'R'], "`L` or `R`")?;
+        cursor.number_at_most(i32::MAX as u64)?;
+        cursor.skip_spaces();
+        cursor.finish()?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -134,6 +265,30 @@ L82";
         assert_eq!(day.part1(EXAMPLE), "3");
     }
 
+    #[test]
+    fn test_custom_dial() {
+        // On a 10-position dial starting at 0: R10 -> 0, L5 -> 5, L15 -> 0
+        let day = Day01;
+        let ctx = SolveContext::from_defaults(day.params())
+            .with("dial_size", "10")
+            .with("start", "0");
+        assert_eq!(day.part1_with("R10\nL5\nL15", &ctx), "2");
+        assert_eq!(day.part2_with("R10\nL5\nL15", &ctx), "3");
+    }
+
+    #[test]
+    fn test_largest_dial() {
+        // Turning right from the last position would overflow `i32`
+        let day = Day01;
+        let ctx = SolveContext::from_defaults(day.params())
+            .with("dial_size", &i32::MAX.to_string())
+            .with("start", &(i32::MAX - 1).to_string());
+        let input = format!("R{}\nR1\nL{}", i32::MAX, i32::MAX);
+        assert_eq!(day.validate(&input), Ok(()));
+        assert_eq!(day.part1_with(&input, &ctx), "2");
+        assert_eq!(day.part2_with(&input, &ctx), "3");
+    }
+
     #[test]
     fn test_rotations() {
         // Test individual rotations from the example
@@ -257,6 +412,61 @@ L82";
         assert_eq!(pos, 32);
         assert_eq!(count, 6);
     }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_input("L68\nR5\n\n").is_ok());
+
+        let err = validate_input("L68\nX5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = validate_input("R99999999999\n").unwrap_err();
+        assert_eq!((err.column, err.width), (2, 11));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid input on line 2")]
+    fn test_rejects_malformed_rotation() {
+        Day01.part1("L68\nL3O\nR48");
+    }
+
+    #[test]
+    fn test_generator() {
+        let mut rng = Rng::new(1);
+        let input = Day01.generate(500, &mut rng);
+        assert_eq!(input.lines().count(), 500);
+        assert!(validate_input(&input).is_ok());
+
+        // The dial passes 0 at least as often as it stops there
+        let landed: usize = Day01.part1(&input).parse().unwrap();
+        let passed: usize = Day01.part2(&input).parse().unwrap();
+        assert!(landed <= passed);
+        assert_eq!(Day01.generate(500, &mut Rng::new(1)), input);
+    }
+
+    #[test]
+    fn test_streaming_matches() {
+        let ctx = SolveContext::from_defaults(PARAMS);
+        let input = format!("\u{feff}{}\r\n\r\n", EXAMPLE.replace('\n', " \r\n"));
+        assert_eq!(
+            Day01.part1_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "3"
+        );
+        assert_eq!(
+            Day01.part2_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "6"
+        );
+    }
+
+    #[test]
+    fn test_streaming_rejects_malformed_rotation() {
+        let ctx = SolveContext::from_defaults(PARAMS);
+        let err = Day01
+            .part1_stream(&mut "L68\n\nL3O\n".as_bytes(), &ctx)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "Invalid input on line 3: \"L3O\"");
+    }
 }
 
 // Define benchmarks using the common macro
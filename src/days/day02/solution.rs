@@ -30,7 +30,9 @@
 //! performance by only generating valid candidates rather than checking all
 //! numbers in each range.
 
-use crate::runner::Day;
+use crate::common::rng::Rng;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::runner::{Day, InputGenerator};
 
 /// Generic solver for Day 2 that accepts different validation functions
 ///
@@ -80,6 +82,10 @@ where
             .sum();
         sum.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 // Helper functions
@@ -374,6 +380,52 @@ impl Day for GeneratingSolver {
             .sum();
         sum.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+}
+
+/// Generates `size` disjoint ranges in random order
+///
+/// Like the real input, starts have 1 to 10 digits in equal proportion and
+/// ranges are at most a few hundred thousand IDs long, so the brute-force
+/// solvers stay usable.
+impl InputGenerator for GeneratingSolver {
+    fn default_size(&self) -> usize {
+        38
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut ranges: Vec<(u64, u64)> = (0..size)
+            .map(|_| {
+                let digits = rng.range(1, 10) as u32;
+                let low = 10u64.pow(digits - 1);
+                let start = low + rng.below(low * 9);
+                let span = rng.below((start / 2 + 16).min(200_000));
+                (start, span)
+            })
+            .collect();
+
+        // Slide ranges up past their predecessor so none overlap
+        ranges.sort_unstable();
+        let mut next_free = 0;
+        for (start, span) in &mut ranges {
+            *start = (*start).max(next_free);
+            next_free = *start + *span + 1;
+        }
+        rng.shuffle(&mut ranges);
+
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|(start, span)| format!("{}-{}", start, start + span))
+            .collect();
+        ranges.join(",") + "\n"
+    }
 }
 
 // Solver instances
@@ -422,6 +474,45 @@ pub const Day02Generate: GeneratingSolver = GeneratingSolver;
 #[allow(non_upper_case_globals)]
 pub const Day02: GeneratingSolver = Day02Generate;
 
+/// Check that the input is one line of comma-separated `start-end` ranges
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .skip_while(|(_, l)| l.trim().is_empty());
+    let Some((idx, line)) = lines.next() else {
+        return Err(Diagnostic::end_of_input(input, "a list of ranges"));
+    };
+
+    let mut cursor = Cursor::new(idx + 1, line);
+    cursor.skip_spaces();
+    loop {
+        let start_pos = cursor.pos();
+        let start = cursor.number()?;
+        cursor.expect('-')?;
+        let end = cursor.number()?;
+        if end < start {
+            return Err(cursor.error_since(
+                start_pos,
+                "a range whose end is not below its start",
+                format!("{}-{}", start, end),
+            ));
+        }
+        if !cursor.eat(',') {
+            break;
+        }
+    }
+    cursor.skip_spaces();
+    if cursor.peek().is_some() {
+        return Err(cursor.error("`,` or end of line"));
+    }
+
+    if let Some((idx, line)) = lines.find(|(_, l)| !l.trim().is_empty()) {
+        return Err(Diagnostic::at(idx + 1, line, 0, "end of input"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -441,6 +532,23 @@ mod tests {
         sum.to_string()
     }
 
+    #[test]
+    fn test_generator() {
+        let input = Day02.generate(60, &mut Rng::new(5));
+        assert!(validate_input(&input).is_ok());
+
+        let mut ranges = parse_ranges(&input);
+        assert_eq!(ranges.len(), 60);
+        ranges.sort_unstable();
+        assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        // Every solver agrees on generated ranges
+        let expected = (Day02.part1(&input), Day02.part2(&input));
+        for day in [&Day02Math as &dyn Day, &Day02Modulo] {
+            assert_eq!((day.part1(&input), day.part2(&input)), expected);
+        }
+    }
+
     #[test]
     fn test_part1_example_math() {
         assert_eq!(
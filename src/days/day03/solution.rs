@@ -1,22 +1,83 @@
 //! Solution implementation for Day 3
 
-use crate::runner::Day;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::runner::{Day, Param, ParamKind, SolveContext, StreamingDay, Witness};
+use crate::stream::LineReader;
+use std::io::{self, BufRead};
 
 /// Solver for Day 3
 pub struct Day03;
 
+/// Tunable parameters for Day 3
+const PARAMS: &[Param] = &[Param {
+    name: "batteries",
+    default: "12",
+    description: "Batteries to turn on per bank in part 2",
+    // Every 19-digit joltage fits in a u64
+    kind: ParamKind::Integer { min: 1, max: 19 },
+}];
+
 impl Day for Day03 {
     fn part1(&self, input: &str) -> String {
         input.lines().map(max_joltage).sum::<u32>().to_string()
     }
 
     fn part2(&self, input: &str) -> String {
+        self.part2_with(input, &SolveContext::from_defaults(PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn part2_with(&self, input: &str, ctx: &SolveContext) -> String {
+        let k = ctx.get("batteries");
         input
             .lines()
-            .map(|line| max_joltage_k(line, 12))
+            .map(|line| max_joltage_k(line, k))
             .sum::<u64>()
             .to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input, 2)
+    }
+
+    fn validate_with(&self, input: &str, ctx: &SolveContext) -> Result<(), Diagnostic> {
+        validate_input(input, ctx.get("batteries"))
+    }
+
+    fn explain(&self, input: &str, ctx: &SolveContext) -> Option<Vec<Witness>> {
+        let witnesses = [(1, 2), (2, ctx.get("batteries"))]
+            .into_iter()
+            .flat_map(|(part, k)| {
+                input.lines().enumerate().map(move |(idx, bank)| {
+                    let positions: Vec<usize> = chosen_positions(bank.as_bytes(), k).collect();
+                    let digits: String = positions.iter().map(|&i| &bank[i..=i]).collect();
+                    let positions: Vec<String> = positions.iter().map(usize::to_string).collect();
+                    Witness::new(part, format!("bank {}", idx + 1))
+                        .with("digits", digits)
+                        .with("positions", positions.join(","))
+                })
+            })
+            .collect();
+        Some(witnesses)
+    }
+
+    fn streaming(&self) -> Option<&dyn StreamingDay> {
+        Some(self)
+    }
+}
+
+impl StreamingDay for Day03 {
+    fn part1_stream(&self, input: &mut dyn BufRead, _ctx: &SolveContext) -> io::Result<String> {
+        Ok(sum_banks_stream(input, 2, |bank| max_joltage(bank) as u64)?.to_string())
+    }
+
+    fn part2_stream(&self, input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<String> {
+        let k = ctx.get("batteries");
+        Ok(sum_banks_stream(input, k, |bank| max_joltage_k(bank, k))?.to_string())
+    }
 }
 
 // Helper functions
@@ -49,17 +110,20 @@ fn max_joltage(bank: &str) -> u32 {
 }
 
 /// Find the maximum joltage by selecting exactly k batteries from a bank
-/// Uses a greedy algorithm: for each position, select the largest digit
-/// that still leaves enough digits for the remaining positions
 /// Optimized to build u64 directly without String intermediary
 fn max_joltage_k(bank: &str, k: usize) -> u64 {
     let digits = bank.as_bytes();
-    let n = digits.len();
+    chosen_positions(digits, k).fold(0, |result, idx| result * 10 + (digits[idx] - b'0') as u64)
+}
 
-    let mut result: u64 = 0;
+/// Positions of the k batteries giving the maximum joltage, left to right
+/// Uses a greedy algorithm: for each position, select the largest digit
+/// that still leaves enough digits for the remaining positions
+fn chosen_positions(digits: &[u8], k: usize) -> impl Iterator<Item = usize> + '_ {
+    let n = digits.len();
     let mut start = 0;
 
-    for position in 0..k {
+    (0..k).map(move |position| {
         // We can search up to this index while leaving enough digits for remaining positions
         let search_end = n - k + position + 1;
 
@@ -71,12 +135,74 @@ fn max_joltage_k(bank: &str, k: usize) -> u64 {
             }
         }
 
-        // Build u64 directly
-        result = result * 10 + (digits[max_idx] - b'0') as u64;
         start = max_idx + 1;
+        max_idx
+    })
+}
+
+/// Sum the joltage of every bank, reading one line at a time
+///
+/// Blank lines are skipped, so the blank lines a file may end with are too.
+/// Fails on a bank with anything but digits, or with fewer than `batteries`
+/// batteries to turn on.
+fn sum_banks_stream(
+    input: &mut dyn BufRead,
+    batteries: usize,
+    joltage: impl Fn(&str) -> u64,
+) -> io::Result<u64> {
+    let mut lines = LineReader::new(input);
+    let mut total = 0;
+    while let Some((line_no, bank)) = lines.next_line()? {
+        if bank.is_empty() {
+            continue;
+        }
+        if let Some((ch, column)) = bank.chars().zip(1..).find(|(ch, _)| !ch.is_ascii_digit()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "line {}, column {}: `{}` is not a battery digit",
+                    line_no, column, ch
+                ),
+            ));
+        }
+        if bank.len() < batteries {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "line {}: bank of {} batteries, fewer than the {} to turn on",
+                    line_no,
+                    bank.len(),
+                    batteries
+                ),
+            ));
+        }
+        total += joltage(bank);
     }
+    Ok(total)
+}
 
-    result
+/// Check that every line is a bank of at least two battery digits, and of at
+/// least `batteries` so that many can be turned on
+fn validate_input(input: &str, batteries: usize) -> Result<(), Diagnostic> {
+    if input.lines().next().is_none() {
+        return Err(Diagnostic::end_of_input(input, "a battery bank"));
+    }
+    for (idx, line) in input.lines().enumerate() {
+        let mut cursor = Cursor::new(idx + 1, line);
+        let digits = cursor.take_while(|ch| ch.is_ascii_digit());
+        if digits.len() < 2 {
+            return Err(cursor.error("at least two battery digits"));
+        }
+        cursor.finish()?;
+        if digits.len() < batteries {
+            return Err(cursor.error_since(
+                0,
+                format!("a bank of at least {} batteries", batteries),
+                format!("{} batteries", digits.len()),
+            ));
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -101,6 +227,79 @@ mod tests {
         assert_eq!(day.part2(EXAMPLE), "3121910778619");
     }
 
+    #[test]
+    fn test_streaming_matches() {
+        let ctx = SolveContext::from_defaults(PARAMS);
+        let input = format!("{}\r\n\r\n", EXAMPLE.replace('\n', "\r\n"));
+        assert_eq!(
+            Day03.part1_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "357"
+        );
+        assert_eq!(
+            Day03.part2_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "3121910778619"
+        );
+    }
+
+    #[test]
+    fn test_part2_custom_batteries() {
+        // With 2 batteries, part 2 reduces to part 1
+        let day = Day03;
+        let ctx = SolveContext::from_defaults(day.params()).with("batteries", "2");
+        assert_eq!(day.part2_with(EXAMPLE, &ctx), "357");
+    }
+
+    #[test]
+    fn test_validate_batteries() {
+        let day = Day03;
+        let ctx = SolveContext::from_defaults(day.params());
+        assert!(day.validate_with(EXAMPLE, &ctx).is_ok());
+
+        // The example's 15-digit banks can't have 16 batteries turned on
+        let ctx = ctx.with("batteries", "16");
+        let err = day.validate_with(EXAMPLE, &ctx).unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (1, 1, 15));
+        assert_eq!(
+            err.to_string(),
+            "expected a bank of at least 16 batteries, found 15 batteries"
+        );
+
+        let err = day.part2_stream(&mut EXAMPLE.as_bytes(), &ctx).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 1: bank of 15"));
+    }
+
+    #[test]
+    fn test_streaming_rejects_malformed_bank() {
+        let ctx = SolveContext::from_defaults(PARAMS);
+        for input in ["987\n81x1\n", "987\n81é1\n"] {
+            let err = Day03.part1_stream(&mut input.as_bytes(), &ctx).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err.to_string().starts_with("line 2, column 3: "), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let day = Day03;
+        let ctx = SolveContext::from_defaults(day.params());
+        let witnesses = day.explain(EXAMPLE, &ctx).unwrap();
+        assert_eq!(witnesses.len(), 8);
+
+        // Part 1 picks the 8 and the 9 at the ends of the second bank
+        assert_eq!(witnesses[1].label, "bank 2");
+        assert_eq!(witnesses[1].fact("digits"), Some("89"));
+        assert_eq!(witnesses[1].fact("positions"), Some("0,14"));
+
+        // Part 2 drops a 2, a 3 and another 2 from the start of the third bank
+        assert_eq!(witnesses[6].part, 2);
+        assert_eq!(witnesses[6].fact("digits"), Some("434234234278"));
+        assert_eq!(
+            witnesses[6].fact("positions"),
+            Some("2,4,5,6,7,8,9,10,11,12,13,14")
+        );
+    }
+
     #[test]
     fn test_part1_edge_cases() {
         // Ascending sequence - max should be last two digits
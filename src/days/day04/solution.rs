@@ -1,22 +1,24 @@
 //! Solution implementation for Day 4
 
-use crate::runner::Day;
+use crate::common::grid::Grid;
+use crate::common::rng::Rng;
+use crate::common::validate::{self, Diagnostic};
+use crate::common::viz::{self, Frame, Observer, Rgb};
+use crate::runner::{Day, InputGenerator};
 
 /// Naive baseline solver - rescans entire grid each iteration
 pub struct Day04Naive;
 
 impl Day for Day04Naive {
     fn part1(&self, input: &str) -> String {
-        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        let rows = grid.len();
-        let cols = grid[0].len();
+        let grid = Grid::parse(input);
 
         let mut accessible = 0;
 
-        for row in 0..rows {
-            for col in 0..cols {
-                if grid[row][col] == '@' {
-                    let adjacent_count = count_adjacent_rolls(&grid, row, col);
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if grid[(x, y)] == b'@' {
+                    let adjacent_count = count_adjacent_rolls(&grid, x, y);
                     if adjacent_count < 4 {
                         accessible += 1;
                     }
@@ -28,21 +30,19 @@ impl Day for Day04Naive {
     }
 
     fn part2(&self, input: &str) -> String {
-        let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let mut grid = Grid::parse(input);
         let mut total_removed = 0;
 
         loop {
             // Find all accessible rolls
             let mut to_remove = Vec::new();
-            let rows = grid.len();
-            let cols = grid[0].len();
 
-            for row in 0..rows {
-                for col in 0..cols {
-                    if grid[row][col] == '@' {
-                        let adjacent_count = count_adjacent_rolls(&grid, row, col);
+            for y in 0..grid.height() {
+                for x in 0..grid.width() {
+                    if grid[(x, y)] == b'@' {
+                        let adjacent_count = count_adjacent_rolls(&grid, x, y);
                         if adjacent_count < 4 {
-                            to_remove.push((row, col));
+                            to_remove.push((x, y));
                         }
                     }
                 }
@@ -54,8 +54,8 @@ impl Day for Day04Naive {
             }
 
             // Remove all accessible rolls
-            for (row, col) in &to_remove {
-                grid[*row][*col] = '.';
+            for &pos in &to_remove {
+                grid[pos] = b'.';
             }
 
             total_removed += to_remove.len();
@@ -63,32 +63,29 @@ impl Day for Day04Naive {
 
         total_removed.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 // Helper functions
 
 /// Count how many adjacent positions contain rolls of paper
-fn count_adjacent_rolls(grid: &[Vec<char>], row: usize, col: usize) -> usize {
-    let rows = grid.len();
-    let cols = grid[0].len();
+fn count_adjacent_rolls(grid: &Grid<u8>, x: usize, y: usize) -> usize {
     let mut count = 0;
 
     // Check all 8 adjacent positions
-    for dr in -1..=1 {
-        for dc in -1..=1 {
-            if dr == 0 && dc == 0 {
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if dx == 0 && dy == 0 {
                 continue; // Skip the current position
             }
 
-            let new_row = row as i32 + dr;
-            let new_col = col as i32 + dc;
+            let nx = x as isize + dx;
+            let ny = y as isize + dy;
 
-            if new_row >= 0
-                && new_row < rows as i32
-                && new_col >= 0
-                && new_col < cols as i32
-                && grid[new_row as usize][new_col as usize] == '@'
-            {
+            if grid.contains(nx, ny) && grid[(nx as usize, ny as usize)] == b'@' {
                 count += 1;
             }
         }
@@ -97,120 +94,12 @@ fn count_adjacent_rolls(grid: &[Vec<char>], row: usize, col: usize) -> usize {
     count
 }
 
-// Optimized implementation using flat Vec<u8>
+// Optimized implementations using flat indices into the grid
 
-/// Optimized grid representation using flat byte array
-struct Grid {
-    data: Vec<u8>,
-    cols: usize,
-    rows: usize,
-}
-
-impl Grid {
-    fn parse(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let rows = lines.len();
-        let cols = lines[0].len();
-        let data = input.bytes().filter(|&b| b != b'\n').collect();
-
-        Self { data, cols, rows }
-    }
-
-    #[inline]
-    fn get(&self, idx: usize) -> u8 {
-        self.data[idx]
-    }
-
-    #[inline]
-    fn set(&mut self, idx: usize, val: u8) {
-        self.data[idx] = val;
-    }
-
-    /// Count adjacent rolls using manual unrolling for performance
-    fn count_adjacent(&self, idx: usize) -> usize {
-        let row = idx / self.cols;
-        let col = idx % self.cols;
-        let mut count = 0;
-
-        // Top row
-        if row > 0 {
-            let above = idx - self.cols;
-            if col > 0 && self.data[above - 1] == b'@' {
-                count += 1;
-            }
-            if self.data[above] == b'@' {
-                count += 1;
-            }
-            if col < self.cols - 1 && self.data[above + 1] == b'@' {
-                count += 1;
-            }
-        }
-
-        // Same row
-        if col > 0 && self.data[idx - 1] == b'@' {
-            count += 1;
-        }
-        if col < self.cols - 1 && self.data[idx + 1] == b'@' {
-            count += 1;
-        }
-
-        // Bottom row
-        if row < self.rows - 1 {
-            let below = idx + self.cols;
-            if col > 0 && self.data[below - 1] == b'@' {
-                count += 1;
-            }
-            if self.data[below] == b'@' {
-                count += 1;
-            }
-            if col < self.cols - 1 && self.data[below + 1] == b'@' {
-                count += 1;
-            }
-        }
-
-        count
-    }
-
-    /// Get all valid neighbor indices for a position
-    fn neighbors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
-        let row = idx / self.cols;
-        let col = idx % self.cols;
-        let mut neighbors = Vec::with_capacity(8);
-
-        // Top row
-        if row > 0 {
-            let above = idx - self.cols;
-            if col > 0 {
-                neighbors.push(above - 1);
-            }
-            neighbors.push(above);
-            if col < self.cols - 1 {
-                neighbors.push(above + 1);
-            }
-        }
-
-        // Same row
-        if col > 0 {
-            neighbors.push(idx - 1);
-        }
-        if col < self.cols - 1 {
-            neighbors.push(idx + 1);
-        }
-
-        // Bottom row
-        if row < self.rows - 1 {
-            let below = idx + self.cols;
-            if col > 0 {
-                neighbors.push(below - 1);
-            }
-            neighbors.push(below);
-            if col < self.cols - 1 {
-                neighbors.push(below + 1);
-            }
-        }
-
-        neighbors.into_iter()
-    }
+/// Count rolls among the 8 neighbours of a flat index
+#[inline]
+fn count_adjacent(grid: &Grid<u8>, idx: usize) -> usize {
+    grid.neighbors8(idx).filter(|&n| grid[n] == b'@').count()
 }
 
 /// Solver using flat arrays and dirty set tracking
@@ -219,8 +108,8 @@ pub struct Day04DirtyTracking;
 impl Day for Day04DirtyTracking {
     fn part1(&self, input: &str) -> String {
         let grid = Grid::parse(input);
-        let accessible = (0..grid.data.len())
-            .filter(|&idx| grid.get(idx) == b'@' && grid.count_adjacent(idx) < 4)
+        let accessible = (0..grid.len())
+            .filter(|&idx| grid[idx] == b'@' && count_adjacent(&grid, idx) < 4)
             .count();
 
         accessible.to_string()
@@ -233,9 +122,8 @@ impl Day for Day04DirtyTracking {
         let mut total_removed = 0;
 
         // Start by checking all positions with '@'
-        let mut to_check: HashSet<usize> = (0..grid.data.len())
-            .filter(|&idx| grid.get(idx) == b'@')
-            .collect();
+        let mut to_check: HashSet<usize> =
+            (0..grid.len()).filter(|&idx| grid[idx] == b'@').collect();
 
         // Reuse vectors to avoid allocations
         let mut accessible = Vec::new();
@@ -246,7 +134,7 @@ impl Day for Day04DirtyTracking {
 
             // Only check candidate positions
             for &idx in &to_check {
-                if grid.get(idx) == b'@' && grid.count_adjacent(idx) < 4 {
+                if grid[idx] == b'@' && count_adjacent(&grid, idx) < 4 {
                     accessible.push(idx);
                 }
             }
@@ -258,11 +146,11 @@ impl Day for Day04DirtyTracking {
             // Remove accessible rolls and mark neighbors for next check
             next_check.clear();
             for &idx in &accessible {
-                grid.set(idx, b'.');
+                grid[idx] = b'.';
 
                 // Add neighbors to next check set
-                for neighbor in grid.neighbors(idx) {
-                    if grid.get(neighbor) == b'@' {
+                for neighbor in grid.neighbors8(idx) {
+                    if grid[neighbor] == b'@' {
                         next_check.insert(neighbor);
                     }
                 }
@@ -274,122 +162,37 @@ impl Day for Day04DirtyTracking {
 
         total_removed.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 /// Grid with pre-computed neighbor counts for maximum performance
 struct GridWithCounts {
-    data: Vec<u8>,
+    grid: Grid<u8>,
     neighbor_counts: Vec<u8>,
-    cols: usize,
-    rows: usize,
 }
 
 impl GridWithCounts {
     fn parse(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let rows = lines.len();
-        let cols = lines[0].len();
-        let data: Vec<u8> = input.bytes().filter(|&b| b != b'\n').collect();
-
-        // Pre-compute neighbor counts for all positions
-        let mut neighbor_counts = vec![0u8; data.len()];
-        for idx in 0..data.len() {
-            if data[idx] == b'@' {
-                let row = idx / cols;
-                let col = idx % cols;
-
-                // Count all 8 neighbors
-                if row > 0 {
-                    let above = idx - cols;
-                    if col > 0 && data[above - 1] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
-                    if data[above] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
-                    if col < cols - 1 && data[above + 1] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
-                }
-
-                if col > 0 && data[idx - 1] == b'@' {
-                    neighbor_counts[idx] += 1;
-                }
-                if col < cols - 1 && data[idx + 1] == b'@' {
-                    neighbor_counts[idx] += 1;
-                }
+        let grid = Grid::parse(input);
 
-                if row < rows - 1 {
-                    let below = idx + cols;
-                    if col > 0 && data[below - 1] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
-                    if data[below] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
-                    if col < cols - 1 && data[below + 1] == b'@' {
-                        neighbor_counts[idx] += 1;
-                    }
+        // Pre-compute neighbor counts for all rolls
+        let neighbor_counts = (0..grid.len())
+            .map(|idx| {
+                if grid[idx] == b'@' {
+                    count_adjacent(&grid, idx) as u8
+                } else {
+                    0
                 }
-            }
-        }
+            })
+            .collect();
 
         Self {
-            data,
+            grid,
             neighbor_counts,
-            cols,
-            rows,
-        }
-    }
-
-    /// Get all valid neighbor indices for a position
-    #[inline]
-    fn neighbors(&self, idx: usize) -> [Option<usize>; 8] {
-        let row = idx / self.cols;
-        let col = idx % self.cols;
-        let mut result = [None; 8];
-        let mut i = 0;
-
-        // Top row
-        if row > 0 {
-            let above = idx - self.cols;
-            if col > 0 {
-                result[i] = Some(above - 1);
-                i += 1;
-            }
-            result[i] = Some(above);
-            i += 1;
-            if col < self.cols - 1 {
-                result[i] = Some(above + 1);
-                i += 1;
-            }
-        }
-
-        // Same row
-        if col > 0 {
-            result[i] = Some(idx - 1);
-            i += 1;
-        }
-        if col < self.cols - 1 {
-            result[i] = Some(idx + 1);
-            i += 1;
-        }
-
-        // Bottom row
-        if row < self.rows - 1 {
-            let below = idx + self.cols;
-            if col > 0 {
-                result[i] = Some(below - 1);
-                i += 1;
-            }
-            result[i] = Some(below);
-            i += 1;
-            if col < self.cols - 1 {
-                result[i] = Some(below + 1);
-            }
         }
-
-        result
     }
 }
 
@@ -400,50 +203,124 @@ impl Day for Day04 {
     fn part1(&self, input: &str) -> String {
         // Use dirty tracking approach for part 1 (faster without pre-computation overhead)
         let grid = Grid::parse(input);
-        let accessible = (0..grid.data.len())
-            .filter(|&idx| grid.get(idx) == b'@' && grid.count_adjacent(idx) < 4)
+        let accessible = (0..grid.len())
+            .filter(|&idx| grid[idx] == b'@' && count_adjacent(&grid, idx) < 4)
             .count();
 
         accessible.to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        use std::collections::VecDeque;
-
         // Use neighbor count tracking for part 2 (faster for iterative removal)
-        let mut grid = GridWithCounts::parse(input);
-        let mut total_removed = 0;
+        remove_all_rolls(GridWithCounts::parse(input), None).to_string()
+    }
 
-        // Start with all rolls that can be removed
-        let mut queue: VecDeque<usize> = (0..grid.data.len())
-            .filter(|&idx| grid.data[idx] == b'@' && grid.neighbor_counts[idx] < 4)
-            .collect();
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 
-        while let Some(idx) = queue.pop_front() {
+    fn visualize(&self, input: &str, observer: &mut dyn Observer) -> bool {
+        remove_all_rolls(GridWithCounts::parse(input), Some(observer));
+        true
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+}
+
+/// Generates a `size` × `size` grid with rolls on about 65% of the cells,
+/// the density of the real input
+impl InputGenerator for Day04 {
+    fn default_size(&self) -> usize {
+        137
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            for _ in 0..size {
+                input.push(if rng.chance(0.65) { '@' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
+
+/// Remove rolls until none is accessible, returning how many were removed
+///
+/// Works in rounds: every roll accessible at the start of a round is removed,
+/// and the neighbours that become accessible make up the next round. The
+/// observer sees the grid before the first round and after each one, with the
+/// rolls that round removed highlighted.
+fn remove_all_rolls(rolls: GridWithCounts, mut observer: Option<&mut dyn Observer>) -> usize {
+    let GridWithCounts {
+        mut grid,
+        mut neighbor_counts,
+    } = rolls;
+    let mut total_removed = 0;
+
+    // Start with all rolls that can be removed
+    let mut round: Vec<usize> = (0..grid.len())
+        .filter(|&idx| grid[idx] == b'@' && neighbor_counts[idx] < 4)
+        .collect();
+    let mut next_round = Vec::new();
+    let mut removed = Vec::new();
+    viz::emit(&mut observer, || render_rolls(&grid, &[]));
+
+    while !round.is_empty() {
+        for &idx in &round {
             // Skip if already removed
-            if grid.data[idx] != b'@' {
+            if grid[idx] != b'@' {
                 continue;
             }
 
             // Remove this roll
-            grid.data[idx] = b'.';
-            total_removed += 1;
+            grid[idx] = b'.';
+            removed.push(idx);
 
-            // Update neighbor counts and add newly accessible neighbors to queue
-            for neighbor_idx in grid.neighbors(idx).iter().filter_map(|&n| n) {
-                if grid.data[neighbor_idx] == b'@' {
-                    grid.neighbor_counts[neighbor_idx] -= 1;
+            // Update neighbor counts and queue newly accessible neighbors
+            for neighbor_idx in grid.neighbors8(idx) {
+                if grid[neighbor_idx] == b'@' {
+                    neighbor_counts[neighbor_idx] -= 1;
 
-                    // If this neighbor just became accessible, add it to queue
-                    if grid.neighbor_counts[neighbor_idx] < 4 {
-                        queue.push_back(neighbor_idx);
+                    // If this neighbor just became accessible, add it to the next round
+                    if neighbor_counts[neighbor_idx] < 4 {
+                        next_round.push(neighbor_idx);
                     }
                 }
             }
         }
 
-        total_removed.to_string()
+        // A round of rolls already removed by the one before removes nothing
+        if !removed.is_empty() {
+            total_removed += removed.len();
+            viz::emit(&mut observer, || render_rolls(&grid, &removed));
+            removed.clear();
+        }
+        round.clear();
+        std::mem::swap(&mut round, &mut next_round);
     }
+
+    total_removed
+}
+
+/// Draw the rolls, with the ones just removed in red
+fn render_rolls(grid: &Grid<u8>, removed: &[usize]) -> Frame {
+    let mut frame = viz::render(grid, |&cell| match cell {
+        b'@' => Rgb(222, 206, 170),
+        _ => Rgb(32, 32, 40),
+    });
+    for &idx in removed {
+        frame[idx] = Rgb(214, 64, 48);
+    }
+    frame
+}
+
+/// Check that the input is a rectangular grid of `@` and `.`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    validate::grid(input, &['@', '.'], "`@` or `.`")
 }
 
 #[cfg(test)]
@@ -474,6 +351,46 @@ mod tests {
         assert_eq!(day.part2(EXAMPLE), "43");
     }
 
+    #[test]
+    fn test_visualize() {
+        let mut recorder = viz::Recorder::new();
+        assert!(Day04.visualize(EXAMPLE, &mut recorder));
+
+        // The starting grid, then one frame per round of removals
+        let frames = &recorder.frames;
+        let rolls = |frame: &Frame| {
+            frame
+                .cells()
+                .iter()
+                .filter(|&&c| c == Rgb(222, 206, 170))
+                .count()
+        };
+        let removed = |frame: &Frame| {
+            frame
+                .cells()
+                .iter()
+                .filter(|&&c| c == Rgb(214, 64, 48))
+                .count()
+        };
+        assert_eq!(rolls(&frames[0]), EXAMPLE.matches('@').count());
+        assert_eq!(removed(&frames[1]), 13);
+        assert_eq!(frames[1..].iter().map(removed).sum::<usize>(), 43);
+        assert!(frames[1..].iter().all(|frame| removed(frame) > 0));
+    }
+
+    #[test]
+    fn test_generator() {
+        let input = Day04.generate(40, &mut Rng::new(2));
+        assert!(validate_input(&input).is_ok());
+        assert_eq!(input.lines().count(), 40);
+        assert!(input.lines().all(|row| row.len() == 40));
+
+        let expected = (Day04.part1(&input), Day04.part2(&input));
+        for day in [&Day04Naive as &dyn Day, &Day04DirtyTracking] {
+            assert_eq!((day.part1(&input), day.part2(&input)), expected);
+        }
+    }
+
     #[test]
     fn test_part1_example_naive() {
         let day = Day04Naive;
@@ -4,13 +4,19 @@
 //!
 //! # Part 1
 //! Count how many ingredient IDs from a list fall within any of the fresh ranges.
-//! Uses a simple O(n×m) linear search approach which is optimal for the input size.
+//! The ranges are merged into an [`IntervalSet`], so each lookup is a binary
+//! search: O((n + m) log n) overall.
 //!
 //! # Part 2
 //! Count the total number of unique IDs covered by all ranges.
-//! Uses range merging to avoid double-counting overlapping ranges.
+//! Merging the ranges removes overlaps, so this is the set's total length.
 
-use crate::runner::Day;
+use crate::common::ranges::IntervalSet;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::common::ParseError;
+use crate::runner::{Day, SolveContext, StreamingDay};
+use crate::stream::LineReader;
+use std::io::{self, BufRead};
 
 /// Solver for Day 5
 pub struct Day05;
@@ -18,18 +24,15 @@ pub struct Day05;
 /// Parse the input into fresh ingredient ranges and ingredient IDs to check.
 ///
 /// The input format is two sections separated by a blank line:
-/// - First section: ranges in format "start-end" (inclusive)
+/// - First section: ranges in format "start-end" (inclusive), merged into a set
 /// - Second section: individual ingredient IDs, one per line
-fn parse_input(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
+fn parse_input(input: &str) -> (IntervalSet<u64>, Vec<u64>) {
     let parts: Vec<&str> = input.split("\n\n").collect();
 
     // Parse ranges in format "start-end"
     let ranges = parts[0]
         .lines()
-        .map(|line| {
-            let nums: Vec<u64> = line.split('-').map(|n| n.parse().unwrap()).collect();
-            (nums[0], nums[1])
-        })
+        .map(|line| parse_range(line).unwrap())
         .collect();
 
     // Parse ingredient IDs to check
@@ -42,74 +45,121 @@ fn parse_input(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
     (ranges, ids)
 }
 
-/// Check if an ingredient ID is fresh (falls within any range).
-///
-/// Uses `.any()` for early termination - stops checking as soon as a match is found.
-fn is_fresh(id: u64, ranges: &[(u64, u64)]) -> bool {
-    ranges.iter().any(|&(start, end)| id >= start && id <= end)
+/// Parse a fresh range in format "start-end"
+fn parse_range(line: &str) -> Option<(u64, u64)> {
+    let (start, end) = line.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?))
 }
 
-/// Merge overlapping or adjacent ranges into non-overlapping ranges.
-///
-/// Algorithm:
-/// 1. Sort ranges by start position
-/// 2. Iterate through sorted ranges, merging when they overlap or are adjacent
-/// 3. Adjacent ranges (e.g., 5-10 and 11-20) are merged into continuous ranges
-///
-/// Time complexity: O(n log n) for sorting, O(n) for merging
-fn merge_ranges(ranges: &[(u64, u64)]) -> Vec<(u64, u64)> {
-    if ranges.is_empty() {
-        return vec![];
-    }
-
-    // Sort ranges by start position
-    let mut sorted = ranges.to_vec();
-    sorted.sort_by_key(|&(start, _)| start);
-
-    let mut merged = vec![sorted[0]];
-
-    for &(start, end) in &sorted[1..] {
-        let last_idx = merged.len() - 1;
-        let (last_start, last_end) = merged[last_idx];
-
-        // Check if current range overlaps or is adjacent (start <= last_end + 1)
-        // This handles both overlapping ranges and adjacent ranges
-        if start <= last_end + 1 {
-            // Merge by extending the end if the current range extends further
-            merged[last_idx] = (last_start, last_end.max(end));
-        } else {
-            // No overlap, add as new range
-            merged.push((start, end));
-        }
+/// A streamed line that is not what its section expects
+fn invalid_line(line_no: usize, line: &str) -> ParseError {
+    ParseError {
+        line: line_no,
+        text: line.trim().to_string(),
     }
-
-    merged
 }
 
-/// Count the total number of unique ingredient IDs covered by all ranges.
+/// Read the ranges section of a streamed input, up to the blank line
 ///
-/// First merges overlapping ranges to avoid double-counting, then sums the sizes.
-fn count_ids_in_ranges(ranges: &[(u64, u64)]) -> u64 {
-    let merged = merge_ranges(ranges);
-    merged.iter().map(|&(start, end)| end - start + 1).sum()
+/// Only the ranges are held in memory; the reader is left at the first ID.
+fn read_ranges_stream<R: BufRead>(lines: &mut LineReader<R>) -> io::Result<IntervalSet<u64>> {
+    let mut ranges = Vec::new();
+    while let Some((line_no, line)) = lines.next_line()? {
+        if line.is_empty() {
+            break;
+        }
+        ranges.push(parse_range(line).ok_or_else(|| invalid_line(line_no, line))?);
+    }
+    Ok(ranges.into_iter().collect())
 }
 
 impl Day for Day05 {
     fn part1(&self, input: &str) -> String {
         let (ranges, ids) = parse_input(input);
-        let count = ids.iter().filter(|&&id| is_fresh(id, &ranges)).count();
+        let count = ids.iter().filter(|&&id| ranges.contains(id)).count();
         count.to_string()
     }
 
     fn part2(&self, input: &str) -> String {
         let (ranges, _) = parse_input(input);
-        let count = count_ids_in_ranges(&ranges);
-        count.to_string()
+        ranges.total_len().to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn streaming(&self) -> Option<&dyn StreamingDay> {
+        Some(self)
+    }
+}
+
+impl StreamingDay for Day05 {
+    fn part1_stream(&self, input: &mut dyn BufRead, _ctx: &SolveContext) -> io::Result<String> {
+        let mut lines = LineReader::new(input);
+        let ranges = read_ranges_stream(&mut lines)?;
+        let mut count = 0;
+        while let Some((line_no, line)) = lines.next_line()? {
+            if line.is_empty() {
+                continue;
+            }
+            let id = line.parse().map_err(|_| invalid_line(line_no, line))?;
+            if ranges.contains(id) {
+                count += 1;
+            }
+        }
+        Ok(count.to_string())
+    }
+
+    fn part2_stream(&self, input: &mut dyn BufRead, _ctx: &SolveContext) -> io::Result<String> {
+        let ranges = read_ranges_stream(&mut LineReader::new(input))?;
+        Ok(ranges.total_len().to_string())
     }
 }
 
 // Helper functions
 
+/// Check for `start-end` ranges, a blank line, then one ingredient ID per line
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    let mut lines = input.lines().enumerate();
+    let mut range_count = 0;
+
+    for (idx, line) in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.number()?;
+        cursor.expect('-')?;
+        cursor.number()?;
+        cursor.finish()?;
+        range_count += 1;
+    }
+
+    if range_count == 0 {
+        return Err(match input.lines().next() {
+            Some(line) => Diagnostic::at(1, line, 0, "a fresh ingredient range"),
+            None => Diagnostic::end_of_input(input, "a fresh ingredient range"),
+        });
+    }
+    if input.lines().count() == range_count {
+        return Err(Diagnostic::end_of_input(
+            input,
+            "a blank line followed by ingredient IDs",
+        ));
+    }
+
+    for (idx, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.number()?;
+        cursor.finish()?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +188,59 @@ mod tests {
         let day = Day05;
         assert_eq!(day.part2(EXAMPLE), "14");
     }
+
+    #[test]
+    fn test_streaming_matches() {
+        let ctx = SolveContext::default();
+        let input = format!("{} \r\n\r\n", EXAMPLE.replace('\n', "\r\n"));
+        assert_eq!(
+            Day05.part1_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "3"
+        );
+        assert_eq!(
+            Day05.part2_stream(&mut input.as_bytes(), &ctx).unwrap(),
+            "14"
+        );
+
+        // Part 2 stops reading at the blank line, so IDs are never parsed
+        let ranges_only = "3-5\n10-14\n\nnot an id\n";
+        assert_eq!(
+            Day05
+                .part2_stream(&mut ranges_only.as_bytes(), &ctx)
+                .unwrap(),
+            "8"
+        );
+    }
+
+    #[test]
+    fn test_streaming_rejects_malformed_lines() {
+        let ctx = SolveContext::default();
+        let bad_range = "3-5\n10~14\n\n1\n";
+        let bad_id = "3-5\n\n1\nfive\n";
+        for (input, message) in [
+            (bad_range, "Invalid input on line 2: \"10~14\""),
+            (bad_id, "Invalid input on line 4: \"five\""),
+        ] {
+            let err = Day05.part1_stream(&mut input.as_bytes(), &ctx).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(err.to_string(), message);
+        }
+        let err = Day05.part2_stream(&mut bad_range.as_bytes(), &ctx);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_input(EXAMPLE).is_ok());
+
+        let err = validate_input("3-5\n10-14\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.expected, "a blank line followed by ingredient IDs");
+
+        let err = validate_input("3-5\n10:14\n\n1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.to_string(), "expected `-`, found `:`");
+    }
 }
 
 // Define benchmarks using the common macro
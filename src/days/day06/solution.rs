@@ -3,6 +3,7 @@
 //! Solves a math worksheet where numbers are arranged vertically in columns.
 //! Each column represents a problem with an operation at the bottom row.
 
+use crate::common::validate::{Cursor, Diagnostic};
 use crate::runner::Day;
 
 /// Solver for Day 6
@@ -90,6 +91,15 @@ impl Day for Day06 {
 
         grand_total.to_string()
     }
+
+    /// The worksheet is column-aligned, so trailing spaces are significant
+    fn preserve_whitespace(&self) -> bool {
+        true
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 // Helper functions
@@ -231,6 +241,36 @@ fn parse_and_calculate_problem_rtl(
     calculate_result(&all_numbers, operation)
 }
 
+/// Check for rows of numbers followed by a row of `+`/`*` operators
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    let lines: Vec<&str> = input.lines().collect();
+    if lines.len() < 2 {
+        return Err(Diagnostic::end_of_input(
+            input,
+            "rows of numbers followed by a row of operators",
+        ));
+    }
+
+    let (operators, numbers) = lines.split_last().unwrap();
+    for (idx, line) in numbers.iter().enumerate() {
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.take_while(|ch| ch == ' ' || ch.is_ascii_digit());
+        cursor
+            .finish()
+            .map_err(|_| cursor.error("a digit or a space"))?;
+    }
+
+    let mut cursor = Cursor::new(lines.len(), operators);
+    cursor.skip_spaces();
+    if cursor.is_done() {
+        return Err(cursor.error("`+` or `*`"));
+    }
+    while !cursor.is_done() {
+        cursor.one_of(&['+', '*', ' '], "`+`, `*` or a space")?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,9 @@
 //! Part 1: Tachyon beam splitter simulation. Beams merge at same column. Count splits.
 //! Part 2: Quantum timeline counting. Particles don't merge. Count distinct timelines.
 
+use crate::common::grid::Grid;
+use crate::common::validate::{self, Diagnostic};
+use crate::common::viz::{self, Frame, Observer, Rgb};
 use crate::runner::Day;
 use std::collections::{HashMap, HashSet};
 
@@ -11,75 +14,102 @@ pub struct Day07;
 
 impl Day for Day07 {
     fn part1(&self, input: &str) -> String {
-        count_splits(input).to_string()
+        count_splits(&Grid::parse(input), None).to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        count_timelines(input).to_string()
+        count_timelines(&Grid::parse(input)).to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn visualize(&self, input: &str, observer: &mut dyn Observer) -> bool {
+        count_splits(&Grid::parse(input), Some(observer));
+        true
     }
 }
 
-fn count_splits(input: &str) -> usize {
-    let lines: Vec<&str> = input.lines().collect();
-    if lines.is_empty() {
+/// Count the splitters beams hit, showing `observer` the beams row by row
+fn count_splits(grid: &Grid<u8>, mut observer: Option<&mut dyn Observer>) -> usize {
+    if grid.is_empty() {
         return 0;
     }
 
-    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let width = grid.width();
 
     // Find starting position
-    let (start_row, start_col) = find_start(&lines, width).unwrap_or_default();
+    let (start_col, start_row) = grid.position(|&c| c == b'S').unwrap_or_default();
 
     // Build splitter positions per row
-    let splitter_map = build_splitter_map(&lines, width);
+    let splitter_map = build_splitter_map(grid);
 
     // Simulate beam propagation
     let mut active_beams: HashSet<usize> = HashSet::new();
     active_beams.insert(start_col);
     let mut split_count = 0;
 
-    for splitters in splitter_map.iter().skip(start_row + 1) {
+    // Beams drawn so far, only kept while someone is watching
+    let mut trail = observer.is_some().then(|| grid.clone());
+    viz::emit(&mut observer, || render_beams(grid));
+
+    for (row, splitters) in splitter_map.iter().enumerate().skip(start_row + 1) {
         if active_beams.is_empty() {
             break;
         }
 
-        if splitters.is_empty() {
-            continue;
-        }
-
-        // Find beams that hit splitters
-        let hits: Vec<usize> = active_beams.intersection(splitters).copied().collect();
+        if !splitters.is_empty() {
+            // Find beams that hit splitters
+            let hits: Vec<usize> = active_beams.intersection(splitters).copied().collect();
 
-        split_count += hits.len();
+            split_count += hits.len();
 
-        // Update beams: remove hits, add left+right neighbors
-        for col in hits {
-            active_beams.remove(&col);
-            if col > 0 {
-                active_beams.insert(col - 1);
+            // Update beams: remove hits, add left+right neighbors
+            for col in hits {
+                active_beams.remove(&col);
+                if col > 0 {
+                    active_beams.insert(col - 1);
+                }
+                if col < width - 1 {
+                    active_beams.insert(col + 1);
+                }
             }
-            if col < width - 1 {
-                active_beams.insert(col + 1);
+        }
+
+        if let Some(trail) = trail.as_mut() {
+            for &col in &active_beams {
+                trail[(col, row)] = b'|';
             }
+            viz::emit(&mut observer, || render_beams(trail));
         }
     }
 
     split_count
 }
 
-fn count_timelines(input: &str) -> u64 {
-    let lines: Vec<&str> = input.lines().collect();
-    if lines.is_empty() {
+/// Draw the manifold, with beams as `|`
+fn render_beams(grid: &Grid<u8>) -> Frame {
+    viz::render(grid, |&cell| match cell {
+        b'^' => Rgb(200, 200, 210),
+        b'S' => Rgb(250, 210, 60),
+        b'|' => Rgb(90, 200, 255),
+        _ => Rgb(16, 20, 36),
+    })
+}
+
+fn count_timelines(grid: &Grid<u8>) -> u64 {
+    if grid.is_empty() {
         return 0;
     }
 
-    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let width = grid.width();
 
     // Find starting position
-    let (start_row, start_col) = find_start(&lines, width).unwrap_or_default();
+    let (start_col, start_row) = grid.position(|&c| c == b'S').unwrap_or_default();
 
     // Build splitter positions per row
-    let splitter_map = build_splitter_map(&lines, width);
+    let splitter_map = build_splitter_map(grid);
 
     // Simulate particle propagation with timeline counting
     let mut particle_counts: HashMap<usize, u64> = HashMap::new();
@@ -119,29 +149,34 @@ fn count_timelines(input: &str) -> u64 {
     particle_counts.values().sum()
 }
 
-fn find_start(lines: &[&str], _width: usize) -> Option<(usize, usize)> {
-    for (row, line) in lines.iter().enumerate() {
-        for (col, ch) in line.chars().enumerate() {
-            if ch == 'S' {
-                return Some((row, col));
-            }
-        }
-    }
-    None
+/// Columns holding a splitter, for each row
+fn build_splitter_map(grid: &Grid<u8>) -> Vec<HashSet<usize>> {
+    grid.rows()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &c)| c == b'^')
+                .map(|(col, _)| col)
+                .collect()
+        })
+        .collect()
 }
 
-fn build_splitter_map(lines: &[&str], _width: usize) -> Vec<HashSet<usize>> {
-    let mut splitter_map = vec![HashSet::new(); lines.len()];
+/// Check for a rectangular grid of `.`, `^` and a single start `S`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    validate::grid(input, &['.', '^', 'S'], "`.`, `^` or `S`")?;
 
-    for (row, line) in lines.iter().enumerate() {
-        for (col, ch) in line.chars().enumerate() {
-            if ch == '^' {
-                splitter_map[row].insert(col);
-            }
-        }
+    let mut starts = input.lines().enumerate().flat_map(|(idx, line)| {
+        line.match_indices('S')
+            .map(move |(pos, _)| (idx, line, pos))
+    });
+    if starts.next().is_none() {
+        return Err(Diagnostic::end_of_input(input, "a start position `S`"));
     }
-
-    splitter_map
+    if let Some((idx, line, pos)) = starts.next() {
+        return Err(Diagnostic::at(idx + 1, line, pos, "only one `S`"));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -177,6 +212,23 @@ mod tests {
         let day = Day07;
         assert_eq!(day.part2(EXAMPLE), "40");
     }
+
+    #[test]
+    fn test_visualize() {
+        let mut recorder = viz::Recorder::new();
+        assert!(Day07.visualize(EXAMPLE, &mut recorder));
+
+        // The empty manifold, then a frame for each row below the start
+        assert_eq!(recorder.frames.len(), 16);
+        let beam = Rgb(90, 200, 255);
+        let last = recorder.frames.last().unwrap();
+        let beams: String = (0..15)
+            .map(|x| if last[(x, 2)] == beam { '|' } else { '.' })
+            .collect();
+        assert_eq!(beams, "......|.|......");
+        assert_eq!(last[(7, 1)], beam);
+        assert!(recorder.frames[0].cells().iter().all(|&c| c != beam));
+    }
 }
 
 // Define benchmarks using the common macro
@@ -9,7 +9,13 @@
 //! ## Algorithm
 //!
 //! TODO: Describe algorithm approach
+//!
+//! ### Alternative Implementations
+//!
+//! - `Day08KdTree`: Streams closest pairs from a k-d tree and builds the
+//!   spanning tree with Borůvka's algorithm, so it scales to inputs far larger
+//!   than the puzzle's 1000 boxes
 
 mod solution;
 
-pub use solution::Day08;
+pub use solution::{Day08, Day08KdTree};
@@ -1,76 +1,35 @@
 //! Solution implementation for Day 8
 
-use crate::runner::Day;
+use crate::common::dot::Dot;
+use crate::common::geometry::Point3;
+use crate::common::graph::UnionFind;
+use crate::common::rng::Rng;
+use crate::common::spatial::KdTree;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::runner::{Day, InputGenerator, Param, ParamKind, SolveContext, Witness};
+use std::collections::{HashMap, HashSet};
 
 /// Solver for Day 8
 pub struct Day08;
 
-/// Union-Find data structure with path compression and union by size
-struct UnionFind {
-    parent: Vec<usize>,
-    size: Vec<usize>,
-    components: usize,
-}
-
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            size: vec![1; n],
-            components: n,
-        }
-    }
-
-    fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] != x {
-            self.parent[x] = self.find(self.parent[x]);
-        }
-        self.parent[x]
-    }
-
-    fn union(&mut self, x: usize, y: usize) -> bool {
-        let root_x = self.find(x);
-        let root_y = self.find(y);
-
-        if root_x == root_y {
-            return false;
-        }
-
-        // Union by size
-        if self.size[root_x] < self.size[root_y] {
-            self.parent[root_x] = root_y;
-            self.size[root_y] += self.size[root_x];
-        } else {
-            self.parent[root_y] = root_x;
-            self.size[root_x] += self.size[root_y];
-        }
-
-        self.components -= 1;
-        true
-    }
-
-    fn get_component_sizes(&mut self) -> Vec<usize> {
-        // Use array instead of HashMap for better performance
-        let mut counts = vec![0usize; self.parent.len()];
-        for i in 0..self.parent.len() {
-            let root = self.find(i);
-            counts[root] += 1;
-        }
-
-        // Collect non-zero counts and sort descending
-        let mut sizes: Vec<usize> = counts.into_iter().filter(|&c| c > 0).collect();
-        sizes.sort_unstable_by(|a, b| b.cmp(a));
-        sizes
-    }
-}
-
-fn parse_coordinates(input: &str) -> Vec<(i64, i64, i64)> {
+/// Tunable parameters for Day 8
+const PARAMS: &[Param] = &[Param {
+    name: "pairs",
+    default: "1000",
+    description: "Number of closest pairs to connect in part 1",
+    kind: ParamKind::Integer {
+        min: 0,
+        max: u32::MAX as u64,
+    },
+}];
+
+fn parse_coordinates(input: &str) -> Vec<Point3> {
     input
         .lines()
         .filter(|line| !line.is_empty())
         .map(|line| {
             let parts: Vec<&str> = line.split(',').collect();
-            (
+            Point3::new(
                 parts[0].parse().unwrap_or(0),
                 parts[1].parse().unwrap_or(0),
                 parts[2].parse().unwrap_or(0),
@@ -79,18 +38,11 @@ fn parse_coordinates(input: &str) -> Vec<(i64, i64, i64)> {
         .collect()
 }
 
-fn distance_squared(p1: (i64, i64, i64), p2: (i64, i64, i64)) -> i64 {
-    let dx = p1.0 - p2.0;
-    let dy = p1.1 - p2.1;
-    let dz = p1.2 - p2.2;
-    dx * dx + dy * dy + dz * dz
-}
-
-fn solve_part1(coordinates: &[(i64, i64, i64)], pairs_to_process: usize) -> i64 {
+fn solve_part1(coordinates: &[Point3], pairs_to_process: usize) -> i64 {
     let n = coordinates.len();
 
     // Pre-allocate exact size needed
-    let total_edges = (n * (n - 1)) / 2;
+    let total_edges = (n * n.saturating_sub(1)) / 2;
     let mut edges = Vec::with_capacity(total_edges);
 
     // Compute all pairwise distances in one pass
@@ -99,18 +51,16 @@ fn solve_part1(coordinates: &[(i64, i64, i64)], pairs_to_process: usize) -> i64
     for i in 0..n {
         let ci = coordinates[i];
         for j in (i + 1)..n {
-            let cj = coordinates[j];
-            // Inline distance calculation for speed
-            let dx = ci.0 - cj.0;
-            let dy = ci.1 - cj.1;
-            let dz = ci.2 - cj.2;
-            edges.push((dx * dx + dy * dy + dz * dz, i, j));
+            edges.push((ci.distance_squared(coordinates[j]), i, j));
         }
     }
 
     // Use partial sort - only need the smallest pairs_to_process edges
-    edges.select_nth_unstable_by_key(pairs_to_process - 1, |e| e.0);
-    edges[..pairs_to_process].sort_unstable_by_key(|e| e.0);
+    let pairs_to_process = pairs_to_process.min(edges.len());
+    if pairs_to_process > 0 {
+        edges.select_nth_unstable_by_key(pairs_to_process - 1, |e| e.0);
+        edges[..pairs_to_process].sort_unstable_by_key(|e| e.0);
+    }
 
     // Process the specified number of closest pairs with Union-Find
     let mut uf = UnionFind::new(n);
@@ -118,8 +68,12 @@ fn solve_part1(coordinates: &[(i64, i64, i64)], pairs_to_process: usize) -> i64
         uf.union(*i, *j);
     }
 
-    // Find the 3 largest component sizes
-    let sizes = uf.get_component_sizes();
+    largest_circuits_product(&uf)
+}
+
+/// Product of the sizes of the 3 largest components
+fn largest_circuits_product(uf: &UnionFind) -> i64 {
+    let sizes = uf.component_sizes();
     if sizes.len() >= 3 {
         sizes[0] as i64 * sizes[1] as i64 * sizes[2] as i64
     } else if sizes.len() == 2 {
@@ -132,10 +86,15 @@ fn solve_part1(coordinates: &[(i64, i64, i64)], pairs_to_process: usize) -> i64
 }
 
 /// Solve Part 2 using optimized Prim's algorithm
-fn solve_part2_prim(coordinates: &[(i64, i64, i64)]) -> i64 {
+fn solve_part2_prim(coordinates: &[Point3]) -> i64 {
+    last_connection_prim(coordinates).map_or(0, |(i, j)| coordinates[i].x * coordinates[j].x)
+}
+
+/// The pair of boxes whose connection joins everything into one circuit
+fn last_connection_prim(coordinates: &[Point3]) -> Option<(usize, usize)> {
     let n = coordinates.len();
-    if n == 0 {
-        return 0;
+    if n < 2 {
+        return None;
     }
 
     let mut in_mst = vec![false; n];
@@ -146,7 +105,8 @@ fn solve_part2_prim(coordinates: &[(i64, i64, i64)]) -> i64 {
     min_dist[0] = 0;
 
     let mut edges_added = 0;
-    let mut last_edge = (0i64, 0i64);
+    let mut last_edge = (0, 0);
+    let mut longest = 0i64;
 
     // Simple O(n²) Prim's without heap - faster for dense graphs
     for _ in 0..n {
@@ -166,7 +126,12 @@ fn solve_part2_prim(coordinates: &[(i64, i64, i64)]) -> i64 {
 
         if u != 0 {
             edges_added += 1;
-            last_edge = (coordinates[parent[u]].0, coordinates[u].0);
+            // Connecting pairs in order finishes with the longest tree edge,
+            // which is not necessarily the last one Prim's algorithm adds
+            if min_val >= longest {
+                longest = min_val;
+                last_edge = (parent[u].min(u), parent[u].max(u));
+            }
 
             if edges_added == n - 1 {
                 break;
@@ -176,7 +141,7 @@ fn solve_part2_prim(coordinates: &[(i64, i64, i64)]) -> i64 {
         // Update distances to non-MST vertices
         for v in 0..n {
             if !in_mst[v] {
-                let dist = distance_squared(coordinates[u], coordinates[v]);
+                let dist = coordinates[u].distance_squared(coordinates[v]);
                 if dist < min_dist[v] {
                     min_dist[v] = dist;
                     parent[v] = u;
@@ -185,19 +150,262 @@ fn solve_part2_prim(coordinates: &[(i64, i64, i64)]) -> i64 {
         }
     }
 
-    last_edge.0 * last_edge.1
+    Some(last_edge)
 }
 
 impl Day for Day08 {
     fn part1(&self, input: &str) -> String {
-        let coordinates = parse_coordinates(input);
-        solve_part1(&coordinates, 1000).to_string()
+        self.part1_with(input, &SolveContext::from_defaults(PARAMS))
     }
 
     fn part2(&self, input: &str) -> String {
         let coordinates = parse_coordinates(input);
         solve_part2_prim(&coordinates).to_string()
     }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+        let coordinates = parse_coordinates(input);
+        solve_part1(&coordinates, ctx.get("pairs")).to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn draw_dot(&self, input: &str, ctx: &SolveContext) -> Option<String> {
+        Some(draw_circuits(&parse_coordinates(input), ctx.get("pairs")))
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+
+    fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        let coordinates = parse_coordinates(input);
+        let (i, j) = last_connection_prim(&coordinates)?;
+        let (a, b) = (coordinates[i], coordinates[j]);
+        let position = |p: Point3| format!("{},{},{}", p.x, p.y, p.z);
+        Some(vec![Witness::new(2, "final connection")
+            .with("from", position(a))
+            .with("to", position(b))
+            .with("distance²", a.distance_squared(b))])
+    }
+}
+
+/// Generates `size` distinct junction boxes with coordinates below 100000
+impl InputGenerator for Day08 {
+    fn default_size(&self) -> usize {
+        1000
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        // Widen the space for inputs too large to fit distinct points in it
+        let limit = 99_999.max(size as i64);
+        let mut seen = HashSet::with_capacity(size);
+        let mut input = String::with_capacity(size * 18);
+        while seen.len() < size {
+            let point = (
+                rng.range(0, limit),
+                rng.range(0, limit),
+                rng.range(0, limit),
+            );
+            if seen.insert(point) {
+                input.push_str(&format!("{},{},{}\n", point.0, point.1, point.2));
+            }
+        }
+        input
+    }
+}
+
+/// Fill colours of the circuits in [`draw_circuits`], reused when there are more
+const CIRCUIT_COLOURS: &[&str] = &[
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#bc80bd", "#ccebc5",
+];
+
+/// Draw the junction boxes and the circuits formed by the closest `pairs`
+///
+/// Boxes are numbered by input line, with their position as a tooltip. Boxes
+/// in a circuit share a fill colour and unconnected boxes are left grey. Pairs
+/// that were already in the same circuit when connected are dashed. The
+/// connection that finally joins everything (part 2) is drawn thick and red.
+fn draw_circuits(coordinates: &[Point3], pairs: usize) -> String {
+    let tree = KdTree::new(coordinates);
+    let mut uf = UnionFind::new(coordinates.len());
+    let connections: Vec<(usize, usize, bool)> = tree
+        .pairs_by_distance()
+        .take(pairs)
+        .map(|(_, i, j)| (i, j, uf.union(i, j)))
+        .collect();
+    let last = last_connection_prim(coordinates);
+
+    let mut dot = Dot::graph("circuits");
+    dot.defaults(
+        "node",
+        &[
+            ("shape", "circle"),
+            ("style", "filled"),
+            ("fillcolor", "#eeeeee"),
+            ("fontsize", "10"),
+        ],
+    );
+
+    let mut colours = HashMap::new();
+    for (i, p) in coordinates.iter().enumerate() {
+        let position = format!("{},{},{}", p.x, p.y, p.z);
+        let root = uf.find(i);
+        if uf.size(root) > 1 {
+            let next = CIRCUIT_COLOURS[colours.len() % CIRCUIT_COLOURS.len()];
+            let colour = *colours.entry(root).or_insert(next);
+            dot.node(
+                &i.to_string(),
+                &[("fillcolor", colour), ("tooltip", &position)],
+            );
+        } else {
+            dot.node(&i.to_string(), &[("tooltip", &position)]);
+        }
+    }
+
+    for (i, j, joined) in connections {
+        if Some((i, j)) == last {
+            continue;
+        }
+        let attrs: &[(&str, &str)] = if joined { &[] } else { &[("style", "dashed")] };
+        dot.edge(&i.to_string(), &j.to_string(), attrs);
+    }
+    if let Some((i, j)) = last {
+        let distance = coordinates[i].distance_squared(coordinates[j]).to_string();
+        dot.edge(
+            &i.to_string(),
+            &j.to_string(),
+            &[
+                ("color", "#d62828"),
+                ("penwidth", "3"),
+                ("label", &format!("last, d²={}", distance)),
+            ],
+        );
+    }
+    dot.finish()
+}
+
+//=============================================================================
+// Alternative Implementation: k-d Tree
+//=============================================================================
+
+/// Solver using a k-d tree, subquadratic in the number of junction boxes
+///
+/// Part 1 streams only the closest pairs it needs instead of building all
+/// n(n-1)/2 edges. Part 2 builds the minimum spanning tree with Borůvka's
+/// algorithm, finding each component's cheapest outgoing edge with
+/// nearest-neighbour queries. The last pair that joins everything into one
+/// circuit is the most expensive edge of that tree.
+pub struct Day08KdTree;
+
+impl Day for Day08KdTree {
+    fn part1(&self, input: &str) -> String {
+        self.part1_with(input, &SolveContext::from_defaults(PARAMS))
+    }
+
+    fn part2(&self, input: &str) -> String {
+        let coordinates = parse_coordinates(input);
+        solve_part2_boruvka(&coordinates).to_string()
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+        let coordinates = parse_coordinates(input);
+        solve_part1_kd_tree(&coordinates, ctx.get("pairs")).to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn draw_dot(&self, input: &str, ctx: &SolveContext) -> Option<String> {
+        Some(draw_circuits(&parse_coordinates(input), ctx.get("pairs")))
+    }
+}
+
+/// Connect the closest pairs, taken lazily from the tree in distance order
+fn solve_part1_kd_tree(coordinates: &[Point3], pairs_to_process: usize) -> i64 {
+    let tree = KdTree::new(coordinates);
+    let mut uf = UnionFind::new(coordinates.len());
+    for (_, i, j) in tree.pairs_by_distance().take(pairs_to_process) {
+        uf.union(i, j);
+    }
+    largest_circuits_product(&uf)
+}
+
+/// Find the last connection with Borůvka's algorithm
+///
+/// Each round finds, for every component, its cheapest edge to another
+/// component and adds them all, at least halving the number of components.
+/// Edges are ordered by `(distance², lower index, higher index)` so ties can't
+/// create cycles. A component's best edge so far bounds the queries of its
+/// remaining points, so most queries only look at their immediate surroundings.
+fn solve_part2_boruvka(coordinates: &[Point3]) -> i64 {
+    let n = coordinates.len();
+    if n < 2 {
+        return 0;
+    }
+
+    let tree = KdTree::new(coordinates);
+    let mut uf = UnionFind::new(n);
+    let mut longest = (0i64, 0usize, 0usize);
+    let mut component = vec![0usize; n];
+
+    while uf.components() > 1 {
+        for (i, c) in component.iter_mut().enumerate() {
+            *c = uf.find(i);
+        }
+
+        // Cheapest outgoing edge per component root
+        let mut cheapest: Vec<Option<(i64, usize, usize)>> = vec![None; n];
+        for i in 0..n {
+            let root = component[i];
+            let bound = cheapest[root].map_or(i64::MAX, |edge| edge.0);
+            let found = tree.nearest_within(coordinates[i], bound, |j| component[j] != root);
+            if let Some((dist, j)) = found {
+                let edge = (dist, i.min(j), i.max(j));
+                if cheapest[root].is_none_or(|best| edge < best) {
+                    cheapest[root] = Some(edge);
+                }
+            }
+        }
+
+        for (dist, i, j) in cheapest.into_iter().flatten() {
+            if uf.union(i, j) {
+                longest = longest.max((dist, i, j));
+            }
+        }
+    }
+
+    let (_, i, j) = longest;
+    coordinates[i].x * coordinates[j].x
+}
+
+/// Check that every line is a junction box position `X,Y,Z`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    if input.lines().next().is_none() {
+        return Err(Diagnostic::end_of_input(input, "a junction box position"));
+    }
+    for (idx, line) in input.lines().enumerate() {
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.signed()?;
+        cursor.expect(',')?;
+        cursor.signed()?;
+        cursor.expect(',')?;
+        cursor.signed()?;
+        cursor.finish()?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -229,8 +437,9 @@ mod tests {
     #[test]
     fn test_part1_example() {
         // The example uses 10 pairs, not 1000
-        let coordinates = parse_coordinates(EXAMPLE);
-        assert_eq!(solve_part1(&coordinates, 10), 40);
+        let day = Day08;
+        let ctx = SolveContext::from_defaults(day.params()).with("pairs", "10");
+        assert_eq!(day.part1_with(EXAMPLE, &ctx), "40");
     }
 
     #[test]
@@ -240,6 +449,107 @@ mod tests {
         let day = Day08;
         assert_eq!(day.part2(EXAMPLE), "25272");
     }
+
+    #[test]
+    fn test_explain() {
+        let day = Day08;
+        let ctx = SolveContext::from_defaults(day.params());
+        let witnesses = day.explain(EXAMPLE, &ctx).unwrap();
+        assert_eq!(witnesses.len(), 1);
+        assert_eq!(witnesses[0].fact("from"), Some("216,146,977"));
+        assert_eq!(witnesses[0].fact("to"), Some("117,168,530"));
+        assert_eq!(witnesses[0].fact("distance²"), Some("210094"));
+    }
+
+    #[test]
+    fn test_draw_dot() {
+        let ctx = SolveContext::from_defaults(PARAMS).with("pairs", "10");
+        let dot = Day08.draw_dot(EXAMPLE, &ctx).unwrap();
+        assert!(dot.starts_with("graph \"circuits\" {\n"));
+        assert_eq!(dot.matches("tooltip=").count(), 20);
+
+        // Ten connections make circuits of 5, 4, 2 and 2 with one pair
+        // already joined, leaving 7 boxes on their own
+        assert_eq!(dot.matches(" -- ").count(), 11);
+        assert_eq!(dot.matches("style=\"dashed\"").count(), 1);
+        assert_eq!(dot.matches(" [tooltip=").count(), 7);
+        let mut filled: Vec<usize> = CIRCUIT_COLOURS
+            .iter()
+            .map(|colour| dot.matches(&format!("fillcolor=\"{}\"", colour)).count())
+            .filter(|&boxes| boxes > 0)
+            .collect();
+        filled.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(filled, vec![5, 4, 2, 2]);
+
+        // Boxes 10 and 12 are (216,146,977) and (117,168,530)
+        assert!(dot.contains("\"10\" -- \"12\" [color=\"#d62828\""));
+        assert!(dot.contains("label=\"last, d²=210094\""));
+    }
+
+    #[test]
+    fn test_generator() {
+        let input = Day08.generate(300, &mut Rng::new(4));
+        assert!(validate_input(&input).is_ok());
+        let coordinates = parse_coordinates(&input);
+        let distinct: HashSet<_> = coordinates.iter().map(|p| (p.x, p.y, p.z)).collect();
+        assert_eq!(distinct.len(), 300);
+
+        let ctx = SolveContext::from_defaults(PARAMS).with("pairs", "200");
+        assert_eq!(
+            Day08KdTree.part1_with(&input, &ctx),
+            Day08.part1_with(&input, &ctx)
+        );
+        assert_eq!(Day08KdTree.part2(&input), Day08.part2(&input));
+    }
+
+    #[test]
+    fn test_kd_tree_example() {
+        let day = Day08KdTree;
+        let ctx = SolveContext::from_defaults(day.params()).with("pairs", "10");
+        assert_eq!(day.part1_with(EXAMPLE, &ctx), "40");
+        assert_eq!(day.part2(EXAMPLE), "25272");
+    }
+
+    #[test]
+    fn test_kd_tree_matches_brute_force() {
+        // Pseudo-random boxes spread widely enough that distances don't tie,
+        // since tied pairs may be connected in either order
+        let mut state = 7u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % 100_000
+        };
+        let input: String = (0..400)
+            .map(|_| format!("{},{},{}\n", next(), next(), next()))
+            .collect();
+        let coordinates = parse_coordinates(&input);
+
+        for pairs in [0, 1, 50, 400, 5000] {
+            assert_eq!(
+                solve_part1_kd_tree(&coordinates, pairs),
+                solve_part1(&coordinates, pairs),
+                "pairs = {}",
+                pairs
+            );
+        }
+
+        assert_eq!(
+            Day08KdTree.part2(&input),
+            Day08.part2(&input),
+            "last connection"
+        );
+    }
+
+    #[test]
+    fn test_part2_last_connection_is_longest_edge() {
+        // Prim's from the first box adds 10-11 last, but connecting pairs in
+        // order joins the two circuits with 1-10
+        let input = "0,0,0\n1,0,0\n10,0,0\n11,0,0";
+        assert_eq!(Day08.part2(input), "10");
+        assert_eq!(Day08KdTree.part2(input), "10");
+    }
 }
 
 // Define benchmarks using the common macro
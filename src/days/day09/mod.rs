@@ -9,19 +9,37 @@
 //!
 //! **Part 2**: Find the largest rectangle that only contains red or green tiles.
 //! Red tiles form a polygon (connected in order), and green tiles are on the
-//! polygon edges and inside it. This requires computational geometry: ray casting
-//! for point-in-polygon tests, cross products for point-on-segment checks, and
-//! caching to avoid redundant expensive calculations.
+//! polygon edges and inside it.
 //!
 //! ## Algorithm
 //!
-//! Part 2 uses:
-//! - **Ray Casting**: Point-in-polygon testing using horizontal ray intersections
-//! - **Cross Product**: Elegant collinearity check for point-on-segment validation
-//! - **HashMap Caching**: Memoize expensive geometric calculations
-//! - **Adaptive Sampling**: Dense sampling for small rectangles, sparse for large
-//! - **Bounding Box Pruning**: Early rejection of invalid rectangles
+//! ### Default Implementation: Day09Compressed
+//!
+//! Exact in O(n²) after an O(n²) precomputation:
+//! - **Coordinate Compression**: Only vertex coordinates and the gaps between
+//!   them matter, so the plane shrinks to at most (2n + 2)² cells
+//! - **Flood Fill**: Cells reachable from outside without crossing an edge are
+//!   the only invalid ones
+//! - **2D Prefix Sum**: Counts outside cells in any rectangle with four lookups
+//!
+//! ### Alternative Implementations
+//!
+//! Only `Day09Compressed` is exact. The others accept a rectangle after
+//! checking a subset of its tiles, so each one can accept a rectangle that
+//! crosses a gap in the polygon. All three return the whole bounding
+//! rectangle for the notched polygon in the tests.
+//!
+//! | Variant | Check | Exact | Part 2 on the real input |
+//! |---|---|---|---|
+//! | `Day09Compressed` | every tile, via prefix sums | yes | correct, ~5 ms |
+//! | `Day09Sampling` | edges every `width / 200` tiles, 9 interior points | no | correct, ~2.3 s |
+//! | `Day09SweepLine` | edges every `max(width, height) / 20` tiles, centre | no | too large, ~540 ms |
+//! | `Day09SpatialGrid` | corners, then edges against a grid of cell samples | no | too small, ~560 ms |
+//!
+//! `Day09SpatialGrid` can also reject valid rectangles: each grid cell takes
+//! the validity of its top-left corner, which may lie outside the polygon
+//! while the rectangle's tiles in that cell are inside.
 
 mod solution;
 
-pub use solution::Day09;
+pub use solution::{Day09, Day09Compressed, Day09Sampling, Day09SpatialGrid, Day09SweepLine};
@@ -1,12 +1,24 @@
 //! Solution implementation for Day 9
 
-use crate::runner::Day;
+use crate::common::geometry::{Point2, Polygon};
+use crate::common::grid::Grid;
+use crate::common::rng::Rng;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::common::viz::Svg;
+use crate::runner::{Day, InputGenerator, SolveContext, Witness};
 use std::collections::{HashMap, HashSet};
 
-/// Solver for Day 9
-pub struct Day09;
+/// Default solver for Day 9
+///
+/// Uses the exact coordinate-compression approach (`Day09Compressed`), which
+/// proves every rectangle it accepts and is far faster than sampling.
+#[allow(non_upper_case_globals)]
+pub const Day09: Day09Compressed = Day09Compressed;
 
-impl Day for Day09 {
+/// Solver for Day 9 that checks part 2 rectangles by sampling points
+pub struct Day09Sampling;
+
+impl Day for Day09Sampling {
     fn part1(&self, input: &str) -> String {
         let tiles = parse_tiles(input);
         let max_area = find_largest_rectangle(&tiles);
@@ -18,12 +30,16 @@ impl Day for Day09 {
         let max_area = find_largest_rectangle_in_polygon(&tiles);
         max_area.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 // Helper functions
 
 /// Parse the input to extract tile coordinates
-fn parse_tiles(input: &str) -> Vec<(i32, i32)> {
+fn parse_tiles(input: &str) -> Vec<Point2> {
     input
         .lines()
         .filter(|line| !line.trim().is_empty())
@@ -31,71 +47,82 @@ fn parse_tiles(input: &str) -> Vec<(i32, i32)> {
             let mut parts = line.split(',');
             let x = parts.next().unwrap().trim().parse().unwrap();
             let y = parts.next().unwrap().trim().parse().unwrap();
-            (x, y)
+            Point2::new(x, y)
         })
         .collect()
 }
 
 /// Find the largest rectangle area using any two tiles as opposite corners
-fn find_largest_rectangle(tiles: &[(i32, i32)]) -> i64 {
+fn find_largest_rectangle(tiles: &[Point2]) -> i64 {
+    largest_rectangle(tiles).map_or(0, |(a, b)| rectangle_area(a, b))
+}
+
+/// Opposite corners of the largest rectangle, or `None` for fewer than two tiles
+fn largest_rectangle(tiles: &[Point2]) -> Option<(Point2, Point2)> {
+    let mut best = None;
     let mut max_area = 0i64;
 
     // Check all pairs of tiles
     for i in 0..tiles.len() {
         for j in (i + 1)..tiles.len() {
-            let (x1, y1) = tiles[i];
-            let (x2, y2) = tiles[j];
-
-            // Calculate rectangle area (inclusive bounds)
-            let width = (x2 - x1).abs() as i64 + 1;
-            let height = (y2 - y1).abs() as i64 + 1;
-            let area = width * height;
-
-            max_area = max_area.max(area);
+            let area = rectangle_area(tiles[i], tiles[j]);
+            if area > max_area {
+                max_area = area;
+                best = Some((tiles[i], tiles[j]));
+            }
         }
     }
 
-    max_area
+    best
+}
+
+/// Describe the rectangle with opposite corners `a` and `b` for `part`
+fn rectangle_witness(part: u8, (a, b): (Point2, Point2)) -> Witness {
+    Witness::new(part, "largest rectangle")
+        .with("corners", format!("{},{} {},{}", a.x, a.y, b.x, b.y))
+        .with("area", rectangle_area(a, b))
+}
+
+/// Area of the rectangle with opposite corners `a` and `b` (inclusive bounds)
+fn rectangle_area(a: Point2, b: Point2) -> i64 {
+    ((b.x - a.x).abs() + 1) * ((b.y - a.y).abs() + 1)
 }
 
 /// Find the largest rectangle that only contains red or green tiles
 /// Red tiles form a polygon in order, green tiles are on edges and inside
-fn find_largest_rectangle_in_polygon(tiles: &[(i32, i32)]) -> i64 {
+fn find_largest_rectangle_in_polygon(tiles: &[Point2]) -> i64 {
     let mut max_area = 0i64;
+    let polygon = Polygon::new(tiles.to_vec());
 
     // Optimization 1: Create HashSet for O(1) red tile lookups
-    let tile_set: HashSet<(i32, i32)> = tiles.iter().copied().collect();
+    let tile_set: HashSet<Point2> = tiles.iter().copied().collect();
 
     // Optimization 2: Compute polygon bounding box for early rejection
-    let bbox_min_x = tiles.iter().map(|(x, _)| *x).min().unwrap();
-    let bbox_max_x = tiles.iter().map(|(x, _)| *x).max().unwrap();
-    let bbox_min_y = tiles.iter().map(|(_, y)| *y).min().unwrap();
-    let bbox_max_y = tiles.iter().map(|(_, y)| *y).max().unwrap();
+    let (bbox_min, bbox_max) = polygon.bounding_box().unwrap();
 
     // Optimization 3: Cache point validation results
-    let mut point_cache: HashMap<(i32, i32), bool> = HashMap::new();
+    let mut point_cache: HashMap<Point2, bool> = HashMap::new();
 
     // Check all pairs of red tiles as corners
     for i in 0..tiles.len() {
         for j in (i + 1)..tiles.len() {
-            let (x1, y1) = tiles[i];
-            let (x2, y2) = tiles[j];
+            let (a, b) = (tiles[i], tiles[j]);
 
             // Get rectangle bounds
-            let min_x = x1.min(x2);
-            let max_x = x1.max(x2);
-            let min_y = y1.min(y2);
-            let max_y = y1.max(y2);
+            let min_x = a.x.min(b.x);
+            let max_x = a.x.max(b.x);
+            let min_y = a.y.min(b.y);
+            let max_y = a.y.max(b.y);
 
             // Early rejection: rectangle must be within polygon bounding box
-            if min_x < bbox_min_x || max_x > bbox_max_x || min_y < bbox_min_y || max_y > bbox_max_y
+            if min_x < bbox_min.x || max_x > bbox_max.x || min_y < bbox_min.y || max_y > bbox_max.y
             {
                 continue;
             }
 
             // Check if rectangle is valid (only contains red/green tiles)
             if is_rectangle_valid(
-                tiles,
+                &polygon,
                 &tile_set,
                 &mut point_cache,
                 min_x,
@@ -103,10 +130,7 @@ fn find_largest_rectangle_in_polygon(tiles: &[(i32, i32)]) -> i64 {
                 min_y,
                 max_y,
             ) {
-                let width = (max_x - min_x) as i64 + 1;
-                let height = (max_y - min_y) as i64 + 1;
-                let area = width * height;
-                max_area = max_area.max(area);
+                max_area = max_area.max(rectangle_area(a, b));
             }
         }
     }
@@ -117,32 +141,32 @@ fn find_largest_rectangle_in_polygon(tiles: &[(i32, i32)]) -> i64 {
 /// Check if a rectangle only contains red or green tiles
 /// All four corners must be red or inside/on the polygon
 fn is_rectangle_valid(
-    polygon: &[(i32, i32)],
-    tile_set: &HashSet<(i32, i32)>,
-    point_cache: &mut HashMap<(i32, i32), bool>,
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
+    polygon: &Polygon,
+    tile_set: &HashSet<Point2>,
+    point_cache: &mut HashMap<Point2, bool>,
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
 ) -> bool {
     // Critical: ALL FOUR corners must be valid (red, on edge, or inside)
     let corners = [
-        (min_x, min_y),
-        (min_x, max_y),
-        (max_x, min_y),
-        (max_x, max_y),
+        Point2::new(min_x, min_y),
+        Point2::new(min_x, max_y),
+        Point2::new(max_x, min_y),
+        Point2::new(max_x, max_y),
     ];
 
-    for &(x, y) in &corners {
-        if !is_point_valid_cached(polygon, tile_set, point_cache, x, y) {
+    for &corner in &corners {
+        if !is_point_valid_cached(polygon, tile_set, point_cache, corner) {
             return false;
         }
     }
 
     // Sample boundary and interior points more densely
     let sample_points = generate_sample_points(min_x, max_x, min_y, max_y);
-    for (x, y) in sample_points {
-        if !is_point_valid_cached(polygon, tile_set, point_cache, x, y) {
+    for point in sample_points {
+        if !is_point_valid_cached(polygon, tile_set, point_cache, point) {
             return false;
         }
     }
@@ -152,20 +176,20 @@ fn is_rectangle_valid(
 
 /// Generate sample points within a rectangle to validate
 /// Sample boundary points densely enough to catch polygon boundaries
-fn generate_sample_points(min_x: i32, max_x: i32, min_y: i32, max_y: i32) -> Vec<(i32, i32)> {
+fn generate_sample_points(min_x: i64, max_x: i64, min_y: i64, max_y: i64) -> Vec<Point2> {
     let mut points = Vec::new();
     let width = max_x - min_x + 1;
     let height = max_y - min_y + 1;
 
     // For small rectangles, check all boundary points
-    if (width as i64) * (height as i64) <= 10000 {
+    if width * height <= 10000 {
         for x in min_x..=max_x {
-            points.push((x, min_y));
-            points.push((x, max_y));
+            points.push(Point2::new(x, min_y));
+            points.push(Point2::new(x, max_y));
         }
         for y in (min_y + 1)..max_y {
-            points.push((min_x, y));
-            points.push((max_x, y));
+            points.push(Point2::new(min_x, y));
+            points.push(Point2::new(max_x, y));
         }
     } else {
         // For large rectangles, sample densely along edges
@@ -173,33 +197,33 @@ fn generate_sample_points(min_x: i32, max_x: i32, min_y: i32, max_y: i32) -> Vec
         let step_y = (height / 200).max(1);
 
         for x in (min_x..=max_x).step_by(step_x as usize) {
-            points.push((x, min_y));
-            points.push((x, max_y));
+            points.push(Point2::new(x, min_y));
+            points.push(Point2::new(x, max_y));
         }
         for y in (min_y + 1..max_y).step_by(step_y as usize) {
-            points.push((min_x, y));
-            points.push((max_x, y));
+            points.push(Point2::new(min_x, y));
+            points.push(Point2::new(max_x, y));
         }
     }
 
     // Always check interior sample points
     let mid_x = (min_x + max_x) / 2;
     let mid_y = (min_y + max_y) / 2;
-    points.push((mid_x, mid_y));
+    points.push(Point2::new(mid_x, mid_y));
 
     let q1_x = (min_x + mid_x) / 2;
     let q3_x = (mid_x + max_x) / 2;
     let q1_y = (min_y + mid_y) / 2;
     let q3_y = (mid_y + max_y) / 2;
     points.extend_from_slice(&[
-        (q1_x, q1_y),
-        (q1_x, q3_y),
-        (q3_x, q1_y),
-        (q3_x, q3_y),
-        (mid_x, q1_y),
-        (mid_x, q3_y),
-        (q1_x, mid_y),
-        (q3_x, mid_y),
+        Point2::new(q1_x, q1_y),
+        Point2::new(q1_x, q3_y),
+        Point2::new(q3_x, q1_y),
+        Point2::new(q3_x, q3_y),
+        Point2::new(mid_x, q1_y),
+        Point2::new(mid_x, q3_y),
+        Point2::new(q1_x, mid_y),
+        Point2::new(q3_x, mid_y),
     ]);
 
     points
@@ -207,89 +231,330 @@ fn generate_sample_points(min_x: i32, max_x: i32, min_y: i32, max_y: i32) -> Vec
 
 /// Check if a point is red or green (inside/on the polygon) with caching
 fn is_point_valid_cached(
-    polygon: &[(i32, i32)],
-    tile_set: &HashSet<(i32, i32)>,
-    cache: &mut HashMap<(i32, i32), bool>,
-    x: i32,
-    y: i32,
+    polygon: &Polygon,
+    tile_set: &HashSet<Point2>,
+    cache: &mut HashMap<Point2, bool>,
+    point: Point2,
 ) -> bool {
-    // Check cache first
-    if let Some(&result) = cache.get(&(x, y)) {
-        return result;
+    *cache
+        .entry(point)
+        .or_insert_with(|| is_point_valid(polygon, tile_set, point))
+}
+
+/// Check if a point is red or green (inside/on the polygon)
+fn is_point_valid(polygon: &Polygon, tile_set: &HashSet<Point2>, point: Point2) -> bool {
+    // Red tiles are polygon vertices, so this is only an O(1) fast path
+    tile_set.contains(&point) || polygon.contains(point)
+}
+
+//=============================================================================
+// Exact Implementation: Coordinate Compression
+//=============================================================================
+
+/// Solver using coordinate compression and a 2D prefix sum
+///
+/// Exact: a rectangle is accepted only if every tile in it is red or green.
+pub struct Day09Compressed;
+
+impl Day for Day09Compressed {
+    fn part1(&self, input: &str) -> String {
+        let tiles = parse_tiles(input);
+        let max_area = find_largest_rectangle(&tiles);
+        max_area.to_string()
     }
 
-    // Compute result
-    let result = is_point_valid(polygon, tile_set, x, y);
+    fn part2(&self, input: &str) -> String {
+        let tiles = parse_tiles(input);
+        let max_area = find_largest_rectangle_compressed(&tiles);
+        max_area.to_string()
+    }
 
-    // Store in cache
-    cache.insert((x, y), result);
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 
-    result
-}
+    fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        let tiles = parse_tiles(input);
+        let witnesses = [
+            largest_rectangle(&tiles).map(|corners| rectangle_witness(1, corners)),
+            largest_rectangle_compressed(&tiles).map(|corners| rectangle_witness(2, corners)),
+        ];
+        Some(witnesses.into_iter().flatten().collect())
+    }
 
-/// Check if a point is red or green (inside/on the polygon)
-fn is_point_valid(polygon: &[(i32, i32)], tile_set: &HashSet<(i32, i32)>, x: i32, y: i32) -> bool {
-    // Check if point is a red tile (O(1) with HashSet)
-    if tile_set.contains(&(x, y)) {
-        return true;
+    fn draw_svg(&self, input: &str, checked: bool) -> Option<String> {
+        Some(draw_tiles(&parse_tiles(input), checked))
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
     }
+}
 
-    // Check if point is on a polygon edge (green)
-    if is_on_polygon_edge(polygon, x, y) {
-        return true;
+/// Generates a closed rectilinear polygon of about `size` red tiles, rounded
+/// down to a multiple of 4
+///
+/// The polygon is a row of vertical strips, each with its own top above the
+/// middle of the floor and its own bottom below it, so it never crosses
+/// itself. It is then randomly transposed, reversed and started at a random
+/// tile.
+impl InputGenerator for Day09Compressed {
+    fn default_size(&self) -> usize {
+        496
     }
 
-    // Check if point is inside polygon (green)
-    is_inside_polygon(polygon, x, y)
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let strips = (size / 4).max(1);
+        // Widen the floor for inputs too large to fit distinct columns in it
+        let limit = 99_999.max(2 * strips as i64 + 2);
+        let mid = limit / 2;
+
+        let mut xs = HashSet::with_capacity(strips + 1);
+        while xs.len() < strips + 1 {
+            xs.insert(rng.range(0, limit));
+        }
+        let mut xs: Vec<i64> = xs.into_iter().collect();
+        xs.sort_unstable();
+
+        // Neighbouring strips never share a top or bottom, so every vertex
+        // is a corner
+        let mut heights = |low: i64, high: i64| {
+            let mut heights: Vec<i64> = Vec::with_capacity(strips);
+            while heights.len() < strips {
+                let height = rng.range(low, high);
+                if heights.last() != Some(&height) {
+                    heights.push(height);
+                }
+            }
+            heights
+        };
+        let tops = heights(mid + 1, limit);
+        let bottoms = heights(0, mid - 1);
+
+        let mut tiles = Vec::with_capacity(4 * strips);
+        for (i, &top) in tops.iter().enumerate() {
+            tiles.push((xs[i], top));
+            tiles.push((xs[i + 1], top));
+        }
+        for (i, &bottom) in bottoms.iter().enumerate().rev() {
+            tiles.push((xs[i + 1], bottom));
+            tiles.push((xs[i], bottom));
+        }
+
+        if rng.chance(0.5) {
+            tiles.iter_mut().for_each(|(x, y)| std::mem::swap(x, y));
+        }
+        if rng.chance(0.5) {
+            tiles.reverse();
+        }
+        let start = rng.index(tiles.len());
+        tiles.rotate_left(start);
+
+        tiles
+            .iter()
+            .map(|(x, y)| format!("{},{}\n", x, y))
+            .collect()
+    }
 }
 
-/// Check if a point lies on any edge of the polygon
-fn is_on_polygon_edge(polygon: &[(i32, i32)], x: i32, y: i32) -> bool {
-    let n = polygon.len();
-    for i in 0..n {
-        let (x1, y1) = polygon[i];
-        let (x2, y2) = polygon[(i + 1) % n];
+/// Draw the polygon, its red tiles and the winning rectangles of both parts
+///
+/// With `checked`, also draws what the heuristic solvers would look at to
+/// accept the part 2 rectangle: the points `generate_sample_points` samples,
+/// and the `SpatialGrid` cells probed along its edges, in teal where the cell
+/// reads as inside and in red where it doesn't.
+fn draw_tiles(tiles: &[Point2], checked: bool) -> String {
+    let polygon = Polygon::new(tiles.to_vec());
+    let Some((min, max)) = polygon.bounding_box() else {
+        return Svg::new((0, 0), (1, 1), 800).finish();
+    };
+    let mut svg = Svg::new((min.x, min.y), (max.x, max.y), 800);
+    let marker = svg.extent() as f64 / 300.0;
+
+    let outline: Vec<(i64, i64)> = tiles.iter().map(|p| (p.x, p.y)).collect();
+    svg.polygon(&outline, "fill:#cde8c4;stroke:#3b7d2f;stroke-width:1");
+
+    // Rectangles include the tiles at both corners
+    let mut rectangle = |(a, b): (Point2, Point2), style: &str| {
+        let corner = (a.x.min(b.x), a.y.min(b.y));
+        let (width, height) = ((b.x - a.x).abs() + 1, (b.y - a.y).abs() + 1);
+        svg.rect(corner, width, height, style);
+    };
+    if let Some(corners) = largest_rectangle(tiles) {
+        rectangle(
+            corners,
+            "fill:none;stroke:#2f5fd0;stroke-width:2;stroke-dasharray:6 4",
+        );
+    }
+    let best = largest_rectangle_compressed(tiles);
+    if let Some(corners) = best {
+        rectangle(
+            corners,
+            "fill:#f0b429;fill-opacity:0.35;stroke:#b7791f;stroke-width:2",
+        );
+    }
+
+    if let (true, Some((a, b))) = (checked, best) {
+        let (min_x, max_x, min_y, max_y) = (a.x.min(b.x), a.x.max(b.x), a.y.min(b.y), a.y.max(b.y));
+
+        let tile_set: HashSet<Point2> = tiles.iter().copied().collect();
+        let extent = (max.x - min.x).max(max.y - min.y);
+        let grid = SpatialGrid::new(&polygon, &tile_set, (extent / GRID_CELLS).max(1));
+        let mut cells = HashSet::new();
+        for (x, y) in grid_probes(min_x, max_x, min_y, max_y, grid.cell_size) {
+            if let Some((col, row)) = grid.cell(x, y) {
+                if cells.insert((col, row)) {
+                    let style = if grid.grid[row][col] {
+                        "fill:#2c9c9c;fill-opacity:0.4"
+                    } else {
+                        "fill:#d64541;fill-opacity:0.6"
+                    };
+                    let corner = (
+                        grid.min_x + col as i64 * grid.cell_size,
+                        grid.min_y + row as i64 * grid.cell_size,
+                    );
+                    svg.rect(corner, grid.cell_size, grid.cell_size, style);
+                }
+            }
+        }
 
-        if is_point_on_segment(x, y, x1, y1, x2, y2) {
-            return true;
+        for point in generate_sample_points(min_x, max_x, min_y, max_y) {
+            svg.circle((point.x, point.y), marker / 2.0, "fill:#5b2c83");
         }
     }
-    false
+
+    for tile in tiles {
+        svg.circle((tile.x, tile.y), marker, "fill:#d62828");
+    }
+    svg.finish()
+}
+
+/// One axis of the compressed grid
+///
+/// Holds a representative coordinate for every distinct vertex coordinate and
+/// for every non-empty gap between consecutive ones, with an extra column on
+/// each side that is always outside the polygon. No polygon edge passes
+/// through the inside of a gap, so each compressed cell is entirely red/green
+/// or entirely outside.
+struct CompressedAxis {
+    coords: Vec<i64>,
 }
 
-/// Check if point (x, y) lies on line segment from (x1, y1) to (x2, y2)
-fn is_point_on_segment(x: i32, y: i32, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
-    // Check if point is within bounding box
-    if x < x1.min(x2) || x > x1.max(x2) || y < y1.min(y2) || y > y1.max(y2) {
-        return false;
+impl CompressedAxis {
+    fn new(values: impl Iterator<Item = i64>) -> Self {
+        let mut stops: Vec<i64> = values.collect();
+        stops.sort_unstable();
+        stops.dedup();
+
+        let mut coords = Vec::with_capacity(stops.len() * 2 + 2);
+        coords.push(stops[0] - 1);
+        for pair in stops.windows(2) {
+            coords.push(pair[0]);
+            if pair[1] - pair[0] > 1 {
+                coords.push(pair[0] + 1);
+            }
+        }
+        coords.push(stops[stops.len() - 1]);
+        coords.push(stops[stops.len() - 1] + 1);
+        Self { coords }
     }
 
-    // Check if point is collinear using cross product
-    let dx1 = x - x1;
-    let dy1 = y - y1;
-    let dx2 = x2 - x1;
-    let dy2 = y2 - y1;
+    fn len(&self) -> usize {
+        self.coords.len()
+    }
+
+    /// Compressed index of a vertex coordinate
+    fn index(&self, value: i64) -> usize {
+        self.coords
+            .binary_search(&value)
+            .expect("Coordinate is not a polygon vertex")
+    }
+}
 
-    // Cross product should be zero for collinear points
-    dx1 as i64 * dy2 as i64 == dy1 as i64 * dx2 as i64
+/// Find the largest rectangle of red and green tiles, checking each candidate
+/// exactly in O(1)
+///
+/// 1. Compress both axes so the grid is at most (2n + 2)² cells
+/// 2. Draw the polygon edges into the compressed grid
+/// 3. Flood fill the outside from a corner, which is outside by construction
+/// 4. Build a prefix sum of outside cells so any rectangle can be checked for
+///    outside cells with four lookups
+fn find_largest_rectangle_compressed(tiles: &[Point2]) -> i64 {
+    largest_rectangle_compressed(tiles).map_or(0, |(a, b)| rectangle_area(a, b))
 }
 
-/// Check if a point is inside the polygon using ray casting algorithm
-fn is_inside_polygon(polygon: &[(i32, i32)], x: i32, y: i32) -> bool {
-    let mut inside = false;
-    let n = polygon.len();
+/// Opposite corners of the largest red and green rectangle, found as in
+/// [`find_largest_rectangle_compressed`]
+fn largest_rectangle_compressed(tiles: &[Point2]) -> Option<(Point2, Point2)> {
+    if tiles.is_empty() {
+        return None;
+    }
+
+    let xs = CompressedAxis::new(tiles.iter().map(|p| p.x));
+    let ys = CompressedAxis::new(tiles.iter().map(|p| p.y));
+    let compressed: Vec<(usize, usize)> = tiles
+        .iter()
+        .map(|p| (xs.index(p.x), ys.index(p.y)))
+        .collect();
+
+    // Mark the cells the boundary passes through
+    let mut edge = Grid::new(xs.len(), ys.len(), false);
+    for (i, &(x1, y1)) in compressed.iter().enumerate() {
+        let (x2, y2) = compressed[(i + 1) % compressed.len()];
+        for y in y1.min(y2)..=y1.max(y2) {
+            for x in x1.min(x2)..=x1.max(x2) {
+                edge[(x, y)] = true;
+            }
+        }
+    }
+
+    // Everything reachable from the corner without crossing an edge is outside
+    let mut outside = Grid::new(xs.len(), ys.len(), false);
+    outside[0] = true;
+    let mut stack = vec![0];
+    while let Some(idx) = stack.pop() {
+        for next in edge.neighbors4(idx) {
+            if !edge[next] && !outside[next] {
+                outside[next] = true;
+                stack.push(next);
+            }
+        }
+    }
 
-    for i in 0..n {
-        let (x1, y1) = polygon[i];
-        let (x2, y2) = polygon[(i + 1) % n];
+    // prefix[y][x] = number of outside cells in [0, x) × [0, y)
+    let stride = xs.len() + 1;
+    let mut prefix = vec![0u32; stride * (ys.len() + 1)];
+    for y in 0..ys.len() {
+        for x in 0..xs.len() {
+            prefix[(y + 1) * stride + x + 1] =
+                prefix[y * stride + x + 1] + prefix[(y + 1) * stride + x] - prefix[y * stride + x]
+                    + outside[(x, y)] as u32;
+        }
+    }
+    let outside_in = |x1: usize, x2: usize, y1: usize, y2: usize| {
+        prefix[(y2 + 1) * stride + x2 + 1] + prefix[y1 * stride + x1]
+            - prefix[y1 * stride + x2 + 1]
+            - prefix[(y2 + 1) * stride + x1]
+    };
 
-        // Ray casting: count intersections with edges
-        if ((y1 > y) != (y2 > y)) && (x < (x2 - x1) * (y - y1) / (y2 - y1) + x1) {
-            inside = !inside;
+    let mut best = None;
+    let mut max_area = 0i64;
+    for i in 0..tiles.len() {
+        let (cx1, cy1) = compressed[i];
+        for j in (i + 1)..tiles.len() {
+            let area = rectangle_area(tiles[i], tiles[j]);
+            if area <= max_area {
+                continue;
+            }
+            let (cx2, cy2) = compressed[j];
+            if outside_in(cx1.min(cx2), cx1.max(cx2), cy1.min(cy2), cy1.max(cy2)) == 0 {
+                max_area = area;
+                best = Some((tiles[i], tiles[j]));
+            }
         }
     }
 
-    inside
+    best
 }
 
 //=============================================================================
@@ -297,7 +562,6 @@ fn is_inside_polygon(polygon: &[(i32, i32)], x: i32, y: i32) -> bool {
 //=============================================================================
 
 /// Solver using sweep line algorithm
-#[allow(dead_code)]
 pub struct Day09SweepLine;
 
 impl Day for Day09SweepLine {
@@ -312,38 +576,42 @@ impl Day for Day09SweepLine {
         let max_area = find_largest_rectangle_sweep_line(&tiles);
         max_area.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
 /// Find largest rectangle using sweep line approach
 /// Sort tiles and only check promising pairs
-#[allow(dead_code)]
-fn find_largest_rectangle_sweep_line(tiles: &[(i32, i32)]) -> i64 {
+fn find_largest_rectangle_sweep_line(tiles: &[Point2]) -> i64 {
     let mut max_area = 0i64;
 
     // Create HashSet for O(1) lookups
-    let tile_set: HashSet<(i32, i32)> = tiles.iter().copied().collect();
+    let tile_set: HashSet<Point2> = tiles.iter().copied().collect();
+
+    // The polygon needs the tiles in input order; sorting is only for the sweep
+    let polygon = Polygon::new(tiles.to_vec());
 
     // Sort tiles by x-coordinate for sweep line
     let mut sorted_tiles = tiles.to_vec();
-    sorted_tiles.sort_by_key(|&(x, _)| x);
+    sorted_tiles.sort_by_key(|p| p.x);
 
     // For each tile as left edge
     for i in 0..sorted_tiles.len() {
-        let (x1, y1) = sorted_tiles[i];
+        let Point2 { x: x1, y: y1 } = sorted_tiles[i];
 
         // Only check tiles to the right
-        for j in (i + 1)..sorted_tiles.len() {
-            let (x2, y2) = sorted_tiles[j];
-
+        for &Point2 { x: x2, y: y2 } in &sorted_tiles[i + 1..] {
             let min_x = x1;
             let max_x = x2;
             let min_y = y1.min(y2);
             let max_y = y1.max(y2);
 
             // Quick validity check with fewer samples
-            if is_rectangle_valid_fast(&sorted_tiles, &tile_set, min_x, max_x, min_y, max_y) {
-                let width = (max_x - min_x) as i64 + 1;
-                let height = (max_y - min_y) as i64 + 1;
+            if is_rectangle_valid_fast(&polygon, &tile_set, min_x, max_x, min_y, max_y) {
+                let width = max_x - min_x + 1;
+                let height = max_y - min_y + 1;
                 let area = width * height;
                 max_area = max_area.max(area);
             }
@@ -354,14 +622,13 @@ fn find_largest_rectangle_sweep_line(tiles: &[(i32, i32)]) -> i64 {
 }
 
 /// Fast rectangle validation with minimal sampling
-#[allow(dead_code)]
 fn is_rectangle_valid_fast(
-    polygon: &[(i32, i32)],
-    tile_set: &HashSet<(i32, i32)>,
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
+    polygon: &Polygon,
+    tile_set: &HashSet<Point2>,
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
 ) -> bool {
     // Check all four corners
     for &(x, y) in &[
@@ -370,7 +637,7 @@ fn is_rectangle_valid_fast(
         (max_x, min_y),
         (max_x, max_y),
     ] {
-        if !is_point_valid(polygon, tile_set, x, y) {
+        if !is_point_valid(polygon, tile_set, Point2::new(x, y)) {
             return false;
         }
     }
@@ -383,8 +650,8 @@ fn is_rectangle_valid_fast(
     // Check boundary points
     let mut x = min_x;
     while x <= max_x {
-        if !is_point_valid(polygon, tile_set, x, min_y)
-            || !is_point_valid(polygon, tile_set, x, max_y)
+        if !is_point_valid(polygon, tile_set, Point2::new(x, min_y))
+            || !is_point_valid(polygon, tile_set, Point2::new(x, max_y))
         {
             return false;
         }
@@ -393,8 +660,8 @@ fn is_rectangle_valid_fast(
 
     let mut y = min_y + step;
     while y < max_y {
-        if !is_point_valid(polygon, tile_set, min_x, y)
-            || !is_point_valid(polygon, tile_set, max_x, y)
+        if !is_point_valid(polygon, tile_set, Point2::new(min_x, y))
+            || !is_point_valid(polygon, tile_set, Point2::new(max_x, y))
         {
             return false;
         }
@@ -404,7 +671,7 @@ fn is_rectangle_valid_fast(
     // Check center
     let mid_x = (min_x + max_x) / 2;
     let mid_y = (min_y + max_y) / 2;
-    is_point_valid(polygon, tile_set, mid_x, mid_y)
+    is_point_valid(polygon, tile_set, Point2::new(mid_x, mid_y))
 }
 
 //=============================================================================
@@ -412,7 +679,6 @@ fn is_rectangle_valid_fast(
 //=============================================================================
 
 /// Solver using spatial grid pre-computation
-#[allow(dead_code)]
 pub struct Day09SpatialGrid;
 
 impl Day for Day09SpatialGrid {
@@ -427,26 +693,30 @@ impl Day for Day09SpatialGrid {
         let max_area = find_largest_rectangle_spatial_grid(&tiles);
         max_area.to_string()
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
 }
 
+/// Target number of spatial grid cells along the polygon's longer side
+const GRID_CELLS: i64 = 250;
+
 /// Spatial grid for fast point-in-polygon checks
-#[allow(dead_code)]
 struct SpatialGrid {
     grid: Vec<Vec<bool>>,
-    min_x: i32,
-    min_y: i32,
-    cell_size: i32,
+    min_x: i64,
+    min_y: i64,
+    cell_size: i64,
     cols: usize,
     rows: usize,
 }
 
 impl SpatialGrid {
-    #[allow(dead_code, clippy::needless_range_loop)]
-    fn new(polygon: &[(i32, i32)], tile_set: &HashSet<(i32, i32)>, cell_size: i32) -> Self {
-        let min_x = polygon.iter().map(|(x, _)| *x).min().unwrap();
-        let max_x = polygon.iter().map(|(x, _)| *x).max().unwrap();
-        let min_y = polygon.iter().map(|(_, y)| *y).min().unwrap();
-        let max_y = polygon.iter().map(|(_, y)| *y).max().unwrap();
+    #[allow(clippy::needless_range_loop)]
+    fn new(polygon: &Polygon, tile_set: &HashSet<Point2>, cell_size: i64) -> Self {
+        let (Point2 { x: min_x, y: min_y }, Point2 { x: max_x, y: max_y }) =
+            polygon.bounding_box().unwrap();
 
         let cols = ((max_x - min_x) / cell_size + 2) as usize;
         let rows = ((max_y - min_y) / cell_size + 2) as usize;
@@ -456,9 +726,9 @@ impl SpatialGrid {
         // Pre-compute valid cells
         for row in 0..rows {
             for col in 0..cols {
-                let x = min_x + col as i32 * cell_size;
-                let y = min_y + row as i32 * cell_size;
-                grid[row][col] = is_point_valid(polygon, tile_set, x, y);
+                let x = min_x + col as i64 * cell_size;
+                let y = min_y + row as i64 * cell_size;
+                grid[row][col] = is_point_valid(polygon, tile_set, Point2::new(x, y));
             }
         }
 
@@ -472,55 +742,53 @@ impl SpatialGrid {
         }
     }
 
-    #[allow(dead_code)]
-    fn is_valid(&self, x: i32, y: i32) -> bool {
+    fn is_valid(&self, x: i64, y: i64) -> bool {
+        self.cell(x, y)
+            .is_some_and(|(col, row)| self.grid[row][col])
+    }
+
+    /// Column and row of the cell holding `(x, y)`, if it is on the grid
+    fn cell(&self, x: i64, y: i64) -> Option<(usize, usize)> {
         let col = ((x - self.min_x) / self.cell_size).max(0) as usize;
         let row = ((y - self.min_y) / self.cell_size).max(0) as usize;
-
-        if row < self.rows && col < self.cols {
-            self.grid[row][col]
-        } else {
-            false
-        }
+        (row < self.rows && col < self.cols).then_some((col, row))
     }
 }
 
 /// Find largest rectangle using spatial grid
-#[allow(dead_code)]
-fn find_largest_rectangle_spatial_grid(tiles: &[(i32, i32)]) -> i64 {
+fn find_largest_rectangle_spatial_grid(tiles: &[Point2]) -> i64 {
     let mut max_area = 0i64;
 
-    let tile_set: HashSet<(i32, i32)> = tiles.iter().copied().collect();
-
-    // Create spatial grid with reasonable cell size
-    let grid = SpatialGrid::new(tiles, &tile_set, 500);
+    let polygon = Polygon::new(tiles.to_vec());
+    let tile_set: HashSet<Point2> = tiles.iter().copied().collect();
+    let (bbox_min, bbox_max) = polygon.bounding_box().unwrap();
 
-    let bbox_min_x = tiles.iter().map(|(x, _)| *x).min().unwrap();
-    let bbox_max_x = tiles.iter().map(|(x, _)| *x).max().unwrap();
-    let bbox_min_y = tiles.iter().map(|(_, y)| *y).min().unwrap();
-    let bbox_max_y = tiles.iter().map(|(_, y)| *y).max().unwrap();
+    // Scale cells to the polygon so the grid has about GRID_CELLS per side;
+    // a fixed size would put small polygons in a single cell
+    let extent = (bbox_max.x - bbox_min.x).max(bbox_max.y - bbox_min.y);
+    let grid = SpatialGrid::new(&polygon, &tile_set, (extent / GRID_CELLS).max(1));
 
     // Check all pairs of red tiles
     for i in 0..tiles.len() {
         for j in (i + 1)..tiles.len() {
-            let (x1, y1) = tiles[i];
-            let (x2, y2) = tiles[j];
+            let (a, b) = (tiles[i], tiles[j]);
 
-            let min_x = x1.min(x2);
-            let max_x = x1.max(x2);
-            let min_y = y1.min(y2);
-            let max_y = y1.max(y2);
+            let min_x = a.x.min(b.x);
+            let max_x = a.x.max(b.x);
+            let min_y = a.y.min(b.y);
+            let max_y = a.y.max(b.y);
 
             // Early rejection
-            if min_x < bbox_min_x || max_x > bbox_max_x || min_y < bbox_min_y || max_y > bbox_max_y
+            if min_x < bbox_min.x || max_x > bbox_max.x || min_y < bbox_min.y || max_y > bbox_max.y
             {
                 continue;
             }
 
             // Use grid for quick check
-            if is_rectangle_valid_with_grid(tiles, &tile_set, &grid, min_x, max_x, min_y, max_y) {
-                let width = (max_x - min_x) as i64 + 1;
-                let height = (max_y - min_y) as i64 + 1;
+            if is_rectangle_valid_with_grid(&polygon, &tile_set, &grid, min_x, max_x, min_y, max_y)
+            {
+                let width = max_x - min_x + 1;
+                let height = max_y - min_y + 1;
                 let area = width * height;
                 max_area = max_area.max(area);
             }
@@ -531,15 +799,14 @@ fn find_largest_rectangle_spatial_grid(tiles: &[(i32, i32)]) -> i64 {
 }
 
 /// Validate rectangle using spatial grid
-#[allow(dead_code)]
 fn is_rectangle_valid_with_grid(
-    polygon: &[(i32, i32)],
-    tile_set: &HashSet<(i32, i32)>,
+    polygon: &Polygon,
+    tile_set: &HashSet<Point2>,
     grid: &SpatialGrid,
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
 ) -> bool {
     // Check corners precisely
     for &(x, y) in &[
@@ -548,30 +815,50 @@ fn is_rectangle_valid_with_grid(
         (max_x, min_y),
         (max_x, max_y),
     ] {
-        if !is_point_valid(polygon, tile_set, x, y) {
+        if !is_point_valid(polygon, tile_set, Point2::new(x, y)) {
             return false;
         }
     }
 
     // Use grid for coarse checks
-    let step = grid.cell_size;
-    let mut x = min_x;
-    while x <= max_x {
-        if !grid.is_valid(x, min_y) || !grid.is_valid(x, max_y) {
-            return false;
-        }
-        x += step;
-    }
+    grid_probes(min_x, max_x, min_y, max_y, grid.cell_size).all(|(x, y)| grid.is_valid(x, y))
+}
 
-    let mut y = min_y + step;
-    while y < max_y {
-        if !grid.is_valid(min_x, y) || !grid.is_valid(max_x, y) {
-            return false;
+/// Points along a rectangle's edges looked up in the spatial grid, one cell
+/// apart: the top and bottom edges in pairs, then the sides
+fn grid_probes(
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+    step: i64,
+) -> impl Iterator<Item = (i64, i64)> {
+    let across = (min_x..=max_x)
+        .step_by(step as usize)
+        .flat_map(move |x| [(x, min_y), (x, max_y)]);
+    let down = (min_y + step..max_y)
+        .step_by(step as usize)
+        .flat_map(move |y| [(min_x, y), (max_x, y)]);
+    across.chain(down)
+}
+
+/// Check that every non-blank line is a red tile position `X,Y`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
-        y += step;
+        let mut cursor = Cursor::new(idx + 1, line);
+        cursor.skip_spaces();
+        cursor.number_at_most(i32::MAX as u64)?;
+        cursor.skip_spaces();
+        cursor.expect(',')?;
+        cursor.skip_spaces();
+        cursor.number_at_most(i32::MAX as u64)?;
+        cursor.skip_spaces();
+        cursor.finish()?;
     }
-
-    true
+    Ok(())
 }
 
 #[cfg(test)]
@@ -601,19 +888,150 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // WIP: Returns 8 instead of 24 - validation logic needs fixing
+    fn test_explain() {
+        let witnesses = Day09.explain(EXAMPLE, &SolveContext::default()).unwrap();
+        assert_eq!(witnesses.len(), 2);
+        assert_eq!(witnesses[0].fact("corners"), Some("11,1 2,5"));
+        assert_eq!(witnesses[0].fact("area"), Some("50"));
+        assert_eq!(witnesses[1].part, 2);
+        assert_eq!(witnesses[1].fact("corners"), Some("9,5 2,3"));
+        assert_eq!(witnesses[1].fact("area"), Some("24"));
+    }
+
+    #[test]
+    fn test_draw_svg() {
+        let svg = Day09.draw_svg(EXAMPLE, false).unwrap();
+        assert!(svg.contains("points=\"7,1 11,1 11,7 9,7 9,5 2,5 2,3 7,3\""));
+        assert_eq!(svg.matches("<circle").count(), 8);
+        // Part 1 from 2,1 to 11,5 and part 2 from 2,3 to 9,5
+        assert!(svg.contains("<rect x=\"2\" y=\"1\" width=\"10\" height=\"5\""));
+        assert!(svg.contains("<rect x=\"2\" y=\"3\" width=\"8\" height=\"3\""));
+
+        // The example is small enough for one-tile cells and checking every
+        // edge tile, so all 24 probes land in distinct, valid cells
+        let svg = Day09.draw_svg(EXAMPLE, true).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 2 + 18);
+        assert_eq!(svg.matches("fill:#d64541").count(), 0);
+        assert_eq!(svg.matches("<circle").count(), 8 + 18 + 9);
+    }
+
+    #[test]
+    fn test_part2_example_sampling() {
+        let day = Day09Sampling;
+        assert_eq!(day.part2(EXAMPLE), "24");
+    }
+
+    /// A wide rectangle with a one-tile-wide notch cut into its top edge
+    const NOTCHED: &str = "\
+0,0
+100000,0
+100000,1000
+50003,1000
+50003,10
+50001,10
+50001,1000
+0,1000";
+
+    #[test]
+    fn test_part2_narrow_notch() {
+        // Largest valid rectangle stops just left of the notch
+        let day = Day09Compressed;
+        assert_eq!(day.part2(NOTCHED), (50002 * 1001).to_string());
+    }
+
+    #[test]
+    fn test_heuristics_miss_narrow_notch() {
+        // Sampling variants step over the notch and accept the bounding box
+        let bounding_box = (100001 * 1001).to_string();
+        assert_eq!(Day09Sampling.part2(NOTCHED), bounding_box);
+        assert_eq!(Day09SweepLine.part2(NOTCHED), bounding_box);
+        assert_eq!(Day09SpatialGrid.part2(NOTCHED), bounding_box);
+    }
+
+    #[test]
+    fn test_compressed_matches_polygon_containment() {
+        for input in [EXAMPLE, NOTCHED] {
+            let tiles = parse_tiles(input);
+            let polygon = Polygon::new(tiles.clone());
+            let mut expected = 0;
+            for (i, &a) in tiles.iter().enumerate() {
+                for &b in &tiles[i + 1..] {
+                    if polygon.contains_rect(a, b) {
+                        expected = expected.max(rectangle_area(a, b));
+                    }
+                }
+            }
+            assert_eq!(find_largest_rectangle_compressed(&tiles), expected);
+        }
+    }
+
+    #[test]
+    fn test_generator() {
+        let input = Day09.generate(40, &mut Rng::new(6));
+        assert!(validate_input(&input).is_ok());
+        let tiles = parse_tiles(&input);
+        assert_eq!(tiles.len(), 40);
+        for (i, a) in tiles.iter().enumerate() {
+            let b = tiles[(i + 1) % tiles.len()];
+            assert!(a != &b && (a.x == b.x || a.y == b.y), "{:?} {:?}", a, b);
+        }
+
+        let polygon = Polygon::new(tiles.clone());
+        let mut expected = 0;
+        for (i, &a) in tiles.iter().enumerate() {
+            for &b in &tiles[i + 1..] {
+                if polygon.contains_rect(a, b) {
+                    expected = expected.max(rectangle_area(a, b));
+                }
+            }
+        }
+        assert_eq!(Day09.part2(&input), expected.to_string());
+    }
+
+    #[test]
     fn test_part2_example_sweep_line() {
         let day = Day09SweepLine;
         assert_eq!(day.part2(EXAMPLE), "24");
     }
 
     #[test]
-    #[ignore] // WIP: Returns 0 instead of 24 - validation logic needs fixing
     fn test_part2_example_spatial_grid() {
         let day = Day09SpatialGrid;
         assert_eq!(day.part2(EXAMPLE), "24");
     }
 }
 
-// Define benchmarks using the common macro
-crate::define_day_benches!(Day09);
+#[cfg(all(test, not(debug_assertions)))]
+mod benches {
+    extern crate test;
+    use super::*;
+    use test::Bencher;
+
+    const INPUT: &str = include_str!("input/input.txt");
+
+    #[bench]
+    fn bench_part1(b: &mut Bencher) {
+        b.iter(|| Day09.part1(INPUT));
+    }
+
+    // Part 2 benchmarks, one per implementation
+    #[bench]
+    fn bench_part2_compressed(b: &mut Bencher) {
+        b.iter(|| Day09Compressed.part2(INPUT));
+    }
+
+    #[bench]
+    fn bench_part2_sampling(b: &mut Bencher) {
+        b.iter(|| Day09Sampling.part2(INPUT));
+    }
+
+    #[bench]
+    fn bench_part2_sweep_line(b: &mut Bencher) {
+        b.iter(|| Day09SweepLine.part2(INPUT));
+    }
+
+    #[bench]
+    fn bench_part2_spatial_grid(b: &mut Bencher) {
+        b.iter(|| Day09SpatialGrid.part2(INPUT));
+    }
+}
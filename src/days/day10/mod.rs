@@ -9,7 +9,15 @@
 //! ## Algorithm
 //!
 //! TODO: Describe algorithm approach
+//!
+//! ### Alternative Implementations
+//!
+//! - `Day10Gf2`: Solves the lights as a linear system over GF(2) and only
+//!   enumerates the null space, with no limit on the number of lights
+//! - `Day10Exact`: Solves the joltage counters with Gaussian elimination over
+//!   the rationals and a bounded search of the free buttons, without `good_lp`.
+//!   `Day10` falls back to it when the `ilp` feature is disabled
 
 mod solution;
 
-pub use solution::Day10;
+pub use solution::{Day10, Day10Exact, Day10Gf2};
@@ -6,41 +6,164 @@
 //!
 //! ## Part 2: Joltage Counter Problem
 //! Configure numeric counters by incrementing them with buttons.
-//! Uses Integer Linear Programming to directly compute optimal solution.
+//! Uses Integer Linear Programming to directly compute optimal solution, or
+//! an exact in-crate integer search when the `ilp` feature is disabled.
 //!
 //! Part 1 uses BFS, Part 2 uses ILP to guarantee minimum button presses.
+//! [`Day10Gf2`] instead solves Part 1 by Gaussian elimination over GF(2), and
+//! [`Day10Exact`] solves Part 2 without an LP solver.
 //!
 //! ## Complexity
 //! - Part 1: O(2^n * b) where n = lights, b = buttons
+//!   (GF(2): O(n * b^2 / 64 + 2^f * b / 64) where f = free buttons)
 //! - Part 2: O(b^3) using Simplex algorithm where b = buttons (typically 8-12)
 
-use crate::runner::Day;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::runner::{Day, SolveContext, SolveError, Witness};
+#[cfg(feature = "ilp")]
 use good_lp::*;
 use std::collections::{HashSet, VecDeque};
+use std::fmt;
 
 /// Solver for Day 10
 pub struct Day10;
 
 impl Day for Day10 {
     fn part1(&self, input: &str) -> String {
-        let machines = parse_machines(input);
-        let total: usize = machines
-            .iter()
-            .map(|m| min_presses(m.target, &m.buttons))
-            .sum();
-        total.to_string()
+        unwrap_answer(solve_part1(input))
     }
 
     fn part2(&self, input: &str) -> String {
-        let machines = parse_machines_part2(input);
-        let total: usize = machines
-            .iter()
-            .map(|m| min_presses_ilp(&m.targets, &m.buttons))
-            .sum();
-        total.to_string()
+        unwrap_answer(solve_part2(input, min_presses_joltage))
+    }
+
+    fn try_part1(&self, input: &str, _ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(solve_part1(input)?)
+    }
+
+    fn try_part2(&self, input: &str, _ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(solve_part2(input, min_presses_joltage)?)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input, MAX_MASK_LIGHTS)
+    }
+
+    fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        explain_presses(input)
+    }
+}
+
+/// Sum the minimum presses of every machine's lights, by BFS
+fn solve_part1(input: &str) -> Result<String, Unsolvable> {
+    let total = parse_machines(input)
+        .iter()
+        .enumerate()
+        .map(|(idx, m)| {
+            min_presses(m.target, &m.buttons).ok_or(Unsolvable::new(idx, Reason::Lights))
+        })
+        .sum::<Result<usize, _>>()?;
+    Ok(total.to_string())
+}
+
+/// The answer for callers outside the runner, which can't report an
+/// [`Unsolvable`] machine other than by panicking
+fn unwrap_answer(answer: Result<String, Unsolvable>) -> String {
+    answer.unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Presses of each button behind both parts, machine by machine
+///
+/// Part 1 presses come from the GF(2) elimination, which handles any number
+/// of buttons. Part 2 presses come from the exact search whichever solver computed the
+/// answer, since a cheapest solution need not be unique. Returns `None` if a
+/// machine has no solution.
+fn explain_presses(input: &str) -> Option<Vec<Witness>> {
+    let describe = |part, idx: usize, presses: Vec<usize>| {
+        let total: usize = presses.iter().sum();
+        let presses: Vec<String> = presses.iter().map(usize::to_string).collect();
+        Witness::new(part, format!("machine {}", idx + 1))
+            .with("presses", presses.join(","))
+            .with("total", total)
+    };
+
+    let lights = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_wide_machine_line)
+        .enumerate()
+        .map(|(idx, m)| {
+            let presses = gf2_presses(&m).ok()?;
+            let presses = (0..m.buttons.len()).map(|i| presses.get(i) as usize);
+            Some(describe(1, idx, presses.collect()))
+        });
+    let joltage = parse_machines_part2(input)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, m)| {
+            let presses = button_presses_exact(&m.targets, &m.buttons)?;
+            Some(describe(2, idx, presses))
+        });
+    lights.chain(joltage).collect()
+}
+
+/// Most lights a `u32` bitmask machine can hold
+const MAX_MASK_LIGHTS: usize = 32;
+
+/// A machine the solvers can't answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Unsolvable {
+    /// 1-based position of the machine in the input
+    machine: usize,
+    reason: Reason,
+}
+
+/// Why a machine can't be answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reason {
+    /// No presses produce the light pattern (Part 1)
+    Lights,
+    /// No presses reach the joltage requirements (Part 2)
+    Joltage,
+    /// Too many free buttons for [`Day10Gf2`] to enumerate
+    FreeButtons,
+}
+
+impl Unsolvable {
+    /// The machine at 0-based `idx` of the input
+    fn new(idx: usize, reason: Reason) -> Self {
+        Self {
+            machine: idx + 1,
+            reason,
+        }
     }
 }
 
+impl fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let machine = self.machine;
+        match self.reason {
+            Reason::Lights => write!(
+                f,
+                "Machine {} cannot reach its light pattern with any button presses",
+                machine
+            ),
+            Reason::Joltage => write!(
+                f,
+                "Machine {} cannot reach its joltage requirements with any button presses",
+                machine
+            ),
+            Reason::FreeButtons => write!(
+                f,
+                "Machine {} has more than {} free buttons to search",
+                machine, MAX_FREE_BUTTONS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Unsolvable {}
+
 /// Represents a machine with its target configuration and buttons (Part 1)
 struct Machine {
     target: u32,       // Target light pattern as bitmask
@@ -114,11 +237,12 @@ fn parse_machine_line(line: &str) -> Machine {
 /// Find minimum button presses using BFS
 ///
 /// BFS guarantees we find the shortest path (minimum presses) from
-/// the start state (all lights off) to the target state.
-fn min_presses(target: u32, buttons: &[u32]) -> usize {
+/// the start state (all lights off) to the target state. Returns `None` if
+/// the target can't be reached.
+fn min_presses(target: u32, buttons: &[u32]) -> Option<usize> {
     // Edge case: already at target
     if target == 0 {
-        return 0;
+        return Some(0);
     }
 
     let mut queue = VecDeque::new();
@@ -134,7 +258,7 @@ fn min_presses(target: u32, buttons: &[u32]) -> usize {
             let next_state = state ^ button; // XOR toggles the lights
 
             if next_state == target {
-                return presses + 1;
+                return Some(presses + 1);
             }
 
             // Only visit each state once
@@ -144,13 +268,273 @@ fn min_presses(target: u32, buttons: &[u32]) -> usize {
         }
     }
 
-    unreachable!("No solution found for target: {}", target)
+    None
+}
+
+//=============================================================================
+// Alternative Implementation: GF(2) Elimination
+//=============================================================================
+
+/// Solver treating part 1 as a linear system over GF(2)
+///
+/// Pressing a button twice cancels out, so every button is pressed at most
+/// once and a machine is the system `A·x = target`, where column `i` of `A` is
+/// the lights button `i` toggles. Gaussian elimination finds one solution and
+/// a basis of the null space, and the minimum is the lightest of the 2^f
+/// solutions for `f` free buttons, instead of a search over 2^lights states.
+/// Lights are held in a bit vector, so machines may have any number of them.
+/// Part 2 is the same as [`Day10`].
+pub struct Day10Gf2;
+
+impl Day for Day10Gf2 {
+    fn part1(&self, input: &str) -> String {
+        unwrap_answer(solve_part1_gf2(input))
+    }
+
+    fn part2(&self, input: &str) -> String {
+        Day10.part2(input)
+    }
+
+    fn try_part1(&self, input: &str, _ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(solve_part1_gf2(input)?)
+    }
+
+    fn try_part2(&self, input: &str, ctx: &SolveContext) -> Result<String, SolveError> {
+        Day10.try_part2(input, ctx)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input, usize::MAX)
+    }
+}
+
+/// Sum the minimum presses of every machine's lights, by elimination
+fn solve_part1_gf2(input: &str) -> Result<String, Unsolvable> {
+    let total = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_wide_machine_line)
+        .enumerate()
+        .map(|(idx, m)| min_presses_gf2(&m).map_err(|reason| Unsolvable::new(idx, reason)))
+        .sum::<Result<usize, _>>()?;
+    Ok(total.to_string())
+}
+
+/// A fixed-width set of bits packed into 64-bit words
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BitVec {
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    /// A bitset of `len` bits, all clear
+    fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, bit: usize) -> bool {
+        self.words[bit / 64] >> (bit % 64) & 1 == 1
+    }
+
+    fn set(&mut self, bit: usize) {
+        self.words[bit / 64] |= 1 << (bit % 64);
+    }
+
+    fn xor_assign(&mut self, other: &Self) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other;
+        }
+    }
+
+    fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// A Part 1 machine with any number of lights
+struct WideMachine {
+    lights: usize,        // Number of indicator lights
+    target: BitVec,       // Lights that must end up on
+    buttons: Vec<BitVec>, // Lights each button toggles
+}
+
+/// Parse a machine line into bitsets sized to its light pattern
+fn parse_wide_machine_line(line: &str) -> WideMachine {
+    let pattern_start = line.find('[').unwrap() + 1;
+    let pattern_end = line.find(']').unwrap();
+    let pattern = &line[pattern_start..pattern_end];
+    let lights = pattern.len();
+
+    let mut target = BitVec::zeros(lights);
+    for (i, ch) in pattern.chars().enumerate() {
+        if ch == '#' {
+            target.set(i);
+        }
+    }
+
+    // Buttons are the `(...)` groups between the pattern and the joltages
+    let buttons_end = line.find('{').unwrap_or(line.len());
+    let buttons = line[pattern_end + 1..buttons_end]
+        .split_whitespace()
+        .map(|group| {
+            let mut button = BitVec::zeros(lights);
+            group
+                .trim_matches(['(', ')'])
+                .split(',')
+                .filter_map(|s| s.trim().parse::<usize>().ok())
+                .for_each(|light| button.set(light));
+            button
+        })
+        .collect();
+
+    WideMachine {
+        lights,
+        target,
+        buttons,
+    }
+}
+
+/// Find minimum button presses by elimination over GF(2)
+///
+/// Counts the presses of [`gf2_presses`].
+fn min_presses_gf2(machine: &WideMachine) -> Result<usize, Reason> {
+    Ok(gf2_presses(machine)?.count_ones())
+}
+
+/// Buttons pressed in a cheapest way to light the target, found by
+/// elimination over GF(2)
+///
+/// Walks the null space from [`gf2_solutions`] in Gray code order, so each
+/// candidate differs from the previous one by a single basis vector. Fails
+/// if no set of presses produces the target, or if the machine has more than
+/// [`MAX_FREE_BUTTONS`] free buttons to enumerate.
+fn gf2_presses(machine: &WideMachine) -> Result<BitVec, Reason> {
+    let (mut presses, basis) = gf2_solutions(machine).ok_or(Reason::Lights)?;
+    if basis.len() > MAX_FREE_BUTTONS {
+        return Err(Reason::FreeButtons);
+    }
+    let combinations = 1u64 << basis.len();
+    let mut best = presses.clone();
+    for step in 1..combinations {
+        presses.xor_assign(&basis[step.trailing_zeros() as usize]);
+        if presses.count_ones() < best.count_ones() {
+            best = presses.clone();
+        }
+    }
+    Ok(best)
+}
+
+/// Most free buttons whose combinations [`gf2_presses`] enumerates
+///
+/// 2^20 candidates take milliseconds; every extra button doubles that.
+const MAX_FREE_BUTTONS: usize = 20;
+
+/// One set of presses lighting the target, and a basis of the presses that
+/// leave every light unchanged
+///
+/// Reduces the augmented matrix (one row per light, one column per button
+/// plus the target) to reduced row echelon form. Returns `None` if a row
+/// reduces to `0 = 1`, meaning no set of presses produces the target. The
+/// basis has one vector per free button.
+fn gf2_solutions(machine: &WideMachine) -> Option<(BitVec, Vec<BitVec>)> {
+    let buttons = machine.buttons.len();
+    let rhs = buttons;
+
+    let mut rows: Vec<BitVec> = (0..machine.lights)
+        .map(|light| {
+            let mut row = BitVec::zeros(buttons + 1);
+            for (i, button) in machine.buttons.iter().enumerate() {
+                if button.get(light) {
+                    row.set(i);
+                }
+            }
+            if machine.target.get(light) {
+                row.set(rhs);
+            }
+            row
+        })
+        .collect();
+
+    // Reduce to RREF, remembering which column each pivot row solves for
+    let mut pivot_cols = Vec::new();
+    for col in 0..buttons {
+        let rank = pivot_cols.len();
+        let Some(found) = (rank..rows.len()).find(|&r| rows[r].get(col)) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let pivot = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && row.get(col) {
+                row.xor_assign(&pivot);
+            }
+        }
+        pivot_cols.push(col);
+    }
+
+    // Rows below the rank have no buttons left, so they must not need any lights
+    let rank = pivot_cols.len();
+    if rows[rank..].iter().any(|row| row.get(rhs)) {
+        return None;
+    }
+
+    // One solution with every free button unpressed
+    let mut presses = BitVec::zeros(buttons);
+    for (row, &col) in rows.iter().zip(&pivot_cols) {
+        if row.get(rhs) {
+            presses.set(col);
+        }
+    }
+
+    // Pressing a free button forces the pivot buttons that share its lights
+    let mut is_pivot = vec![false; buttons];
+    for &col in &pivot_cols {
+        is_pivot[col] = true;
+    }
+    let basis: Vec<BitVec> = (0..buttons)
+        .filter(|&free| !is_pivot[free])
+        .map(|free| {
+            let mut vector = BitVec::zeros(buttons);
+            vector.set(free);
+            for (row, &col) in rows.iter().zip(&pivot_cols) {
+                if row.get(free) {
+                    vector.set(col);
+                }
+            }
+            vector
+        })
+        .collect();
+    Some((presses, basis))
 }
 
 //=============================================================================
 // Part 2: Joltage Counter Problem
 //=============================================================================
 
+#[cfg(not(feature = "ilp"))]
+use self::min_presses_exact as min_presses_joltage;
+/// Part 2 solver used by [`Day10`]
+#[cfg(feature = "ilp")]
+use self::min_presses_ilp as min_presses_joltage;
+
+/// Sum the minimum presses of every machine's joltages
+fn solve_part2(
+    input: &str,
+    solver: fn(&[u32], &[Vec<usize>]) -> Option<usize>,
+) -> Result<String, Unsolvable> {
+    let total = parse_machines_part2(input)
+        .iter()
+        .enumerate()
+        .map(|(idx, m)| solver(&m.targets, &m.buttons).ok_or(Unsolvable::new(idx, Reason::Joltage)))
+        .sum::<Result<usize, _>>()?;
+    Ok(total.to_string())
+}
+
 /// Parse all machines for Part 2 (joltage counters)
 fn parse_machines_part2(input: &str) -> Vec<MachinePart2> {
     input
@@ -211,11 +595,12 @@ fn parse_machine_line_part2(line: &str) -> MachinePart2 {
 /// - Constraints: for each counter j, sum(x[i] where button i increments j) = target[j]
 ///
 /// This directly computes the optimal solution using linear algebra instead of
-/// exploring the state space.
-fn min_presses_ilp(targets: &[u32], buttons: &[Vec<usize>]) -> usize {
+/// exploring the state space. Returns `None` if the solver finds no solution.
+#[cfg(feature = "ilp")]
+fn min_presses_ilp(targets: &[u32], buttons: &[Vec<usize>]) -> Option<usize> {
     // Edge case: all targets already at zero
     if targets.iter().all(|&t| t == 0) {
-        return 0;
+        return Some(0);
     }
 
     let num_buttons = buttons.len();
@@ -249,13 +634,423 @@ fn min_presses_ilp(targets: &[u32], buttons: &[Vec<usize>]) -> usize {
         .using(microlp)
         .with_all(constraints)
         .solve()
-        .unwrap();
+        .ok()?;
 
     // Sum up the button presses (integer constraint guarantees integer solutions)
-    button_vars
+    Some(
+        button_vars
+            .iter()
+            .map(|&v| solution.value(v).round() as usize)
+            .sum(),
+    )
+}
+
+//=============================================================================
+// Alternative Implementation: Exact Integer Search
+//=============================================================================
+
+/// Solver with an exact integer search for Part 2 instead of an LP solver
+///
+/// Part 1 is the same as [`Day10`].
+pub struct Day10Exact;
+
+impl Day for Day10Exact {
+    fn part1(&self, input: &str) -> String {
+        Day10.part1(input)
+    }
+
+    fn part2(&self, input: &str) -> String {
+        unwrap_answer(solve_part2(input, min_presses_exact))
+    }
+
+    fn try_part1(&self, input: &str, ctx: &SolveContext) -> Result<String, SolveError> {
+        Day10.try_part1(input, ctx)
+    }
+
+    fn try_part2(&self, input: &str, _ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(solve_part2(input, min_presses_exact)?)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input, MAX_MASK_LIGHTS)
+    }
+
+    fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        explain_presses(input)
+    }
+}
+
+/// Find minimum button presses with non-negative integers only
+///
+/// Gaussian elimination over the rationals, kept fraction-free by scaling rows
+/// to integers, writes each pivot button as `(rhs - Σ coef·free) / scale` in
+/// terms of the free buttons. The free buttons are then assigned by branch and
+/// bound (see [`ExactSearch`]), keeping assignments where every pivot button
+/// comes out as a non-negative integer. A button is never pressed more often
+/// than the smallest target among the counters it increments, so the search
+/// is finite.
+///
+/// Returns `None` if no combination of presses reaches the targets exactly.
+fn min_presses_exact(targets: &[u32], buttons: &[Vec<usize>]) -> Option<usize> {
+    Some(button_presses_exact(targets, buttons)?.iter().sum())
+}
+
+/// Presses of each button in a cheapest solution, found as in
+/// [`min_presses_exact`]
+fn button_presses_exact(targets: &[u32], buttons: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let n = buttons.len();
+    let rhs = n;
+
+    // Augmented matrix: one row per counter, one column per button plus target
+    let mut rows: Vec<Vec<i64>> = targets
         .iter()
-        .map(|&v| solution.value(v).round() as usize)
-        .sum()
+        .enumerate()
+        .map(|(counter, &target)| {
+            let mut row: Vec<i64> = buttons
+                .iter()
+                .map(|button| button.contains(&counter) as i64)
+                .collect();
+            row.push(target as i64);
+            row
+        })
+        .collect();
+
+    // Fraction-free reduction to RREF (up to row scaling)
+    let mut pivot_cols = Vec::new();
+    for col in 0..n {
+        let rank = pivot_cols.len();
+        let Some(found) = (rank..rows.len()).find(|&r| rows[r][col] != 0) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let pivot = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == rank || factor == 0 {
+                continue;
+            }
+            for (value, &p) in row.iter_mut().zip(&pivot) {
+                *value = *value * pivot[col] - p * factor;
+            }
+            reduce_row(row);
+        }
+        pivot_cols.push(col);
+    }
+
+    // A row with no buttons left must not need any presses
+    let rank = pivot_cols.len();
+    if rows[rank..].iter().any(|row| row[rhs] != 0) {
+        return None;
+    }
+    rows.truncate(rank);
+    for (row, &col) in rows.iter_mut().zip(&pivot_cols) {
+        if row[col] < 0 {
+            row.iter_mut().for_each(|value| *value = -*value);
+        }
+    }
+
+    let mut is_pivot = vec![false; n];
+    for &col in &pivot_cols {
+        is_pivot[col] = true;
+    }
+
+    // Most presses each button can take, trying tightly bounded buttons first
+    let limit = |button: usize| -> i64 {
+        buttons[button]
+            .iter()
+            .map(|&c| targets[c] as i64)
+            .min()
+            .unwrap_or(0)
+    };
+    let mut free: Vec<usize> = (0..n).filter(|&col| !is_pivot[col]).collect();
+    free.sort_by_key(|&col| limit(col));
+
+    ExactSearch::new(&rows, &pivot_cols, &free, limit).solve()
+}
+
+/// Divide a row by the gcd of its entries
+fn reduce_row(row: &mut [i64]) {
+    let divisor = row.iter().fold(0, |acc, &value| gcd(acc, value.abs()));
+    if divisor > 1 {
+        row.iter_mut().for_each(|value| *value /= divisor);
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Branch and bound over the free buttons of a reduced system
+///
+/// Pivot row `r` reads `scale[r]·pivot + Σ coef[r][k]·free[k] = value[r]`.
+/// Before choosing presses for a free button, each row bounds it: the row's
+/// value must stay reachable by the unassigned free buttons, whose coefficients
+/// can only raise it up to their limits. The total presses are linear in the
+/// free buttons, so once a button's range is known it is walked from its
+/// cheapest end and abandoned as soon as even the best case for the remaining
+/// buttons can't beat the best total found. Totals are scaled by `lcm(scale)`
+/// to stay in integers.
+struct ExactSearch {
+    pivot_cols: Vec<usize>,
+    free_cols: Vec<usize>,
+    /// `coef[r][k]`: coefficient of the `k`th free button in pivot row `r`
+    coef: Vec<Vec<i64>>,
+    scale: Vec<i64>,
+    limit: Vec<i64>,
+    /// Change in the scaled total per press of each free button
+    weight: Vec<i64>,
+    /// `slack_tail[k][r]`: most the free buttons from `k` on can raise row `r`
+    slack_tail: Vec<Vec<i64>>,
+    /// `weight_tail[k]`: least the free buttons from `k` on can add to the total
+    weight_tail: Vec<i64>,
+    value: Vec<i64>,
+    /// Presses chosen so far for each free button
+    presses: Vec<i64>,
+    total: i64,
+    best: Option<i64>,
+    /// Presses of every button in the best solution
+    best_presses: Vec<usize>,
+}
+
+impl ExactSearch {
+    fn new(
+        rows: &[Vec<i64>],
+        pivot_cols: &[usize],
+        free: &[usize],
+        limit: impl Fn(usize) -> i64,
+    ) -> Self {
+        let rhs = rows.first().map_or(0, |row| row.len() - 1);
+        let scale: Vec<i64> = rows
+            .iter()
+            .zip(pivot_cols)
+            .map(|(row, &col)| row[col])
+            .collect();
+        let lcm = scale.iter().fold(1, |acc, &s| acc / gcd(acc, s) * s);
+        let coef: Vec<Vec<i64>> = rows
+            .iter()
+            .map(|row| free.iter().map(|&col| row[col]).collect())
+            .collect();
+        let limit: Vec<i64> = free.iter().map(|&col| limit(col)).collect();
+
+        // Each press adds itself and takes coef/scale presses off every pivot
+        let weight: Vec<i64> = (0..free.len())
+            .map(|k| {
+                lcm - (0..rows.len())
+                    .map(|r| coef[r][k] * (lcm / scale[r]))
+                    .sum::<i64>()
+            })
+            .collect();
+
+        let mut slack_tail = vec![vec![0; rows.len()]; free.len() + 1];
+        let mut weight_tail = vec![0; free.len() + 1];
+        for k in (0..free.len()).rev() {
+            for r in 0..rows.len() {
+                slack_tail[k][r] = slack_tail[k + 1][r] + (-coef[r][k]).max(0) * limit[k];
+            }
+            weight_tail[k] = weight_tail[k + 1] + (weight[k] * limit[k]).min(0);
+        }
+
+        let value: Vec<i64> = rows.iter().map(|row| row[rhs]).collect();
+        let total = (0..rows.len()).map(|r| value[r] * (lcm / scale[r])).sum();
+
+        Self {
+            pivot_cols: pivot_cols.to_vec(),
+            free_cols: free.to_vec(),
+            coef,
+            scale,
+            limit,
+            weight,
+            slack_tail,
+            weight_tail,
+            value,
+            presses: vec![0; free.len()],
+            total,
+            best: None,
+            best_presses: Vec::new(),
+        }
+    }
+
+    /// Presses of each button in a cheapest solution, or `None` if no
+    /// assignment works
+    fn solve(mut self) -> Option<Vec<usize>> {
+        self.search(0);
+        self.best.map(|_| self.best_presses)
+    }
+
+    fn search(&mut self, depth: usize) {
+        if depth == self.limit.len() {
+            let valid = self
+                .value
+                .iter()
+                .zip(&self.scale)
+                .all(|(&value, &scale)| value >= 0 && value % scale == 0);
+            if valid && self.best.is_none_or(|best| self.total < best) {
+                self.best = Some(self.total);
+                self.record_presses();
+            }
+            return;
+        }
+
+        // Presses of this button that keep every pivot row reachable
+        let (mut lo, mut hi) = (0, self.limit[depth]);
+        for r in 0..self.value.len() {
+            let slack = self.value[r] + self.slack_tail[depth + 1][r];
+            let coef = self.coef[r][depth];
+            if coef > 0 {
+                hi = hi.min(slack.div_euclid(coef));
+            } else if slack < 0 {
+                if coef == 0 {
+                    return;
+                }
+                lo = lo.max((-slack + -coef - 1) / -coef);
+            }
+        }
+        if lo > hi {
+            return;
+        }
+
+        let weight = self.weight[depth];
+        let rest = self.weight_tail[depth + 1];
+        for step in 0..=hi - lo {
+            let x = if weight >= 0 { lo + step } else { hi - step };
+            if self
+                .best
+                .is_some_and(|best| self.total + weight * x + rest >= best)
+            {
+                break;
+            }
+            self.presses[depth] = x;
+            self.apply(depth, x);
+            self.search(depth + 1);
+            self.apply(depth, -x);
+        }
+    }
+
+    /// Keep the presses of the current assignment as the best solution
+    fn record_presses(&mut self) {
+        let n = self.pivot_cols.len() + self.free_cols.len();
+        self.best_presses = vec![0; n];
+        for (r, &col) in self.pivot_cols.iter().enumerate() {
+            self.best_presses[col] = (self.value[r] / self.scale[r]) as usize;
+        }
+        for (&col, &presses) in self.free_cols.iter().zip(&self.presses) {
+            self.best_presses[col] = presses as usize;
+        }
+    }
+
+    /// Add `presses` of the free button at `depth` to the running state
+    fn apply(&mut self, depth: usize, presses: i64) {
+        for (value, coef) in self.value.iter_mut().zip(&self.coef) {
+            *value -= coef[depth] * presses;
+        }
+        self.total += self.weight[depth] * presses;
+    }
+}
+
+/// Check that every line is `[lights] (buttons)... {joltages}`
+///
+/// Button indices must refer to existing lights and there must be one joltage
+/// per light. Machines may have at most `max_lights` lights. Elimination
+/// over GF(2), which is cheap, also checks that some presses light each
+/// pattern with at most [`MAX_FREE_BUTTONS`] free buttons to search. Whether
+/// the joltages can be reached takes a full Part 2 solve, so an unsolvable
+/// machine is reported as an [`Unsolvable`] error by the solvers instead.
+fn validate_input(input: &str, max_lights: usize) -> Result<(), Diagnostic> {
+    for (idx, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let mut cursor = Cursor::new(idx + 1, line);
+
+        cursor.expect('[')?;
+        let pattern_start = cursor.pos();
+        let pattern = cursor.take_while(|ch| ch == '.' || ch == '#');
+        if pattern.is_empty() {
+            return Err(cursor.error("`.` or `#`"));
+        }
+        if pattern.len() > max_lights {
+            return Err(cursor.error_since(
+                pattern_start,
+                format!("at most {} lights", max_lights),
+                format!("{} lights", pattern.len()),
+            ));
+        }
+        cursor.expect(']')?;
+        let lights = pattern.len() as u64;
+
+        cursor.expect(' ')?;
+        let buttons_start = cursor.pos();
+        let mut buttons_end;
+        cursor.expect('(')?;
+        loop {
+            loop {
+                let index_start = cursor.pos();
+                let index = cursor.number()?;
+                if index >= lights {
+                    return Err(cursor.error_since(
+                        index_start,
+                        format!("a light index below {}", lights),
+                        format!("{}", index),
+                    ));
+                }
+                if !cursor.eat(',') {
+                    break;
+                }
+            }
+            cursor.expect(')')?;
+            buttons_end = cursor.pos();
+            cursor.expect(' ')?;
+            if !cursor.eat('(') {
+                break;
+            }
+        }
+
+        cursor.expect('{')?;
+        let joltage_start = cursor.pos();
+        let mut joltages = 0;
+        loop {
+            cursor.number_at_most(u32::MAX as u64)?;
+            joltages += 1;
+            if !cursor.eat(',') {
+                break;
+            }
+        }
+        if joltages != lights {
+            return Err(cursor.error_since(
+                joltage_start,
+                format!("{} joltage requirements", lights),
+                format!("{}", joltages),
+            ));
+        }
+        cursor.expect('}')?;
+        cursor.finish()?;
+
+        let span = |start, end, expected: &str, found: String| {
+            Diagnostic::span(idx + 1, line, start, end - start, expected, found)
+        };
+        let machine = parse_wide_machine_line(line);
+        let Some((_, basis)) = gf2_solutions(&machine) else {
+            return Err(span(
+                pattern_start,
+                pattern_start + pattern.len(),
+                "a light pattern the buttons can produce",
+                "an unreachable pattern".to_string(),
+            ));
+        };
+        if basis.len() > MAX_FREE_BUTTONS {
+            return Err(span(
+                buttons_start,
+                buttons_end,
+                &format!("at most {} free buttons", MAX_FREE_BUTTONS),
+                format!("{} free buttons", basis.len()),
+            ));
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -297,7 +1092,7 @@ mod tests {
         let line = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
         let machine = parse_machine_line(line);
         let presses = min_presses(machine.target, &machine.buttons);
-        assert_eq!(presses, 2);
+        assert_eq!(presses, Some(2));
     }
 
     #[test]
@@ -305,7 +1100,7 @@ mod tests {
         let line = "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}";
         let machine = parse_machine_line(line);
         let presses = min_presses(machine.target, &machine.buttons);
-        assert_eq!(presses, 3);
+        assert_eq!(presses, Some(3));
     }
 
     #[test]
@@ -313,7 +1108,7 @@ mod tests {
         let line = "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
         let machine = parse_machine_line(line);
         let presses = min_presses(machine.target, &machine.buttons);
-        assert_eq!(presses, 2);
+        assert_eq!(presses, Some(2));
     }
 
     #[test]
@@ -323,27 +1118,148 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "ilp")]
     fn test_machine_1_part2() {
         let line = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
         let machine = parse_machine_line_part2(line);
-        let presses = min_presses_ilp(&machine.targets, &machine.buttons);
-        assert_eq!(presses, 10);
+        assert_eq!(
+            min_presses_ilp(&machine.targets, &machine.buttons),
+            Some(10)
+        );
     }
 
     #[test]
+    #[cfg(feature = "ilp")]
     fn test_machine_2_part2() {
         let line = "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}";
         let machine = parse_machine_line_part2(line);
-        let presses = min_presses_ilp(&machine.targets, &machine.buttons);
-        assert_eq!(presses, 12);
+        assert_eq!(
+            min_presses_ilp(&machine.targets, &machine.buttons),
+            Some(12)
+        );
     }
 
     #[test]
+    #[cfg(feature = "ilp")]
     fn test_machine_3_part2() {
         let line = "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
         let machine = parse_machine_line_part2(line);
-        let presses = min_presses_ilp(&machine.targets, &machine.buttons);
-        assert_eq!(presses, 11);
+        assert_eq!(
+            min_presses_ilp(&machine.targets, &machine.buttons),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_exact_example() {
+        let day = Day10Exact;
+        assert_eq!(day.part2(EXAMPLE), "33");
+
+        let presses: Vec<Option<usize>> = parse_machines_part2(EXAMPLE)
+            .iter()
+            .map(|m| min_presses_exact(&m.targets, &m.buttons))
+            .collect();
+        assert_eq!(presses, vec![Some(10), Some(12), Some(11)]);
+    }
+
+    #[test]
+    #[cfg(feature = "ilp")]
+    fn test_exact_matches_ilp() {
+        // Pseudo-random machines whose targets come from random presses, so
+        // every one is solvable
+        let mut state = 5u64;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        for _ in 0..200 {
+            let counters = 2 + next(6) as usize;
+            let buttons: Vec<Vec<usize>> = (0..2 + next(6))
+                .map(|_| {
+                    let button: Vec<usize> = (0..counters).filter(|_| next(3) == 0).collect();
+                    if button.is_empty() {
+                        vec![next(counters as u64) as usize]
+                    } else {
+                        button
+                    }
+                })
+                .collect();
+            let mut targets = vec![0u32; counters];
+            for button in &buttons {
+                let presses = next(12) as u32;
+                for &c in button {
+                    targets[c] += presses;
+                }
+            }
+
+            assert_eq!(
+                min_presses_exact(&targets, &buttons),
+                min_presses_ilp(&targets, &buttons),
+                "targets {:?}, buttons {:?}",
+                targets,
+                buttons
+            );
+        }
+    }
+
+    #[test]
+    fn test_exact_unsolvable_machine() {
+        // Counters 0 and 1 always rise together but need different values,
+        // which only a Part 2 solve finds out
+        let input = "[..] (0) {0,0}\n[##] (0,1) {1,2}";
+        let ctx = SolveContext::default();
+        for day in [&Day10 as &dyn Day, &Day10Exact] {
+            assert!(day.validate(input).is_ok());
+            assert_eq!(day.try_part1(input, &ctx).unwrap(), "1");
+            let err = day.try_part2(input, &ctx).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Machine 2 cannot reach its joltage requirements with any button presses"
+            );
+            assert_eq!(day.explain(input, &ctx), None);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let witnesses = Day10.explain(EXAMPLE, &SolveContext::default()).unwrap();
+        assert_eq!(witnesses.len(), 6);
+        assert_eq!(witnesses[0].label, "machine 1");
+        assert_eq!(witnesses[0].fact("presses"), Some("0,1,0,1,0,0"));
+
+        let totals: Vec<_> = witnesses.iter().map(|w| w.fact("total").unwrap()).collect();
+        assert_eq!(totals, vec!["2", "3", "2", "10", "12", "11"]);
+
+        // Every part 2 witness presses the buttons up to the targets exactly
+        for (witness, machine) in witnesses[3..].iter().zip(parse_machines_part2(EXAMPLE)) {
+            let mut counters = vec![0; machine.targets.len()];
+            let presses = witness.fact("presses").unwrap().split(',');
+            for (button, presses) in machine.buttons.iter().zip(presses) {
+                let presses: u32 = presses.parse().unwrap();
+                button.iter().for_each(|&c| counters[c] += presses);
+            }
+            assert_eq!(counters, machine.targets);
+        }
+    }
+
+    #[test]
+    fn test_explain_many_buttons() {
+        // 33 buttons, one per light plus a last one lighting the first two
+        let singles: Vec<String> = (0..32).map(|i| format!("({})", i)).collect();
+        let joltages = format!("1,1{}", ",0".repeat(30));
+        let line = format!(
+            "[##{}] {} (0,1) {{{}}}",
+            ".".repeat(30),
+            singles.join(" "),
+            joltages
+        );
+
+        let witnesses = Day10.explain(&line, &SolveContext::default()).unwrap();
+        let presses = format!("{},1", vec!["0"; 32].join(","));
+        assert_eq!(witnesses[0].fact("presses"), Some(presses.as_str()));
+        assert_eq!(witnesses[0].fact("total"), Some("1"));
     }
 
     #[test]
@@ -353,6 +1269,121 @@ mod tests {
         assert_eq!(machine.targets, vec![3, 5, 4, 7]);
         assert_eq!(machine.buttons, vec![vec![3], vec![1, 3]]);
     }
+
+    #[test]
+    fn test_unsolvable_machine() {
+        let err = Day10
+            .validate("[#.] (0) {1,0}\n[#.] (1) {0,1}")
+            .unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (2, 2, 2));
+        assert_eq!(err.found, "an unreachable pattern");
+    }
+
+    #[test]
+    fn test_gf2_example() {
+        let day = Day10Gf2;
+        assert_eq!(day.part1(EXAMPLE), "7");
+        assert_eq!(day.part2(EXAMPLE), "33");
+    }
+
+    #[test]
+    fn test_gf2_matches_bfs() {
+        // Pseudo-random small machines, including unsolvable ones
+        let mut state = 11u64;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        for _ in 0..300 {
+            let lights = 1 + next(10) as usize;
+            let pattern: String = (0..lights)
+                .map(|_| if next(2) == 1 { '#' } else { '.' })
+                .collect();
+            let buttons: Vec<String> = (0..1 + next(8))
+                .map(|_| {
+                    let mut indices: Vec<String> = (0..lights)
+                        .filter(|_| next(3) == 0)
+                        .map(|i| i.to_string())
+                        .collect();
+                    if indices.is_empty() {
+                        indices.push(next(lights as u64).to_string());
+                    }
+                    format!("({})", indices.join(","))
+                })
+                .collect();
+            let line = format!("[{}] {} {{0}}", pattern, buttons.join(" "));
+
+            let machine = parse_machine_line(&line);
+            assert_eq!(
+                min_presses_gf2(&parse_wide_machine_line(&line)).ok(),
+                min_presses(machine.target, &machine.buttons),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_gf2_wide_machine() {
+        // 40 lights in a chain of neighbouring pairs, lighting both ends
+        let chain: Vec<String> = (0..39).map(|i| format!("({},{})", i, i + 1)).collect();
+        let pattern = format!("#{}#", ".".repeat(38));
+        let joltages = vec!["1"; 40].join(",");
+        let line = format!("[{}] {} {{{}}}", pattern, chain.join(" "), joltages);
+        assert_eq!(min_presses_gf2(&parse_wide_machine_line(&line)), Ok(39));
+
+        // A button joining the ends directly
+        let shortcut = format!("[{}] {} (0,39) {{{}}}", pattern, chain.join(" "), joltages);
+        assert_eq!(Day10Gf2.part1(&shortcut), "1");
+
+        assert!(Day10Gf2.validate(&shortcut).is_ok());
+        let err = Day10.validate(&shortcut).unwrap_err();
+        assert_eq!(err.expected, "at most 32 lights");
+    }
+
+    #[test]
+    fn test_gf2_unsolvable_machine() {
+        // Every button toggles two lights, so an odd number can't end up on
+        let err = Day10Gf2
+            .validate("[#..] (0,1) (1,2) (0,2) {1,1,1}")
+            .unwrap_err();
+        assert_eq!(err.expected, "a light pattern the buttons can produce");
+
+        // 40 copies of one button leave 39 free buttons, too many to
+        // enumerate, whichever solver would run
+        let line = format!("[#] {} {{1}}", vec!["(0)"; 40].join(" "));
+        for day in [&Day10 as &dyn Day, &Day10Gf2, &Day10Exact] {
+            let err = day.validate(&line).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "expected at most 20 free buttons, found 39 free buttons"
+            );
+            assert_eq!((err.column, err.width), (5, 40 * 4 - 1));
+        }
+        assert_eq!(
+            gf2_presses(&parse_wide_machine_line(&line)),
+            Err(Reason::FreeButtons)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_input(EXAMPLE, MAX_MASK_LIGHTS).is_ok());
+
+        // Button refers to a light that does not exist
+        let err = validate_input("[.##.] (3) (1,4) {3,5,4,7}", MAX_MASK_LIGHTS).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 15));
+
+        // Missing pattern
+        let err = validate_input("(3) (1,3) {3,5,4,7}", MAX_MASK_LIGHTS).unwrap_err();
+        assert_eq!(err.to_string(), "expected `[`, found `(`");
+
+        // Joltage count does not match the number of lights
+        let err = validate_input("[.##.] (3) {3,5,4}", MAX_MASK_LIGHTS).unwrap_err();
+        assert_eq!(err.expected, "4 joltage requirements");
+    }
 }
 
 // Define benchmarks using the common macro
@@ -9,7 +9,13 @@
 //! ## Algorithm
 //!
 //! TODO: Describe algorithm approach
+//!
+//! ### Alternative Implementations
+//!
+//! - `Day11Dag`: Counts paths with topological-order dynamic programming in
+//!   `u128`, supports any number of waypoints, and rejects graphs with a cycle
+//!   during validation
 
 mod solution;
 
-pub use solution::Day11;
+pub use solution::{Day11, Day11Dag};
@@ -10,80 +10,279 @@
 //! Uses DFS with memoization and state tracking (bit flags for visited required nodes).
 //! Performance: ~551 µs
 //!
+//! Node names are parameters (`part1_start`, `part2_start`, `end`, `waypoints`)
+//! so other graphs can be run through the normal `Day` interface.
+//!
+//! [`Day11Dag`] counts both parts with dynamic programming over a topological
+//! order instead, in `u128` and with any number of waypoints.
+//!
 //! ## Algorithm Complexity
 //! - Time: O((V+E) × P) where P = number of paths
 //! - Space: O(V) for visited/constraint tracking
 //! - Both parts use DFS with backtracking
 
-use crate::runner::Day;
+use crate::common::dot;
+use crate::common::graph::Graph;
+use crate::common::rng::Rng;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::runner::{Day, InputGenerator, Param, ParamKind, SolveContext};
 use std::collections::{HashMap, HashSet};
 
 /// Solver for Day 11
 pub struct Day11;
 
+/// Tunable parameters for Day 11
+///
+/// [`Day11`] tracks the waypoints a path has visited as the bits of a `u64`,
+/// so it takes at most 63 of them.
+const PARAMS: &[Param] = &[
+    PART1_START,
+    PART2_START,
+    END,
+    Param {
+        kind: ParamKind::List { max: 63 },
+        ..WAYPOINTS
+    },
+];
+
+/// Tunable parameters for [`Day11Dag`], which takes any number of waypoints
+const DAG_PARAMS: &[Param] = &[PART1_START, PART2_START, END, WAYPOINTS];
+
+const PART1_START: Param = Param {
+    name: "part1_start",
+    default: "you",
+    description: "Start node for part 1",
+    kind: ParamKind::Text,
+};
+
+const PART2_START: Param = Param {
+    name: "part2_start",
+    default: "svr",
+    description: "Start node for part 2",
+    kind: ParamKind::Text,
+};
+
+const END: Param = Param {
+    name: "end",
+    default: "out",
+    description: "End node for both parts",
+    kind: ParamKind::Text,
+};
+
+const WAYPOINTS: Param = Param {
+    name: "waypoints",
+    default: "dac,fft",
+    description: "Comma-separated nodes every part 2 path must visit",
+    kind: ParamKind::List { max: usize::MAX },
+};
+
 impl Day for Day11 {
     fn part1(&self, input: &str) -> String {
-        let graph = parse_graph(input);
-        let mut visited = HashSet::with_capacity(graph.len());
-        count_paths_dfs(&graph, "you", "out", &mut visited).to_string()
+        self.part1_with(input, &SolveContext::from_defaults(PARAMS))
     }
 
     fn part2(&self, input: &str) -> String {
+        self.part2_with(input, &SolveContext::from_defaults(PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+        let graph = parse_graph(input);
+        let (start, end) = (ctx.get_str("part1_start"), ctx.get_str("end"));
+        if start == end {
+            return "1".to_string();
+        }
+        let (Some(start), Some(end)) = (graph.id(start), graph.id(end)) else {
+            return "0".to_string();
+        };
+
+        let mut visited = vec![false; graph.len()];
+        count_paths_dfs(&graph, start, end, &mut visited).to_string()
+    }
+
+    fn part2_with(&self, input: &str, ctx: &SolveContext) -> String {
         let graph = parse_graph(input);
+        let (Some(start), Some(end)) = (
+            graph.id(ctx.get_str("part2_start")),
+            graph.id(ctx.get_str("end")),
+        ) else {
+            return "0".to_string();
+        };
+        // A waypoint missing from the graph can never be visited
+        let Some(mut waypoints) = parse_waypoints(ctx.get_str("waypoints"))
+            .into_iter()
+            .map(|name| graph.id(name))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return "0".to_string();
+        };
+        // Each waypoint needs one bit, however often it is listed
+        waypoints.sort_unstable();
+        waypoints.dedup();
+        assert!(waypoints.len() < 64, "At most 63 waypoints are supported");
 
         // Use memoized version for better performance
-        let mut visited = HashSet::with_capacity(graph.len());
+        let mut visited = vec![false; graph.len()];
         let mut memo = HashMap::new();
 
-        count_paths_memoized(&graph, "svr", "out", &mut visited, &mut memo, 0).to_string()
+        count_paths_memoized(&graph, start, end, &waypoints, &mut visited, &mut memo, 0).to_string()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn draw_dot(&self, input: &str, ctx: &SolveContext) -> Option<String> {
+        Some(draw_graph(input, ctx))
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
+}
+
+/// Generates a DAG of `size` devices (at least 5), including `svr`, `you`,
+/// `dac`, `fft` and `out`
+///
+/// Devices are laid out in a random order from `svr` to `out`, with `you`
+/// late in it, and each feeds one to three devices a short way further on.
+/// Every device reaches `out`, and keeping the links short bounds how many
+/// paths there are however large the graph. A chain of links from `svr`
+/// through `dac` and `fft`, in whichever order they were laid out, to `out`
+/// makes sure part 2 has paths too.
+impl InputGenerator for Day11 {
+    fn default_size(&self) -> usize {
+        583
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        const NAMED: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+        let size = size.max(NAMED.len());
+
+        let mut order: Vec<Option<String>> = vec![None; size];
+        order[0] = Some("svr".to_string());
+        order[size - 1] = Some("out".to_string());
+        for (name, low, high) in [("you", 60, 90), ("dac", 10, 90), ("fft", 10, 90)] {
+            let low = (size * low / 100).clamp(1, size - 2);
+            let high = (size * high / 100).clamp(low, size - 2);
+            loop {
+                let slot = rng.range(low as i64, high as i64) as usize;
+                if order[slot].is_none() {
+                    order[slot] = Some(name.to_string());
+                    break;
+                }
+            }
+        }
+
+        // Names stay three letters, as in the real input, while there are
+        // plenty of those to go round
+        let mut length = 3;
+        while 26usize.pow(length) < 2 * size {
+            length += 1;
+        }
+        let mut taken: HashSet<String> = NAMED.iter().map(|name| name.to_string()).collect();
+        let names: Vec<String> = order
+            .into_iter()
+            .map(|slot| {
+                slot.unwrap_or_else(|| loop {
+                    let name: String = (0..length)
+                        .map(|_| (b'a' + rng.below(26) as u8) as char)
+                        .collect();
+                    if taken.insert(name.clone()) {
+                        break name;
+                    }
+                })
+            })
+            .collect();
+
+        let window = (size / 16).max(4);
+        let position = |name: &str| names.iter().position(|n| n == name).unwrap();
+        let mut stops = [0, position("dac"), position("fft"), size - 1];
+        stops.sort_unstable();
+        let mut chained = vec![None; size];
+        for pair in stops.windows(2) {
+            let mut from = pair[0];
+            while from < pair[1] {
+                let to = rng.range(from as i64 + 1, (from + window).min(pair[1]) as i64) as usize;
+                chained[from] = Some(to);
+                from = to;
+            }
+        }
+
+        let mut lines: Vec<String> = Vec::with_capacity(size - 1);
+        for (i, name) in names.iter().enumerate().take(size - 1) {
+            let reach = (i + window).min(size - 1);
+            let wanted = match rng.below(20) {
+                0..=9 => 1,
+                10..=16 => 2,
+                _ => 3,
+            };
+            let mut outputs: Vec<usize> = chained[i].into_iter().collect();
+            while outputs.len() < wanted.min(reach - i) {
+                let target = rng.range(i as i64 + 1, reach as i64) as usize;
+                if !outputs.contains(&target) {
+                    outputs.push(target);
+                }
+            }
+            let outputs: Vec<&str> = outputs.iter().map(|&j| names[j].as_str()).collect();
+            lines.push(format!("{}: {}\n", name, outputs.join(" ")));
+        }
+        rng.shuffle(&mut lines);
+        lines.concat()
     }
 }
 
 // Helper functions
 
-/// Parse the input into a directed graph represented as an adjacency list
-fn parse_graph(input: &str) -> HashMap<&str, Vec<&str>> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (device, outputs) = line.split_once(": ").expect("Invalid line format");
-            let neighbors = outputs.split_whitespace().collect();
-            (device, neighbors)
-        })
+/// Parse the input into a directed graph with interned device names
+fn parse_graph(input: &str) -> Graph<&str> {
+    let mut graph = Graph::new();
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let (device, outputs) = line.split_once(": ").expect("Invalid line format");
+        graph.intern(device);
+        for output in outputs.split_whitespace() {
+            graph.add_edge(device, output);
+        }
+    }
+    graph
+}
+
+/// Split a comma-separated waypoint list, ignoring empty entries
+fn parse_waypoints(list: &str) -> Vec<&str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
         .collect()
 }
 
 /// Count all paths from start to target using DFS with backtracking
-fn count_paths_dfs<'a>(
-    graph: &HashMap<&'a str, Vec<&'a str>>,
-    current: &'a str,
-    target: &'a str,
-    visited: &mut HashSet<&'a str>,
+fn count_paths_dfs(
+    graph: &Graph<&str>,
+    current: usize,
+    target: usize,
+    visited: &mut [bool],
 ) -> usize {
     // Base case: reached the target
     if current == target {
         return 1;
     }
 
-    // Get neighbors or return 0 if node has no outgoing edges
-    let Some(neighbors) = graph.get(current) else {
-        return 0;
-    };
-
     // Mark current node as visited
-    visited.insert(current);
+    visited[current] = true;
 
     // Count paths through all unvisited neighbors
     let mut count = 0;
-    for &neighbor in neighbors {
-        if !visited.contains(neighbor) {
+    for neighbor in graph.neighbors(current) {
+        if !visited[neighbor] {
             count += count_paths_dfs(graph, neighbor, target, visited);
         }
     }
 
-    // Backtrack: remove current node from visited set
-    visited.remove(current);
+    // Backtrack: unmark current node
+    visited[current] = false;
 
     count
 }
@@ -91,59 +290,217 @@ fn count_paths_dfs<'a>(
 /// Count paths with memoization and state tracking for required nodes
 ///
 /// Uses DFS with memoization to handle graphs with exponentially many paths.
-/// State is represented as bit flags: 0=neither, 1=dac only, 2=fft only, 3=both.
-/// Memoizes (node, state) → count to avoid recomputing overlapping subproblems.
-fn count_paths_memoized<'a>(
-    graph: &HashMap<&'a str, Vec<&'a str>>,
-    current: &'a str,
-    target: &'a str,
-    visited: &mut HashSet<&'a str>,
-    memo: &mut HashMap<(&'a str, u8), usize>,
-    state: u8,
-) -> usize {
-    // Base case: reached target with both required nodes
+/// State is a bitmask of visited waypoints: bit `i` is set once `waypoints[i]`
+/// has been seen, so there can be at most 63 distinct waypoints. Memoizes
+/// (node, state) → count to avoid recomputing overlapping subproblems.
+///
+/// # Panics
+///
+/// Panics if the count overflows `u128`.
+fn count_paths_memoized(
+    graph: &Graph<&str>,
+    current: usize,
+    target: usize,
+    waypoints: &[usize],
+    visited: &mut [bool],
+    memo: &mut HashMap<(usize, u64), u128>,
+    state: u64,
+) -> u128 {
+    // Base case: reached target with every required node
     if current == target {
-        return if state == 3 { 1 } else { 0 };
+        let all_visited = (1u64 << waypoints.len()) - 1;
+        return if state == all_visited { 1 } else { 0 };
     }
 
     // Check memo (only if not in current path to avoid cycle issues)
-    if !visited.contains(current) {
+    if !visited[current] {
         if let Some(&cached) = memo.get(&(current, state)) {
             return cached;
         }
     }
 
-    // Dead end
-    let Some(neighbors) = graph.get(current) else {
-        return 0;
-    };
-
-    visited.insert(current);
+    visited[current] = true;
 
     // Update state based on current node
-    let new_state = match current {
-        "dac" => state | 1, // Set bit 0
-        "fft" => state | 2, // Set bit 1
-        _ => state,
+    let new_state = match waypoints.iter().position(|&w| w == current) {
+        Some(bit) => state | (1 << bit),
+        None => state,
     };
 
-    let mut count = 0;
-    for &neighbor in neighbors {
-        if !visited.contains(neighbor) {
-            count += count_paths_memoized(graph, neighbor, target, visited, memo, new_state);
+    let mut count = 0u128;
+    for neighbor in graph.neighbors(current) {
+        if !visited[neighbor] {
+            let paths =
+                count_paths_memoized(graph, neighbor, target, waypoints, visited, memo, new_state);
+            count = count.checked_add(paths).expect("Path count overflows u128");
         }
     }
 
-    visited.remove(current);
+    visited[current] = false;
 
-    // Cache result (only if we weren't in a cycle)
-    if !visited.contains(current) {
-        memo.insert((current, state), count);
-    }
+    // Cache result
+    memo.insert((current, state), count);
 
     count
 }
 
+//=============================================================================
+// Alternative Implementation: DAG Dynamic Programming
+//=============================================================================
+
+/// Solver counting paths by dynamic programming over a topological order
+///
+/// Uses [`Graph::count_paths`] for both parts, so any number of waypoints is
+/// supported, the cost is linear in the graph per waypoint, and counts are
+/// `u128`. Validation rejects graphs with a cycle, where the number of paths
+/// could be infinite and [`Day11`] would count simple paths instead.
+pub struct Day11Dag;
+
+impl Day for Day11Dag {
+    fn part1(&self, input: &str) -> String {
+        self.part1_with(input, &SolveContext::from_defaults(DAG_PARAMS))
+    }
+
+    fn part2(&self, input: &str) -> String {
+        self.part2_with(input, &SolveContext::from_defaults(DAG_PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        DAG_PARAMS
+    }
+
+    fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+        count_dag_paths(input, ctx.get_str("part1_start"), ctx.get_str("end"), "")
+    }
+
+    fn part2_with(&self, input: &str, ctx: &SolveContext) -> String {
+        count_dag_paths(
+            input,
+            ctx.get_str("part2_start"),
+            ctx.get_str("end"),
+            ctx.get_str("waypoints"),
+        )
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)?;
+        validate_acyclic(input)
+    }
+
+    fn draw_dot(&self, input: &str, ctx: &SolveContext) -> Option<String> {
+        Some(draw_graph(input, ctx))
+    }
+}
+
+/// Check that no device can feed back into itself
+///
+/// Points at the name of a device on a cycle, on the line listing its outputs.
+fn validate_acyclic(input: &str) -> Result<(), Diagnostic> {
+    let graph = parse_graph(input);
+    let Err(err) = graph.topological_order() else {
+        return Ok(());
+    };
+    let name = *graph.label(err.node);
+    let (idx, line) = input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.split_once(": ").is_some_and(|(device, _)| device == name))
+        .expect("A device on a cycle has outputs");
+    Err(Diagnostic::span(
+        idx + 1,
+        line,
+        0,
+        name.len(),
+        "a device on no cycle",
+        format!("`{name}` on a cycle"),
+    ))
+}
+
+/// Count paths from `start` to `end` through every node in the `waypoints` list
+fn count_dag_paths(input: &str, start: &str, end: &str, waypoints: &str) -> String {
+    let graph = parse_graph(input);
+    if start == end && graph.id(start).is_none() {
+        return "1".to_string();
+    }
+    let (Some(start), Some(end)) = (graph.id(start), graph.id(end)) else {
+        return "0".to_string();
+    };
+    let Some(waypoints) = parse_waypoints(waypoints)
+        .into_iter()
+        .map(|name| graph.id(name))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return "0".to_string();
+    };
+
+    graph
+        .count_paths(start, end, &waypoints)
+        .expect("Device graph has a cycle")
+        .to_string()
+}
+
+/// Draw the device graph with the start, end and waypoint devices highlighted
+///
+/// Both parts' start devices are green boxes, the end a red double circle and
+/// the waypoints gold diamonds. Every other device is a small grey dot so the
+/// few that matter stand out in a graph of hundreds.
+fn draw_graph(input: &str, ctx: &SolveContext) -> String {
+    let graph = parse_graph(input);
+    let starts = [ctx.get_str("part1_start"), ctx.get_str("part2_start")];
+    let end = ctx.get_str("end");
+    let waypoints = parse_waypoints(ctx.get_str("waypoints"));
+
+    dot::digraph("reactor", &graph, |id| {
+        let name = *graph.label(id);
+        if name == end {
+            vec![
+                ("shape", "doublecircle"),
+                ("style", "filled"),
+                ("fillcolor", "#f4a6a6"),
+            ]
+        } else if starts.contains(&name) {
+            vec![
+                ("shape", "box"),
+                ("style", "filled"),
+                ("fillcolor", "#a8e6a1"),
+            ]
+        } else if waypoints.contains(&name) {
+            vec![
+                ("shape", "diamond"),
+                ("style", "filled"),
+                ("fillcolor", "#f6d365"),
+            ]
+        } else {
+            vec![("shape", "point"), ("color", "#888888")]
+        }
+    })
+}
+
+/// Check that every line is `device: output output ...`
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    let is_name = |ch: char| ch.is_ascii_alphanumeric();
+    for (idx, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let mut cursor = Cursor::new(idx + 1, line);
+        if cursor.take_while(is_name).is_empty() {
+            return Err(cursor.error("a device name"));
+        }
+        cursor.expect_str(": ")?;
+        loop {
+            if cursor.take_while(is_name).is_empty() {
+                return Err(cursor.error("an output device name"));
+            }
+            if !cursor.eat(' ') {
+                break;
+            }
+        }
+        cursor.finish()?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,10 +526,18 @@ iii: out";
     #[test]
     fn test_parse_graph() {
         let graph = parse_graph(EXAMPLE);
-        assert_eq!(graph.get("you"), Some(&vec!["bbb", "ccc"]));
-        assert_eq!(graph.get("bbb"), Some(&vec!["ddd", "eee"]));
-        assert_eq!(graph.get("ccc"), Some(&vec!["ddd", "eee", "fff"]));
-        assert_eq!(graph.get("eee"), Some(&vec!["out"]));
+        let outputs = |device| {
+            let id = graph.id(device).unwrap();
+            graph
+                .neighbors(id)
+                .map(|n| *graph.label(n))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(outputs("you"), vec!["bbb", "ccc"]);
+        assert_eq!(outputs("bbb"), vec!["ddd", "eee"]);
+        assert_eq!(outputs("ccc"), vec!["ddd", "eee", "fff"]);
+        assert_eq!(outputs("eee"), vec!["out"]);
+        assert_eq!(outputs("out"), Vec::<&str>::new());
     }
 
     #[test]
@@ -244,6 +609,41 @@ hhh: out";
         assert_eq!(day.part2(input), "1");
     }
 
+    #[test]
+    fn test_part2_custom_waypoints() {
+        // Only paths through "hub" count when it is the single waypoint
+        let day = Day11;
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "hub");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "4");
+
+        // No waypoints: every path from svr to out counts
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "8");
+
+        // A waypoint listed twice still only has to be visited once
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "hub,hub");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "4");
+    }
+
+    #[test]
+    fn test_waypoint_limit() {
+        let names: Vec<String> = (0..64).map(|i| format!("w{}", i)).collect();
+        let names = names.join(",");
+        let waypoints = |day: &dyn Day| day.params()[3];
+        assert!(waypoints(&Day11).check(&names).is_err());
+        assert!(waypoints(&Day11)
+            .check(names.rsplit_once(',').unwrap().0)
+            .is_ok());
+        assert!(waypoints(&Day11Dag).check(&names).is_ok());
+    }
+
+    #[test]
+    fn test_part1_custom_start() {
+        let day = Day11;
+        let ctx = SolveContext::from_defaults(day.params()).with("part1_start", "hhh");
+        assert_eq!(day.part1_with(EXAMPLE, &ctx), "5");
+    }
+
     #[test]
     fn test_part2_both_required_reverse_order() {
         // Single path: svr -> fft -> dac -> out
@@ -251,6 +651,104 @@ hhh: out";
         let day = Day11;
         assert_eq!(day.part2(input), "1");
     }
+
+    #[test]
+    fn test_dag_examples() {
+        let day = Day11Dag;
+        assert_eq!(day.part1(EXAMPLE), "5");
+        assert_eq!(day.part2(PART2_EXAMPLE), "2");
+
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "hub");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "4");
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "8");
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "fft,hub,dac");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "0");
+        let ctx = SolveContext::from_defaults(day.params()).with("waypoints", "ccc,eee,dac,fff");
+        assert_eq!(day.part2_with(PART2_EXAMPLE, &ctx), "4");
+    }
+
+    #[test]
+    fn test_dag_count_beyond_u64() {
+        // 70 diamonds in a row give 2^70 paths
+        let input: String = (0..70)
+            .map(|i| {
+                format!(
+                    "n{i}: a{i} b{i}\na{i}: n{next}\nb{i}: n{next}\n",
+                    next = i + 1
+                )
+            })
+            .collect();
+        let day = Day11Dag;
+        let ctx = SolveContext::from_defaults(day.params())
+            .with("part1_start", "n0")
+            .with("end", "n70");
+        assert_eq!(day.part1_with(&input, &ctx), (1u128 << 70).to_string());
+    }
+
+    #[test]
+    fn test_dag_rejects_cycles() {
+        // Day11 counts the one simple path; with the cycle there are infinitely many walks
+        let input = "you: aaa\naaa: bbb out\nbbb: aaa";
+        assert_eq!(Day11.validate(input), Ok(()));
+        assert_eq!(Day11.part1(input), "1");
+        let err = Day11Dag.validate(input).unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (2, 1, 3));
+        assert_eq!(err.expected, "a device on no cycle");
+        assert_eq!(err.found, "`aaa` on a cycle");
+    }
+
+    #[test]
+    fn test_draw_dot() {
+        let ctx = SolveContext::from_defaults(PARAMS);
+        let dot = Day11Dag.draw_dot(PART2_EXAMPLE, &ctx).unwrap();
+        assert!(dot.starts_with("digraph \"reactor\" {\n"));
+        assert!(dot.contains("\"svr\" [shape=\"box\""));
+        assert!(dot.contains("\"out\" [shape=\"doublecircle\""));
+        assert!(dot.contains("\"dac\" [shape=\"diamond\""));
+        assert!(dot.contains("\"fft\" [shape=\"diamond\""));
+        assert!(dot.contains("\"aaa\" [shape=\"point\""));
+        let edges = PART2_EXAMPLE
+            .lines()
+            .map(|line| line.split_whitespace().count() - 1)
+            .sum::<usize>();
+        assert_eq!(dot.matches(" -> ").count(), edges);
+
+        let ctx = ctx.with("waypoints", "aaa");
+        let dot = Day11.draw_dot(PART2_EXAMPLE, &ctx).unwrap();
+        assert!(dot.contains("\"aaa\" [shape=\"diamond\""));
+        assert!(dot.contains("\"dac\" [shape=\"point\""));
+    }
+
+    #[test]
+    fn test_generator() {
+        for size in [5, 300] {
+            let input = Day11.generate(size, &mut Rng::new(size as u64));
+            assert!(validate_input(&input).is_ok());
+            assert_eq!(input.lines().count(), size - 1);
+            let graph = parse_graph(&input);
+            assert_eq!(graph.len(), size);
+            for name in ["svr", "you", "dac", "fft"] {
+                assert!(input.contains(&format!("{}: ", name)), "{}", name);
+            }
+
+            // Every device reaches out, and svr reaches it through both
+            // waypoints, so both parts have paths
+            assert_ne!(Day11.part1(&input), "0");
+            assert_ne!(Day11.part2(&input), "0");
+            assert_eq!(Day11Dag.part1(&input), Day11.part1(&input));
+            assert_eq!(Day11Dag.part2(&input), Day11.part2(&input));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_input(EXAMPLE).is_ok());
+
+        let err = validate_input("aaa: bbb\nbbb out\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.to_string(), "expected `: `, found a space");
+    }
 }
 
 // Define benchmarks using the common macro
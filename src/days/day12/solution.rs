@@ -6,7 +6,8 @@
 //! can fit all their required shapes.
 //!
 //! Algorithm:
-//! 1. Parse 6 polyomino shapes and 1000+ regions from input
+//! 1. Parse the numbered polyomino shapes (any number, any size) and 1000+
+//!    regions from input
 //! 2. Precompute all unique transformations (rotations/flips) for each shape
 //! 3. For each region, use backtracking to attempt placement
 //! 4. Count regions that successfully pack all shapes
@@ -16,198 +17,347 @@
 //! - Early rejection: Check area feasibility before backtracking
 //! - Bounding box pruning: Skip variants that exceed grid dimensions
 //! - First solution termination: Don't enumerate all packings
-//! - Flat grid layout: Vec<u8> with row-major indexing for cache locality
+//! - Flat grid layout: `common::grid::Grid<bool>`, row-major for cache locality
 //!
 //! Time: O(regions × W × H × V × branches) with heavy pruning
 //! Space: O(W × H) for grid + O(shapes × variants) for cache
 //!
+//! [`Day12Dlx`] instead models each region as an exact cover problem and
+//! solves it with dancing links. Both solvers report [`SearchStats`] through
+//! `search_stats`, shown by `--stats`, for comparison. [`Day12Parallel`]
+//! decides most regions from cheap bounds and only searches the rest, on all
+//! cores; `--stats` shows how many regions each phase decided.
+//!
 //! ## Part 2
 //! No Part 2 for this day
 
-use crate::runner::Day;
+use crate::common::exact_cover::{ExactCover, SearchStats};
+use crate::common::grid::Grid;
+use crate::common::rng::Rng;
+use crate::common::validate::{Cursor, Diagnostic};
+use crate::common::viz::{self, Frame, Observer, Rgb};
+use crate::runner::{Day, InputGenerator, SolveContext, Witness};
 use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
 /// Solver for Day 12
 pub struct Day12;
 
+impl Day12 {
+    /// Search effort summed over every region of `input`
+    pub fn search_stats(&self, input: &str) -> SearchStats {
+        count_packable(input, can_fit_all_shapes).1
+    }
+}
+
 impl Day for Day12 {
     fn part1(&self, input: &str) -> String {
-        let (transforms, regions) = parse_input(input);
-        let count = regions
-            .iter()
-            .filter(|region| can_fit_all_shapes(region, &transforms))
-            .count();
-        count.to_string()
+        count_packable(input, can_fit_all_shapes).0.to_string()
     }
 
     fn part2(&self, input: &str) -> String {
         // No Part 2 for Day 12 - return same result as Part 1
         self.part1(input)
     }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        let (transforms, regions) = parse_input(input);
+        let mut stats = SearchStats::default();
+        let witnesses = regions
+            .iter()
+            .enumerate()
+            .map(|(idx, region)| {
+                let label = format!("region {} ({}x{})", idx + 1, region.width, region.height);
+                match pack_region(region, &transforms, &mut stats, None) {
+                    Some(placements) => {
+                        let placements: Vec<String> =
+                            placements.iter().map(Placement::to_string).collect();
+                        Witness::new(1, label)
+                            .with("fits", "yes")
+                            .with("placements", placements.join(";"))
+                    }
+                    None => Witness::new(1, label).with("fits", "no"),
+                }
+            })
+            .collect();
+        Some(witnesses)
+    }
+
+    fn stats(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<(&'static str, u64)>> {
+        Some(self.search_stats(input).counters())
+    }
+
+    fn visualize(&self, input: &str, observer: &mut dyn Observer) -> bool {
+        let (transforms, regions) = parse_input(input);
+        let mut stats = SearchStats::default();
+        for region in &regions {
+            if !observer.wants_frame() {
+                break;
+            }
+            pack_region(region, &transforms, &mut stats, Some(&mut *observer));
+        }
+        true
+    }
+
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        Some(self)
+    }
 }
 
-// Data Structures
+/// Generates six 3×3 shapes and `size` regions, like the real input
+///
+/// Each shape has 5 to 7 connected cells touching every side of its box.
+/// Half the regions hold few enough shapes for each to get a 3×3 box of its
+/// own, and the other half more shape cells than the region has, so
+/// [`Day12Parallel`] decides them all without searching.
+impl InputGenerator for Day12 {
+    fn default_size(&self) -> usize {
+        1000
+    }
 
-/// A polyomino shape with normalized coordinates
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Polyomino {
-    cells: Vec<(i8, i8)>,
-    width: i8,
-    height: i8,
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        const SHAPES: usize = 6;
+        let mut input = String::new();
+        let mut areas = [0; SHAPES];
+        for (index, area) in areas.iter_mut().enumerate() {
+            let cells = random_shape(rng);
+            *area = cells.iter().filter(|&&filled| filled).count();
+            input.push_str(&format!("{}:\n", index));
+            for row in cells.chunks(3) {
+                input.extend(row.iter().map(|&filled| if filled { '#' } else { '.' }));
+                input.push('\n');
+            }
+            input.push('\n');
+        }
+
+        for _ in 0..size {
+            let width = rng.range(35, 50) as usize;
+            let height = rng.range(35, 50) as usize;
+            let mut counts = [0; SHAPES];
+            if rng.chance(0.5) {
+                let boxes = (width / 3) * (height / 3);
+                for _ in 0..rng.range(boxes as i64 * 3 / 4, boxes as i64) {
+                    counts[rng.index(SHAPES)] += 1;
+                }
+            } else {
+                let mut cells = 0;
+                while cells <= width * height {
+                    let shape = rng.index(SHAPES);
+                    counts[shape] += 1;
+                    cells += areas[shape];
+                }
+            }
+            let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+            input.push_str(&format!("{}x{}: {}\n", width, height, counts.join(" ")));
+        }
+        input
+    }
 }
 
+/// A random 3×3 shape, row-major, of 5 to 7 orthogonally connected cells
+/// with at least one in every row and column
+fn random_shape(rng: &mut Rng) -> [bool; 9] {
+    loop {
+        let mut cells = [true; 9];
+        let mut order: Vec<usize> = (0..9).collect();
+        rng.shuffle(&mut order);
+        for &cell in &order[..rng.range(2, 4) as usize] {
+            cells[cell] = false;
+        }
+
+        let spans =
+            (0..3).all(|i| (0..3).any(|j| cells[i * 3 + j]) && (0..3).any(|j| cells[j * 3 + i]));
+        if spans && is_connected(&cells) {
+            return cells;
+        }
+    }
+}
+
+/// Whether the filled cells of a 3×3 shape form one orthogonal group
+fn is_connected(cells: &[bool; 9]) -> bool {
+    let Some(first) = cells.iter().position(|&filled| filled) else {
+        return false;
+    };
+    let mut seen = [false; 9];
+    let mut stack = vec![first];
+    seen[first] = true;
+    while let Some(cell) = stack.pop() {
+        let (row, col) = (cell / 3, cell % 3);
+        let neighbours = [
+            (row > 0).then(|| cell - 3),
+            (row < 2).then(|| cell + 3),
+            (col > 0).then(|| cell - 1),
+            (col < 2).then(|| cell + 1),
+        ];
+        for next in neighbours.into_iter().flatten() {
+            if cells[next] && !seen[next] {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    seen == *cells
+}
+
+/// A solver deciding whether a region fits its shapes, recording its effort
+type RegionSolver = fn(&Region, &TransformCache, &mut SearchStats) -> bool;
+
+/// Count the regions `solver` can pack, with the total search effort
+fn count_packable(input: &str, solver: RegionSolver) -> (usize, SearchStats) {
+    let (transforms, regions) = parse_input(input);
+    let mut stats = SearchStats::default();
+    let count = regions
+        .iter()
+        .filter(|region| solver(region, &transforms, &mut stats))
+        .count();
+    (count, stats)
+}
+
+// Data Structures
+
 /// A transformed variant of a shape
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct ShapeVariant {
-    cells: Vec<(i8, i8)>,
-    width: i8,
-    height: i8,
+    cells: Vec<(i32, i32)>,
+    width: i32,
+    height: i32,
 }
 
-/// Cache of all unique transformations for each shape
-type TransformCache = Vec<Vec<ShapeVariant>>;
-
-/// Grid for tracking occupied cells
-struct Grid {
-    occupied: Vec<u8>,
-    width: usize,
-    height: usize,
+/// A copy of a shape placed in a region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    shape: usize,
+    /// Index into the shape's transformations
+    variant: usize,
+    x: usize,
+    y: usize,
 }
 
-impl Grid {
-    fn new(width: usize, height: usize) -> Self {
-        Self {
-            occupied: vec![0u8; width * height],
-            width,
-            height,
-        }
+impl fmt::Display for Placement {
+    /// `shape:variant@x,y`, with the top-left corner of the variant's box
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}@{},{}", self.shape, self.variant, self.x, self.y)
     }
 }
 
+/// Cache of all unique transformations for each shape
+type TransformCache = Vec<Vec<ShapeVariant>>;
+
 /// A region specification
 struct Region {
     width: usize,
     height: usize,
-    required_counts: [usize; 6],
+    /// Copies needed of each shape, indexed by shape number
+    required_counts: Vec<usize>,
 }
 
 // Parsing
 
+/// Parse shapes, introduced by `N:` headers, and the `WxH: counts` regions
+///
+/// Shapes are stored by their header number and may have any number of rows
+/// of any length. Regions listing fewer counts than there are shapes need
+/// none of the rest.
 fn parse_input(input: &str) -> (TransformCache, Vec<Region>) {
-    let lines: Vec<&str> = input.lines().collect();
-
-    // Parse 6 shapes (each is 4 lines: header + 3 grid lines, separated by blank lines)
-    let mut shapes = Vec::with_capacity(6);
-    let mut line_idx = 0;
-
-    for _ in 0..6 {
-        // Skip header line (e.g., "0:")
-        line_idx += 1;
-
-        // Parse 3 lines of grid
-        let grid_lines = &lines[line_idx..line_idx + 3];
-        shapes.push(parse_shape(grid_lines));
-        line_idx += 3;
+    let mut shapes: Vec<Grid<bool>> = Vec::new();
+    let mut regions = Vec::new();
+    let mut lines = input.lines().map(str::trim).peekable();
 
-        // Skip blank line (if present)
-        if line_idx < lines.len() && lines[line_idx].trim().is_empty() {
-            line_idx += 1;
+    while let Some(line) = lines.next() {
+        if line.is_empty() {
+            continue;
+        }
+        match shape_header(line) {
+            Some(id) => {
+                let mut rows = Vec::new();
+                while let Some(row) = lines.next_if(|line| is_shape_row(line)) {
+                    rows.push(row);
+                }
+                if shapes.len() <= id {
+                    shapes.resize(id + 1, Grid::new(0, 0, false));
+                }
+                shapes[id] = parse_shape(&rows);
+            }
+            None => regions.push(parse_region(line)),
         }
     }
 
-    // Precompute all transformations
-    let transforms = precompute_transforms(&shapes);
+    for region in &mut regions {
+        if region.required_counts.len() < shapes.len() {
+            region.required_counts.resize(shapes.len(), 0);
+        }
+    }
 
-    // Parse regions (remaining lines)
-    let regions = lines[line_idx..]
-        .iter()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| parse_region(line))
-        .collect();
+    (precompute_transforms(&shapes), regions)
+}
 
-    (transforms, regions)
+/// The shape number of a header line like `4:`
+fn shape_header(line: &str) -> Option<usize> {
+    line.strip_suffix(':')?.parse().ok()
 }
 
-fn parse_shape(lines: &[&str]) -> Polyomino {
-    let mut cells = Vec::new();
+/// Whether a line is a row of a shape drawing
+fn is_shape_row(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|ch| ch == '#' || ch == '.')
+}
 
+/// Parse a shape's rows into a grid of filled cells
+///
+/// Short rows are padded with empty cells.
+fn parse_shape(lines: &[&str]) -> Grid<bool> {
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let mut shape = Grid::new(width, lines.len(), false);
     for (y, line) in lines.iter().enumerate() {
         for (x, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                cells.push((x as i8, y as i8));
-            }
+            shape[(x, y)] = ch == '#';
         }
     }
-
-    // Normalize to (0, 0) origin
-    if !cells.is_empty() {
-        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap();
-        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap();
-
-        for cell in &mut cells {
-            cell.0 -= min_x;
-            cell.1 -= min_y;
-        }
-    }
-
-    let width = cells.iter().map(|&(x, _)| x).max().unwrap_or(0) + 1;
-    let height = cells.iter().map(|&(_, y)| y).max().unwrap_or(0) + 1;
-
-    Polyomino {
-        cells,
-        width,
-        height,
-    }
+    shape
 }
 
 fn parse_region(line: &str) -> Region {
     // Format: "50x44: 49 45 27 41 32 30"
-    let (dims, counts) = line.split_once(": ").unwrap();
+    let (dims, counts) = line.split_once(':').unwrap();
     let (w, h) = dims.split_once('x').unwrap();
 
-    let mut required_counts = [0; 6];
-    for (i, count_str) in counts.split_whitespace().enumerate() {
-        required_counts[i] = count_str.parse().unwrap();
-    }
-
     Region {
         width: w.parse().unwrap(),
         height: h.parse().unwrap(),
-        required_counts,
+        required_counts: counts
+            .split_whitespace()
+            .map(|count| count.parse().unwrap())
+            .collect(),
     }
 }
 
 // Transformation Generation
 
-fn precompute_transforms(shapes: &[Polyomino]) -> TransformCache {
+fn precompute_transforms(shapes: &[Grid<bool>]) -> TransformCache {
     shapes
         .iter()
         .map(|shape| {
             let mut variants = HashSet::new();
 
             // Try both non-flipped and flipped versions
-            for flip in [false, true] {
-                let mut current = shape.cells.clone();
-
-                if flip {
-                    // Horizontal flip: negate x coordinates
-                    for cell in &mut current {
-                        cell.0 = -cell.0;
-                    }
-                }
-
+            for mut current in [shape.clone(), shape.flipped_horizontal()] {
                 // Try 4 rotations: 0°, 90°, 180°, 270°
                 for _ in 0..4 {
                     // Normalize and add to set
-                    let normalized = normalize_cells(&current);
-                    variants.insert(normalized);
-
-                    // Rotate 90° clockwise: (x, y) → (y, -x)
-                    current = current.iter().map(|&(x, y)| (y, -x)).collect();
+                    variants.insert(normalize_cells(&filled_cells(&current)));
+                    current = current.rotated_cw();
                 }
             }
 
-            // Convert HashSet to Vec of ShapeVariants
+            // Convert HashSet to Vec of ShapeVariants, sorted so variant
+            // indices are the same on every run
+            let mut variants: Vec<_> = variants.into_iter().collect();
+            variants.sort_unstable();
             variants
                 .into_iter()
                 .map(|cells| {
@@ -224,7 +374,16 @@ fn precompute_transforms(shapes: &[Polyomino]) -> TransformCache {
         .collect()
 }
 
-fn normalize_cells(cells: &[(i8, i8)]) -> Vec<(i8, i8)> {
+/// Coordinates of the filled cells of a shape grid
+fn filled_cells(shape: &Grid<bool>) -> Vec<(i32, i32)> {
+    shape
+        .iter()
+        .filter(|&(_, &filled)| filled)
+        .map(|((x, y), _)| (x as i32, y as i32))
+        .collect()
+}
+
+fn normalize_cells(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
     if cells.is_empty() {
         return Vec::new();
     }
@@ -240,38 +399,63 @@ fn normalize_cells(cells: &[(i8, i8)]) -> Vec<(i8, i8)> {
 
 // Grid Operations
 
-fn can_place(grid: &Grid, variant: &ShapeVariant, base_x: usize, base_y: usize) -> bool {
-    for &(dx, dy) in &variant.cells {
-        let x = base_x + dx as usize;
-        let y = base_y + dy as usize;
-
-        // Check bounds
-        if x >= grid.width || y >= grid.height {
-            return false;
-        }
-
-        // Check occupancy with flat indexing
-        let idx = y * grid.width + x;
-        if grid.occupied[idx] != 0 {
-            return false;
-        }
-    }
-    true
+fn can_place(grid: &Grid<bool>, variant: &ShapeVariant, base_x: usize, base_y: usize) -> bool {
+    variant.cells.iter().all(|&(dx, dy)| {
+        // Out of bounds counts as occupied
+        grid.get(base_x + dx as usize, base_y + dy as usize) == Some(&false)
+    })
 }
 
-fn place(grid: &mut Grid, variant: &ShapeVariant, base_x: usize, base_y: usize, occupy: bool) {
-    let val = if occupy { 1u8 } else { 0u8 };
+fn place(
+    grid: &mut Grid<bool>,
+    variant: &ShapeVariant,
+    base_x: usize,
+    base_y: usize,
+    occupy: bool,
+) {
     for &(dx, dy) in &variant.cells {
-        let x = base_x + dx as usize;
-        let y = base_y + dy as usize;
-        let idx = y * grid.width + x;
-        grid.occupied[idx] = val;
+        grid[(base_x + dx as usize, base_y + dy as usize)] = occupy;
     }
 }
 
 // Backtracking Algorithm
 
-fn can_fit_all_shapes(region: &Region, transforms: &TransformCache) -> bool {
+/// Cells the region's shapes cover in total, or `None` if it needs a shape
+/// that doesn't exist
+fn required_area(region: &Region, transforms: &TransformCache) -> Option<usize> {
+    region
+        .required_counts
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(shape, &count)| Some(count * transforms.get(shape)?[0].cells.len()))
+        .sum()
+}
+
+fn can_fit_all_shapes(
+    region: &Region,
+    transforms: &TransformCache,
+    stats: &mut SearchStats,
+) -> bool {
+    pack_region(region, transforms, stats, None).is_some()
+}
+
+/// Find a packing of the region's shapes, or `None` if there is none
+///
+/// The observer sees the empty region, then the packing after every shape
+/// placed or taken back.
+fn pack_region(
+    region: &Region,
+    transforms: &TransformCache,
+    stats: &mut SearchStats,
+    mut observer: Option<&mut dyn Observer>,
+) -> Option<Vec<Placement>> {
+    // Early rejection: Check if total area is feasible
+    match required_area(region, transforms) {
+        Some(cells) if cells <= region.width * region.height => {}
+        _ => return None,
+    }
+
     // Build placement list (flattened: each shape repeated by its count)
     let mut placements = Vec::new();
     for (shape_id, &count) in region.required_counts.iter().enumerate() {
@@ -280,30 +464,61 @@ fn can_fit_all_shapes(region: &Region, transforms: &TransformCache) -> bool {
         }
     }
 
-    // Early rejection: Check if total area is feasible
-    let total_cells: usize = placements
-        .iter()
-        .map(|&shape_id| transforms[shape_id][0].cells.len())
-        .sum();
-
-    if total_cells > region.width * region.height {
-        return false;
-    }
-
     // Sort by MRV heuristic: shapes with fewer variants first (more constrained)
     placements.sort_by_key(|&shape_id| transforms[shape_id].len());
 
     // Initialize empty grid and attempt backtracking
-    let mut grid = Grid::new(region.width, region.height);
-    backtrack(&mut grid, &placements, 0, transforms)
+    let mut grid = Grid::new(region.width, region.height, false);
+    let mut placed = Vec::with_capacity(placements.len());
+    viz::emit(&mut observer, || render_packing(&grid, &[], transforms));
+    backtrack(
+        &mut grid,
+        &placements,
+        0,
+        transforms,
+        &mut placed,
+        stats,
+        &mut observer,
+    )
+    .then_some(placed)
+}
+
+/// Colours for placed shapes, cycled in placement order
+const SHAPE_COLOURS: [Rgb; 8] = [
+    Rgb(230, 85, 70),
+    Rgb(80, 170, 90),
+    Rgb(70, 130, 220),
+    Rgb(240, 190, 60),
+    Rgb(160, 90, 200),
+    Rgb(60, 190, 190),
+    Rgb(240, 140, 50),
+    Rgb(200, 200, 200),
+];
+
+/// Draw a region with each placed shape in its own colour
+fn render_packing(grid: &Grid<bool>, placed: &[Placement], transforms: &TransformCache) -> Frame {
+    let mut frame = Grid::new(grid.width(), grid.height(), Rgb(28, 28, 36));
+    for (idx, placement) in placed.iter().enumerate() {
+        let variant = &transforms[placement.shape][placement.variant];
+        for &(dx, dy) in &variant.cells {
+            let cell = (placement.x + dx as usize, placement.y + dy as usize);
+            frame[cell] = SHAPE_COLOURS[idx % SHAPE_COLOURS.len()];
+        }
+    }
+    frame
 }
 
 fn backtrack(
-    grid: &mut Grid,
+    grid: &mut Grid<bool>,
     placements: &[usize],
     idx: usize,
     transforms: &TransformCache,
+    placed: &mut Vec<Placement>,
+    stats: &mut SearchStats,
+    observer: &mut Option<&mut dyn Observer>,
 ) -> bool {
+    stats.nodes += 1;
+
     // Base case: all shapes placed successfully
     if idx == placements.len() {
         return true;
@@ -312,26 +527,44 @@ fn backtrack(
     let shape_id = placements[idx];
 
     // Try each transformation variant
-    for variant in &transforms[shape_id] {
+    for (variant_id, variant) in transforms[shape_id].iter().enumerate() {
         // Early rejection: variant too large for grid
-        if variant.width as usize > grid.width || variant.height as usize > grid.height {
+        if variant.width as usize > grid.width() || variant.height as usize > grid.height() {
             continue;
         }
 
         // Try placing at each position (top-left scan order)
-        for y in 0..=(grid.height - variant.height as usize) {
-            for x in 0..=(grid.width - variant.width as usize) {
+        for y in 0..=(grid.height() - variant.height as usize) {
+            for x in 0..=(grid.width() - variant.width as usize) {
                 if can_place(grid, variant, x, y) {
                     // Place shape
                     place(grid, variant, x, y, true);
+                    placed.push(Placement {
+                        shape: shape_id,
+                        variant: variant_id,
+                        x,
+                        y,
+                    });
+                    viz::emit(observer, || render_packing(grid, placed, transforms));
 
                     // Recurse to next shape
-                    if backtrack(grid, placements, idx + 1, transforms) {
+                    if backtrack(
+                        grid,
+                        placements,
+                        idx + 1,
+                        transforms,
+                        placed,
+                        stats,
+                        observer,
+                    ) {
                         return true; // Early termination on first valid solution
                     }
 
                     // Backtrack: remove placement
                     place(grid, variant, x, y, false);
+                    placed.pop();
+                    stats.backtracks += 1;
+                    viz::emit(observer, || render_packing(grid, placed, transforms));
                 }
             }
         }
@@ -340,6 +573,337 @@ fn backtrack(
     false
 }
 
+//=============================================================================
+// Alternative Implementation: Exact Cover with Dancing Links
+//=============================================================================
+
+/// Solver packing each region as an exact cover problem
+///
+/// Every shape is a primary column that must be covered as many times as the
+/// region needs copies of it, and every cell of the region is a secondary
+/// column, since cells may stay empty. Each placement of a shape variant is a
+/// row covering its shape and cells. Rows are numbered in scan order of their
+/// top-left corner and copies of a shape are chosen in increasing row order,
+/// so the search never tries the same packing with its copies swapped.
+pub struct Day12Dlx;
+
+impl Day12Dlx {
+    /// Search effort summed over every region of `input`
+    pub fn search_stats(&self, input: &str) -> SearchStats {
+        count_packable(input, can_fit_exact_cover).1
+    }
+}
+
+impl Day for Day12Dlx {
+    fn part1(&self, input: &str) -> String {
+        count_packable(input, can_fit_exact_cover).0.to_string()
+    }
+
+    fn part2(&self, input: &str) -> String {
+        // No Part 2 for Day 12 - return same result as Part 1
+        self.part1(input)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn stats(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<(&'static str, u64)>> {
+        Some(self.search_stats(input).counters())
+    }
+}
+
+/// Build the exact cover problem for a region and look for one solution
+fn can_fit_exact_cover(
+    region: &Region,
+    transforms: &TransformCache,
+    stats: &mut SearchStats,
+) -> bool {
+    // Same cheap rejection as the backtracking solver
+    match required_area(region, transforms) {
+        Some(cells) if cells <= region.width * region.height => {}
+        _ => return false,
+    }
+
+    let shapes = region.required_counts.len();
+    let mut problem =
+        ExactCover::with_multiplicities(&region.required_counts, region.width * region.height);
+    let mut columns = Vec::new();
+    for y in 0..region.height {
+        for x in 0..region.width {
+            for (shape, variants) in transforms.iter().enumerate() {
+                if region.required_counts[shape] == 0 {
+                    continue;
+                }
+                for variant in variants {
+                    let (right, bottom) = (x + variant.width as usize, y + variant.height as usize);
+                    if right > region.width || bottom > region.height {
+                        continue;
+                    }
+                    columns.clear();
+                    columns.push(shape);
+                    columns.extend(variant.cells.iter().map(|&(dx, dy)| {
+                        shapes + (y + dy as usize) * region.width + x + dx as usize
+                    }));
+                    problem.add_row(&columns);
+                }
+            }
+        }
+    }
+
+    let fits = problem.solve().is_some();
+    *stats += problem.stats();
+    fits
+}
+
+//=============================================================================
+// Alternative Implementation: Bounds First, Then Parallel Search
+//=============================================================================
+
+/// Solver that decides regions from cheap bounds before searching
+///
+/// A region fits without search if every shape can have a bounding box of its
+/// own, and can't fit if the shapes have more cells than the region. Only the
+/// regions in between are backtracked, spread over threads with
+/// [`std::thread::scope`].
+pub struct Day12Parallel;
+
+/// How many regions each phase of [`Day12Parallel`] decided
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseCounts {
+    /// Regions where each shape gets its own bounding box
+    pub fits_by_boxes: usize,
+    /// Regions whose shapes need more cells than they have
+    pub exceeds_area: usize,
+    /// Regions left to the backtracking search
+    pub searched: usize,
+    /// Searched regions that fit
+    pub searched_fit: usize,
+    /// Effort of the search, summed over its threads
+    pub search: SearchStats,
+}
+
+impl PhaseCounts {
+    /// Regions that fit, from either phase
+    pub fn fit(&self) -> usize {
+        self.fits_by_boxes + self.searched_fit
+    }
+}
+
+impl Day12Parallel {
+    /// How many regions of `input` each phase decided
+    pub fn phase_counts(&self, input: &str) -> PhaseCounts {
+        let (transforms, regions) = parse_input(input);
+        let mut counts = PhaseCounts::default();
+        let mut undecided = Vec::new();
+        for region in &regions {
+            match classify(region, &transforms) {
+                Some(true) => counts.fits_by_boxes += 1,
+                Some(false) => counts.exceeds_area += 1,
+                None => undecided.push(region),
+            }
+        }
+        counts.searched = undecided.len();
+        (counts.searched_fit, counts.search) = count_fitting_parallel(&undecided, &transforms);
+        counts
+    }
+}
+
+impl Day for Day12Parallel {
+    fn part1(&self, input: &str) -> String {
+        self.phase_counts(input).fit().to_string()
+    }
+
+    fn part2(&self, input: &str) -> String {
+        // No Part 2 for Day 12 - return same result as Part 1
+        self.part1(input)
+    }
+
+    fn validate(&self, input: &str) -> Result<(), Diagnostic> {
+        validate_input(input)
+    }
+
+    fn stats(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<(&'static str, u64)>> {
+        let counts = self.phase_counts(input);
+        let mut stats = vec![
+            ("fits_by_boxes", counts.fits_by_boxes as u64),
+            ("exceeds_area", counts.exceeds_area as u64),
+            ("searched", counts.searched as u64),
+            ("searched_fit", counts.searched_fit as u64),
+        ];
+        stats.extend(counts.search.counters());
+        Some(stats)
+    }
+}
+
+/// Decide a region from bounds alone, or `None` if it needs a search
+fn classify(region: &Region, transforms: &TransformCache) -> Option<bool> {
+    match required_area(region, transforms) {
+        Some(cells) if cells <= region.width * region.height => {}
+        _ => return Some(false),
+    }
+
+    // Turn every shape long side across; a box as long as the longest side and
+    // as tall as the tallest short side then holds any of them
+    let (mut long, mut short, mut pieces) = (0, 0, 0);
+    for (shape, &count) in region.required_counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let variant = &transforms[shape][0];
+        let (width, height) = (variant.width as usize, variant.height as usize);
+        long = long.max(width.max(height));
+        short = short.max(width.min(height));
+        pieces += count;
+    }
+    if pieces == 0 {
+        return Some(true);
+    }
+
+    let boxes = |box_width: usize, box_height: usize| {
+        (region.width / box_width) * (region.height / box_height)
+    };
+    if boxes(long, short).max(boxes(short, long)) >= pieces {
+        return Some(true);
+    }
+    None
+}
+
+/// Backtrack the regions on every available core, counting those that fit
+/// and the search effort of all threads
+///
+/// Threads take the next unclaimed region until none are left, so a few slow
+/// regions don't hold up a whole batch.
+fn count_fitting_parallel(
+    regions: &[&Region],
+    transforms: &TransformCache,
+) -> (usize, SearchStats) {
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(regions.len());
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut stats = SearchStats::default();
+                    let mut fit = 0;
+                    while let Some(region) = regions.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if can_fit_all_shapes(region, transforms, &mut stats) {
+                            fit += 1;
+                        }
+                    }
+                    (fit, stats)
+                })
+            })
+            .collect();
+        let mut total = (0, SearchStats::default());
+        for worker in workers {
+            let (fit, stats) = worker.join().expect("Region search panicked");
+            total.0 += fit;
+            total.1 += stats;
+        }
+        total
+    })
+}
+
+/// Check for shapes numbered from 0, then `WxH: counts` regions
+///
+/// Each shape is an `N:` header followed by at least one row of `#` and `.`.
+/// Regions may list at most one count per shape.
+fn validate_input(input: &str) -> Result<(), Diagnostic> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut idx = 0;
+    let mut shapes = 0;
+
+    while idx < lines.len() {
+        let line = lines[idx];
+        if line.trim().is_empty() {
+            idx += 1;
+            continue;
+        }
+        if shape_header(line).is_none() {
+            break;
+        }
+
+        let mut cursor = Cursor::new(idx + 1, line);
+        let number_start = cursor.pos();
+        let number = cursor.number()?;
+        if number != shapes {
+            return Err(cursor.error_since(
+                number_start,
+                format!("shape {}", shapes),
+                format!("shape {}", number),
+            ));
+        }
+        cursor.expect(':')?;
+        cursor.finish()?;
+        idx += 1;
+
+        let rows_start = idx;
+        while let Some(&row) = lines.get(idx) {
+            if row.trim().is_empty() || row.contains(':') {
+                break;
+            }
+            let mut cursor = Cursor::new(idx + 1, row);
+            while !cursor.is_done() {
+                cursor.one_of(&['#', '.'], "`#` or `.`")?;
+            }
+            idx += 1;
+        }
+        if idx == rows_start {
+            return Err(match lines.get(idx) {
+                Some(line) => Cursor::new(idx + 1, line).error("a shape row"),
+                None => Diagnostic::end_of_input(input, "a shape row"),
+            });
+        }
+        shapes += 1;
+    }
+
+    if shapes == 0 {
+        return Err(match lines.get(idx) {
+            Some(line) => Cursor::new(idx + 1, line).error("a shape header"),
+            None => Diagnostic::end_of_input(input, "a shape header"),
+        });
+    }
+
+    let mut regions = 0;
+    for (offset, line) in lines[idx..].iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut cursor = Cursor::new(idx + offset + 1, line);
+        cursor.number()?;
+        cursor.expect('x')?;
+        cursor.number()?;
+        cursor.expect_str(": ")?;
+        let counts_start = cursor.pos();
+        let mut counts = 0;
+        loop {
+            cursor.number()?;
+            counts += 1;
+            if !cursor.eat(' ') {
+                break;
+            }
+        }
+        if counts > shapes {
+            return Err(cursor.error_since(
+                counts_start,
+                format!("at most {} shape counts", shapes),
+                format!("{}", counts),
+            ));
+        }
+        cursor.finish()?;
+        regions += 1;
+    }
+
+    if regions == 0 {
+        return Err(Diagnostic::end_of_input(input, "a region `WxH: counts`"));
+    }
+    Ok(())
+}
+
 // Tests
 
 #[cfg(test)]
@@ -390,11 +954,11 @@ mod tests {
     fn test_parse_shape() {
         let lines = ["###", "#.#", "#.#"];
         let shape = parse_shape(&lines);
-        assert_eq!(shape.cells.len(), 7); // 7 '#' cells
-        assert_eq!(shape.width, 3);
-        assert_eq!(shape.height, 3);
-        // Check normalization (should start at 0,0)
-        assert!(shape.cells.contains(&(0, 0)));
+        let cells = filled_cells(&shape);
+        assert_eq!(cells.len(), 7); // 7 '#' cells
+        assert_eq!(shape.width(), 3);
+        assert_eq!(shape.height(), 3);
+        assert!(cells.contains(&(0, 0)));
     }
 
     #[test]
@@ -419,7 +983,7 @@ mod tests {
 
     #[test]
     fn test_can_place() {
-        let grid = Grid::new(4, 4);
+        let grid = Grid::new(4, 4, false);
         let variant = ShapeVariant {
             cells: vec![(0, 0), (1, 0), (2, 0)],
             width: 3,
@@ -438,10 +1002,14 @@ mod tests {
         let region = Region {
             width: 4,
             height: 4,
-            required_counts: [0, 0, 0, 0, 2, 0],
+            required_counts: vec![0, 0, 0, 0, 2, 0],
         };
 
-        assert!(can_fit_all_shapes(&region, &transforms));
+        assert!(can_fit_all_shapes(
+            &region,
+            &transforms,
+            &mut SearchStats::default()
+        ));
     }
 
     #[test]
@@ -451,10 +1019,234 @@ mod tests {
         let region = Region {
             width: 12,
             height: 5,
-            required_counts: [1, 0, 1, 0, 3, 2],
+            required_counts: vec![1, 0, 1, 0, 3, 2],
+        };
+
+        assert!(!can_fit_all_shapes(
+            &region,
+            &transforms,
+            &mut SearchStats::default()
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        // The third example region takes a long exhaustive search to rule out,
+        // so swap it for one that fails on area
+        let (fitting, _) = EXAMPLE.rsplit_once('\n').unwrap();
+        let input = format!("{}\n2x2: 0 0 0 0 1 0", fitting);
+        let witnesses = Day12.explain(&input, &SolveContext::default()).unwrap();
+        let fits: Vec<_> = witnesses.iter().map(|w| w.fact("fits").unwrap()).collect();
+        assert_eq!(fits, vec!["yes", "yes", "no"]);
+        assert_eq!(witnesses[0].label, "region 1 (4x4)");
+        assert_eq!(witnesses[2].fact("placements"), None);
+
+        // The placements pack the region without overlapping
+        let (transforms, regions) = parse_input(&input);
+        for (witness, region) in witnesses.iter().zip(&regions).take(2) {
+            let mut grid = Grid::new(region.width, region.height, false);
+            let mut counts = vec![0; transforms.len()];
+            for placement in witness.fact("placements").unwrap().split(';') {
+                let (shape, rest) = placement.split_once(':').unwrap();
+                let (variant, corner) = rest.split_once('@').unwrap();
+                let (x, y) = corner.split_once(',').unwrap();
+                let shape: usize = shape.parse().unwrap();
+                let variant = &transforms[shape][variant.parse::<usize>().unwrap()];
+                let (x, y) = (x.parse().unwrap(), y.parse().unwrap());
+                assert!(can_place(&grid, variant, x, y));
+                place(&mut grid, variant, x, y, true);
+                counts[shape] += 1;
+            }
+            assert_eq!(counts, region.required_counts);
+        }
+    }
+
+    #[test]
+    fn test_visualize() {
+        // The first example region: the empty region, then each of the two
+        // shapes as it is placed
+        let (first, _) = EXAMPLE.split_once("\n12x5").unwrap();
+        let mut recorder = viz::Recorder::new();
+        assert!(Day12.visualize(first, &mut recorder));
+        assert_eq!(recorder.frames.len(), 3);
+
+        let empty = Rgb(28, 28, 36);
+        let filled = |frame: &Frame| frame.cells().iter().filter(|&&c| c != empty).count();
+        let counts: Vec<_> = recorder.frames.iter().map(filled).collect();
+        assert_eq!(counts, vec![0, 7, 14]);
+        assert_eq!(
+            (recorder.frames[2].width(), recorder.frames[2].height()),
+            (4, 4)
+        );
+    }
+
+    #[test]
+    fn test_dlx_example() {
+        // Every example region passes the area check, so the search runs
+        let (count, stats) = count_packable(EXAMPLE, can_fit_exact_cover);
+        assert_eq!(count, 2);
+        assert!(stats.nodes > 0);
+        assert!(stats.backtracks > 0);
+
+        // Two copies of shape 4 in 4x4: one node per placement plus the root
+        let small = EXAMPLE.replace("\n12x5: 1 0 1 0 2 2\n12x5: 1 0 1 0 3 2", "");
+        assert!(Day12.search_stats(&small).nodes >= 3);
+        assert!(Day12Dlx.search_stats(&small).nodes >= 3);
+
+        let ctx = SolveContext::default();
+        let stats = Day12Dlx.stats(&small, &ctx).unwrap();
+        assert_eq!(stats, Day12Dlx.search_stats(&small).counters());
+        assert_eq!(stats[0].0, "nodes");
+    }
+
+    #[test]
+    fn test_dlx_matches_backtracking() {
+        let (transforms, _) = parse_input(EXAMPLE);
+        for (width, height) in [(3, 3), (4, 4), (5, 4), (6, 3), (6, 6)] {
+            for counts in [[1, 1, 0, 0, 0, 0], [0, 0, 0, 0, 2, 0], [0, 1, 0, 0, 0, 2]] {
+                let region = Region {
+                    width,
+                    height,
+                    required_counts: counts.to_vec(),
+                };
+                let mut stats = SearchStats::default();
+                assert_eq!(
+                    can_fit_exact_cover(&region, &transforms, &mut stats),
+                    can_fit_all_shapes(&region, &transforms, &mut stats),
+                    "{}x{} {:?}",
+                    width,
+                    height,
+                    counts
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_any_number_of_shapes() {
+        // A domino, an L tromino and a 1x5 bar, with ragged rows and a
+        // region that omits the bar's count
+        let input = "\
+0:
+##
+
+1:
+#.
+##
+
+2:
+#####
+
+2x2: 0 1
+5x2: 1 1 1
+4x2: 1 2 0
+5x1: 0 0 1
+3x3: 0 3";
+        assert!(validate_input(input).is_ok());
+
+        let (transforms, regions) = parse_input(input);
+        assert_eq!(transforms.len(), 3);
+        assert_eq!(transforms[0].len(), 2);
+        assert_eq!(transforms[1].len(), 4);
+        assert_eq!(regions[0].required_counts, vec![0, 1, 0]);
+
+        assert_eq!(Day12.part1(input), "3");
+        assert_eq!(Day12Dlx.part1(input), "3");
+    }
+
+    #[test]
+    fn test_unknown_shape_never_fits() {
+        let input = "0:\n#\n\n3x3: 1 1";
+        assert!(validate_input(input).is_err());
+        assert_eq!(Day12.part1(input), "0");
+        assert_eq!(Day12Dlx.part1(input), "0");
+    }
+
+    #[test]
+    fn test_parallel_phases() {
+        // The example regions are too tight for one box per shape
+        let day = Day12Parallel;
+        assert_eq!(day.part1(EXAMPLE), "2");
+        assert_eq!(
+            day.phase_counts(EXAMPLE),
+            PhaseCounts {
+                fits_by_boxes: 0,
+                exceeds_area: 0,
+                searched: 3,
+                searched_fit: 2,
+                search: Day12.search_stats(EXAMPLE),
+            }
+        );
+        let stats = day.stats(EXAMPLE, &SolveContext::default()).unwrap();
+        assert_eq!(stats[2], ("searched", 3));
+        assert_eq!(stats[4].0, "nodes");
+
+        // Three boxes side by side, and too many cells for a 2x2 region
+        let input = format!("{}\n9x3: 0 0 0 0 3 0\n2x2: 1 0 0 0 0 0", EXAMPLE);
+        let counts = day.phase_counts(&input);
+        assert_eq!((counts.fits_by_boxes, counts.exceeds_area), (1, 1));
+        assert_eq!(counts.fit(), 3);
+        assert_eq!(Day12.part1(&input), "3");
+    }
+
+    #[test]
+    fn test_generator() {
+        let input = Day12.generate(20, &mut Rng::new(12));
+        assert!(validate_input(&input).is_ok());
+        let (transforms, regions) = parse_input(&input);
+        assert_eq!((transforms.len(), regions.len()), (6, 20));
+        for variants in &transforms {
+            let cells = variants[0].cells.len();
+            assert!((5..=7).contains(&cells), "{}", cells);
+            assert_eq!((variants[0].width, variants[0].height), (3, 3));
+        }
+
+        // Every region is decided by bounds alone
+        let counts = Day12Parallel.phase_counts(&input);
+        assert_eq!(counts.searched, 0);
+        assert_eq!(counts.fits_by_boxes + counts.exceeds_area, 20);
+        assert_eq!(Day12.part1(&input), counts.fit().to_string());
+    }
+
+    #[test]
+    fn test_is_connected() {
+        let shape = |rows: &str| -> [bool; 9] {
+            let cells: Vec<bool> = rows.chars().map(|ch| ch == '#').collect();
+            cells.try_into().unwrap()
         };
+        assert!(is_connected(&shape("###.#..#.")));
+        assert!(!is_connected(&shape("#.#.#.#.#")));
+        assert!(!is_connected(&shape(".........")));
+    }
+
+    #[test]
+    fn test_boxes_turn_long_shapes() {
+        // A 1x3 bar and a 3x1 bar fit one above the other in 3x2, whichever
+        // way each was drawn
+        let input = "0:\n###\n\n1:\n#\n#\n#\n\n3x2: 1 1\n2x3: 1 1\n3x3: 2 2";
+        let (transforms, regions) = parse_input(input);
+        let decided: Vec<_> = regions
+            .iter()
+            .map(|region| classify(region, &transforms))
+            .collect();
+        assert_eq!(decided, vec![Some(true), Some(true), Some(false)]);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_input(EXAMPLE).is_ok());
+
+        let err = validate_input("0:\n###\n#x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+
+        let err = validate_input("1:\n").unwrap_err();
+        assert_eq!(err.expected, "shape 0");
+
+        let err = validate_input("0:\n\n").unwrap_err();
+        assert_eq!(err.expected, "a shape row");
 
-        assert!(!can_fit_all_shapes(&region, &transforms));
+        let err = validate_input("0:\n##\n\n1:\n#\n\n4x4: 1 2 3").unwrap_err();
+        assert_eq!(err.expected, "at most 2 shape counts");
     }
 }
 
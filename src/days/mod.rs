@@ -110,16 +110,54 @@ pub fn get_all_implementations() -> Vec<DayInfo> {
         register_day!(6, day06::Day06, "day06/input/input.txt"),
         // Day 7 - single implementation
         register_day!(7, day07::Day07, "day07/input/input.txt"),
-        // Day 8 - single implementation
+        // Day 8 - default (all pairs, O(n²) Prim's)
         register_day!(8, day08::Day08, "day08/input/input.txt"),
-        // Day 9 - single implementation
-        register_day!(9, day09::Day09, "day09/input/input.txt"),
-        // Day 10 - single implementation
+        // Day 8 - k-d tree closest pairs and Borůvka
+        register_day!(8, "kd-tree", day08::Day08KdTree, "day08/input/input.txt"),
+        // Day 9 - exact coordinate compression (default)
+        register_day!(
+            9,
+            "compressed",
+            day09::Day09Compressed,
+            "day09/input/input.txt"
+        ),
+        // Day 9 - edge sampling
+        register_day!(9, "sampling", day09::Day09Sampling, "day09/input/input.txt"),
+        // Day 9 - sweep line with sparse sampling
+        register_day!(
+            9,
+            "sweep-line",
+            day09::Day09SweepLine,
+            "day09/input/input.txt"
+        ),
+        // Day 9 - precomputed spatial grid
+        register_day!(
+            9,
+            "spatial-grid",
+            day09::Day09SpatialGrid,
+            "day09/input/input.txt"
+        ),
+        // Day 10 - default (BFS over light states)
         register_day!(10, day10::Day10, "day10/input/input.txt"),
-        // Day 11 - single implementation
+        // Day 10 - GF(2) elimination
+        register_day!(10, "gf2", day10::Day10Gf2, "day10/input/input.txt"),
+        // Day 10 - exact integer search, no LP solver
+        register_day!(10, "exact", day10::Day10Exact, "day10/input/input.txt"),
+        // Day 11 - default (DFS over simple paths)
         register_day!(11, day11::Day11, "day11/input/input.txt"),
-        // Day 12 - single implementation
+        // Day 11 - topological-order dynamic programming
+        register_day!(11, "dag", day11::Day11Dag, "day11/input/input.txt"),
+        // Day 12 - default (backtracking over placements)
         register_day!(12, day12::Day12, "day12/input/input.txt"),
+        // Day 12 - exact cover with dancing links
+        register_day!(12, "dlx", day12::Day12Dlx, "day12/input/input.txt"),
+        // Day 12 - bounds first, then parallel backtracking
+        register_day!(
+            12,
+            "parallel",
+            day12::Day12Parallel,
+            "day12/input/input.txt"
+        ),
     ]
 }
 
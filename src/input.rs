@@ -0,0 +1,469 @@
+//! Puzzle input resolution
+//!
+//! Inputs are looked up in order of precedence:
+//!
+//! 1. `AOC2025_INPUT_DAYNN` environment variable, naming a file
+//! 2. `<inputs-dir>/dayNN.txt` when `--inputs-dir` is given
+//! 3. `$XDG_DATA_HOME/aoc2025/<account>/dayNN.txt` (falling back to
+//!    `~/.local/share` when `XDG_DATA_HOME` is unset)
+//! 4. The input embedded at compile time from `src/days/dayNN/input/input.txt`
+//!
+//! The embedded input belongs to the default account, so it is only used when
+//! no `--account` was requested. Each result carries a stable [`hash_input`]
+//! of its normalised input so it can be matched to the input that produced it.
+//!
+//! Before a day sees its input the runner passes it through [`normalize`], so
+//! files saved on Windows or pasted from a browser parse the same as the
+//! originals.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use crate::stream::LineReader;
+
+/// Account name used when `--account` is not given
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Prefix of the per-day input override environment variables
+const ENV_PREFIX: &str = "AOC2025_INPUT_DAY";
+
+/// Where a day's input was loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// File named by `AOC2025_INPUT_DAYNN`
+    Env(PathBuf),
+    /// File in the `--inputs-dir` directory
+    InputsDir(PathBuf),
+    /// File in the per-account XDG data directory
+    DataHome(PathBuf),
+    /// Input compiled into the binary
+    Embedded,
+}
+
+impl InputSource {
+    /// Whether the input is the one compiled into the binary
+    pub fn is_embedded(&self) -> bool {
+        matches!(self, InputSource::Embedded)
+    }
+
+    /// The file the input is read from, if it is not embedded
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            InputSource::Env(path) | InputSource::InputsDir(path) | InputSource::DataHome(path) => {
+                Some(path)
+            }
+            InputSource::Embedded => None,
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Env(path) => write!(f, "env {}", path.display()),
+            InputSource::InputsDir(path) => write!(f, "inputs-dir {}", path.display()),
+            InputSource::DataHome(path) => write!(f, "data {}", path.display()),
+            InputSource::Embedded => write!(f, "embedded"),
+        }
+    }
+}
+
+/// A day's input together with its origin
+#[derive(Debug, Clone)]
+pub struct ResolvedInput {
+    pub text: Cow<'static, str>,
+    pub source: InputSource,
+}
+
+/// Errors raised while resolving an input
+#[derive(Debug)]
+pub enum InputError {
+    /// A candidate file exists (or was named explicitly) but could not be read
+    Io { path: PathBuf, source: io::Error },
+    /// No input was found for a non-default account
+    Missing { day: usize, account: String },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            InputError::Missing { day, account } => {
+                write!(f, "No input for day {} and account '{}'", day, account)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Resolves puzzle inputs from the environment, disk and embedded files
+#[derive(Debug, Clone, Default)]
+pub struct InputStore {
+    /// Per-day files named by environment variables
+    pub env_files: HashMap<usize, PathBuf>,
+    /// Directory given with `--inputs-dir`
+    pub inputs_dir: Option<PathBuf>,
+    /// Base data directory, normally `$XDG_DATA_HOME/aoc2025`
+    pub data_dir: Option<PathBuf>,
+    /// Account given with `--account`
+    pub account: Option<String>,
+}
+
+impl InputStore {
+    /// Create a store configured from the process environment
+    pub fn from_env(inputs_dir: Option<PathBuf>, account: Option<String>) -> Self {
+        Self {
+            env_files: parse_env_files(env::vars()),
+            inputs_dir,
+            data_dir: default_data_dir(),
+            account,
+        }
+    }
+
+    /// Name of the account inputs are resolved for
+    pub fn account(&self) -> &str {
+        self.account.as_deref().unwrap_or(DEFAULT_ACCOUNT)
+    }
+
+    /// Per-account data directory, e.g. `$XDG_DATA_HOME/aoc2025/<account>`
+    pub fn account_dir(&self) -> Option<PathBuf> {
+        self.data_dir.as_ref().map(|dir| dir.join(self.account()))
+    }
+
+    /// Path of a day's input in the per-account data directory
+    pub fn data_path(&self, day: usize) -> Option<PathBuf> {
+        self.account_dir().map(|dir| dir.join(file_name(day)))
+    }
+
+    /// Resolve the input for a day, falling back to the embedded copy
+    pub fn resolve(&self, day: usize, embedded: &'static str) -> Result<ResolvedInput, InputError> {
+        Self::load(self.locate(day)?, embedded)
+    }
+
+    /// Find where a day's input comes from without reading it
+    ///
+    /// A file named by the environment is returned whether or not it exists,
+    /// so reading it reports the problem.
+    pub fn locate(&self, day: usize) -> Result<InputSource, InputError> {
+        if let Some(path) = self.env_files.get(&day) {
+            return Ok(InputSource::Env(path.clone()));
+        }
+
+        if let Some(dir) = &self.inputs_dir {
+            let path = dir.join(file_name(day));
+            if path.is_file() {
+                return Ok(InputSource::InputsDir(path));
+            }
+        }
+
+        if let Some(path) = self.data_path(day) {
+            if path.is_file() {
+                return Ok(InputSource::DataHome(path));
+            }
+        }
+
+        if self.account.is_some() {
+            return Err(InputError::Missing {
+                day,
+                account: self.account().to_string(),
+            });
+        }
+
+        Ok(InputSource::Embedded)
+    }
+
+    /// Read the input from `source`, which is `embedded` if it is not a file
+    pub fn load(source: InputSource, embedded: &'static str) -> Result<ResolvedInput, InputError> {
+        let text = match source.path() {
+            Some(path) => Cow::Owned(read(path)?),
+            None => Cow::Borrowed(embedded),
+        };
+        Ok(ResolvedInput { text, source })
+    }
+}
+
+/// Hash input text with 64-bit FNV-1a
+///
+/// Stable across platforms and Rust versions, unlike `DefaultHasher`, so the
+/// value can be compared between machines and runs.
+pub fn hash_input(input: &str) -> u64 {
+    fnv1a(FNV_OFFSET_BASIS, input.as_bytes())
+}
+
+/// Hash a streamed input as [`hash_input`] would hash its [`normalize`]d text
+///
+/// Lines are hashed as they are read, holding back blank lines until a later
+/// line shows they are not trailing.
+pub fn hash_stream<R: BufRead>(reader: R) -> io::Result<u64> {
+    let mut lines = LineReader::new(reader);
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut blank = Vec::new();
+    let mut first = true;
+
+    while let Some((_, line)) = lines.next_line()? {
+        if line.trim().is_empty() {
+            blank.push(line.to_string());
+            continue;
+        }
+        for held in blank.drain(..) {
+            if !std::mem::take(&mut first) {
+                hash = fnv1a(hash, b"\n");
+            }
+            hash = fnv1a(hash, held.as_bytes());
+        }
+        if !std::mem::take(&mut first) {
+            hash = fnv1a(hash, b"\n");
+        }
+        hash = fnv1a(hash, line.as_bytes());
+    }
+    Ok(hash)
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continue a 64-bit FNV-1a hash over `bytes`
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// Normalise an input before it is handed to a solver
+///
+/// - A leading UTF-8 byte order mark is removed
+/// - CRLF line endings become LF
+/// - Trailing spaces and tabs are stripped from every line, unless
+///   `preserve_whitespace` is set for days whose layout depends on them
+/// - Trailing blank lines and the final newline are dropped, matching the
+///   shape of the examples the solvers are tested against
+///
+/// Returns the input unchanged (and unallocated) when it is already normal.
+///
+/// # Examples
+///
+/// ```
+/// use aoc2025::input::normalize;
+///
+/// assert_eq!(normalize("\u{feff}1-3 \r\n4\r\n\r\n", false), "1-3\n4");
+/// assert_eq!(normalize("12 \n *\n", true), "12 \n *");
+/// ```
+pub fn normalize(input: &str, preserve_whitespace: bool) -> Cow<'_, str> {
+    let text = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let mut lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if preserve_whitespace {
+                line
+            } else {
+                line.trim_end_matches([' ', '\t'])
+            }
+        })
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let out = lines.join("\n");
+
+    if out == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(out)
+    }
+}
+
+/// Standard file name for a day's input
+pub fn file_name(day: usize) -> String {
+    format!("day{:02}.txt", day)
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Collect `AOC2025_INPUT_DAYNN=<path>` variables into a day → path map
+fn parse_env_files<I>(vars: I) -> HashMap<usize, PathBuf>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    vars.into_iter()
+        .filter_map(|(key, value)| {
+            let day = key.strip_prefix(ENV_PREFIX)?.parse().ok()?;
+            Some((day, PathBuf::from(value)))
+        })
+        .collect()
+}
+
+/// `$XDG_DATA_HOME/aoc2025`, or `~/.local/share/aoc2025` when unset
+fn default_data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("aoc2025"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create an empty scratch directory unique to one test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2025-input-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_embedded_fallback() {
+        let store = InputStore::default();
+        let input = store.resolve(1, "L10\n").unwrap();
+        assert_eq!(input.text, "L10\n");
+        assert_eq!(input.source, InputSource::Embedded);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let dir = scratch_dir("precedence");
+        let inputs_dir = dir.join("inputs");
+        let data_dir = dir.join("data");
+        fs::create_dir_all(&inputs_dir).unwrap();
+        fs::create_dir_all(data_dir.join("alice")).unwrap();
+        fs::write(inputs_dir.join("day03.txt"), "inputs-dir").unwrap();
+        fs::write(data_dir.join("alice/day03.txt"), "alice").unwrap();
+        fs::write(data_dir.join("alice/day04.txt"), "alice day 4").unwrap();
+        fs::write(dir.join("env.txt"), "env").unwrap();
+
+        let mut store = InputStore {
+            env_files: HashMap::new(),
+            inputs_dir: Some(inputs_dir),
+            data_dir: Some(data_dir),
+            account: Some("alice".to_string()),
+        };
+
+        assert_eq!(store.resolve(3, "embedded").unwrap().text, "inputs-dir");
+        assert_eq!(store.resolve(4, "embedded").unwrap().text, "alice day 4");
+
+        store.env_files.insert(3, dir.join("env.txt"));
+        let input = store.resolve(3, "embedded").unwrap();
+        assert_eq!(input.text, "env");
+        assert!(matches!(input.source, InputSource::Env(_)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_account_input() {
+        let store = InputStore {
+            account: Some("bob".to_string()),
+            ..InputStore::default()
+        };
+        let err = store.resolve(5, "embedded").unwrap_err();
+        assert!(matches!(err, InputError::Missing { day: 5, .. }));
+    }
+
+    #[test]
+    fn test_missing_env_file() {
+        let mut store = InputStore::default();
+        store
+            .env_files
+            .insert(2, PathBuf::from("/nonexistent/aoc2025/day02.txt"));
+        assert!(matches!(
+            store.resolve(2, "embedded"),
+            Err(InputError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_env_files() {
+        let vars = vec![
+            ("AOC2025_INPUT_DAY07".to_string(), "/tmp/seven".to_string()),
+            ("AOC2025_INPUT_DAYX".to_string(), "/tmp/bad".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ];
+        let files = parse_env_files(vars);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[&7], PathBuf::from("/tmp/seven"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        // Already normal input is borrowed
+        assert!(matches!(normalize("a\nb", false), Cow::Borrowed(_)));
+
+        assert_eq!(normalize("a\r\nb\r\n", false), "a\nb");
+        assert_eq!(normalize("\u{feff}a\n", false), "a");
+        assert_eq!(normalize("a \t\nb  \n\n \n", false), "a\nb");
+        assert_eq!(normalize("a\n\nb\n", false), "a\n\nb");
+        assert_eq!(normalize("", false), "");
+        assert_eq!(normalize("\n\n", false), "");
+
+        // Interior spacing survives when whitespace is preserved
+        assert_eq!(normalize("1  \r\n+ \r\n  \r\n", true), "1  \n+ ");
+    }
+
+    #[test]
+    fn test_hash_input() {
+        // Reference FNV-1a 64 values
+        assert_eq!(hash_input(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash_input("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash_input("L10\n"), hash_input("L10"));
+    }
+
+    #[test]
+    fn test_hash_stream_matches_normalised() {
+        for text in [
+            "",
+            "\n\n",
+            "a",
+            "\u{feff}1-3 \r\n4\r\n\r\n",
+            "a\n\n \nb\n\n\t\n",
+            "\n\na\n",
+            "a\n\u{c}\nb\n\u{c}\n",
+        ] {
+            assert_eq!(
+                hash_stream(text.as_bytes()).unwrap(),
+                hash_input(&normalize(text, false)),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_locate_then_load() {
+        let dir = scratch_dir("locate");
+        fs::write(dir.join("day06.txt"), "from dir").unwrap();
+        let store = InputStore {
+            inputs_dir: Some(dir.clone()),
+            ..InputStore::default()
+        };
+
+        let source = store.locate(6).unwrap();
+        assert_eq!(source.path(), Some(dir.join("day06.txt").as_path()));
+        let input = InputStore::load(source, "embedded").unwrap();
+        assert_eq!(input.text, "from dir");
+
+        let source = store.locate(7).unwrap();
+        assert_eq!(source, InputSource::Embedded);
+        assert_eq!(source.path(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
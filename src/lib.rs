@@ -4,9 +4,14 @@
 //! Each day's solution is organized in its own module under `days`,
 //! with common utilities in the `common` module.
 
-#![feature(test)]
+// Only the release-mode benches from `define_day_benches!` use the `test`
+// crate; enabling it anywhere else trips the nightly `unused_features` lint
+#![cfg_attr(all(test, not(debug_assertions)), feature(test))]
 
 pub mod common;
 pub mod days;
+pub mod input;
+pub mod remote;
 pub mod runner;
+pub mod stream;
 pub mod table;
@@ -5,54 +5,638 @@
 //! Usage:
 //!   aoc2025              # Run all implemented days
 //!   aoc2025 --day N      # Run a specific day
+//!   aoc2025 --param K=V  # Override a day parameter
+//!   aoc2025 --explain    # Also show why each answer is what it is
+//!   aoc2025 --stats      # Also show the work behind each answer
+//!   aoc2025 --account A  # Run against another account's inputs
+//!   aoc2025 fetch N      # Download day N's input and puzzle text
+//!   aoc2025 submit N P   # Submit the answer to day N part P
+//!   aoc2025 check-input N [PATH]  # Validate day N's input format
+//!   aoc2025 viz N        # Draw day N's solve in the terminal or as images
+//!   aoc2025 gen N        # Print a random input for day N
 //!   aoc2025 --help       # Show help message
 
-use aoc2025::{days, runner, table};
+use aoc2025::common::rng::Rng;
+use aoc2025::common::viz::{self, ImageFormat, Recorder};
+use aoc2025::input::{InputError, InputSource, InputStore};
+use aoc2025::runner::SolveContext;
+use aoc2025::stream::InputFile;
+use aoc2025::{days, remote, runner, table};
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::process;
 
+/// Subcommand selected on the command line
+#[derive(Debug, Default, PartialEq)]
+enum Command {
+    /// Run solutions and print the results table (the default)
+    #[default]
+    Run,
+    /// Download a day's input and puzzle text
+    Fetch { day: usize },
+    /// Submit the computed answer for one part of a day
+    Submit { day: usize, part: u8 },
+    /// Validate the format of a day's input, optionally read from a file
+    CheckInput { day: usize, path: Option<PathBuf> },
+    /// Draw the steps of a day's solve
+    Viz { day: usize },
+    /// Print a random input for a day
+    Gen { day: usize },
+}
+
+/// Where `viz` sends the frames it draws
+#[derive(Debug, Default, PartialEq)]
+enum VizOutput {
+    /// Print every frame with ANSI colours (the default)
+    #[default]
+    Ansi,
+    /// Write numbered PPM images into a directory
+    Ppm(PathBuf),
+    /// Write numbered PNG images into a directory
+    Png(PathBuf),
+    /// Write one animated GIF
+    Gif(PathBuf),
+    /// Write one SVG drawing of the answer instead of frames
+    Svg(PathBuf),
+    /// Print the puzzle's graph as Graphviz DOT instead of frames
+    Dot,
+}
+
+/// Pixels per cell in images written by `viz`, unless `--scale` is given
+const DEFAULT_SCALE: usize = 4;
+
+/// Most frames `viz` draws, unless `--max-frames` is given
+const DEFAULT_MAX_FRAMES: usize = 1000;
+
+/// Parsed command line arguments
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    command: Command,
+    /// `None` to run all days, `Some(n)` to run day n
+    day_filter: Option<usize>,
+    /// `true` to run all implementations, `false` for default only
+    all_impls: bool,
+    /// Parameter overrides from `--param key=value`
+    params: Vec<(String, String)>,
+    /// Directory of `dayNN.txt` inputs from `--inputs-dir`
+    inputs_dir: Option<PathBuf>,
+    /// Account whose inputs to use from `--account`
+    account: Option<String>,
+    /// Server to fetch from and submit to, from `--base-url`
+    base_url: Option<String>,
+    /// Re-download cached files with `fetch --force`
+    force: bool,
+    /// Show each day's witnesses after the results, from `--explain`
+    explain: bool,
+    /// Show each day's search counters after the results, from `--stats`
+    stats: bool,
+    /// Where `viz` sends its frames, from `--ansi`, `--ppm`, `--png`, `--gif`,
+    /// `--svg` or `--dot`
+    viz_output: VizOutput,
+    /// Draw what the solver checked in `viz --svg`, from `--checked`
+    checked: bool,
+    /// Pixels per cell in `viz` images, from `--scale`
+    scale: Option<usize>,
+    /// Most frames `viz` draws, from `--max-frames`
+    max_frames: Option<usize>,
+    /// Size of the input `gen` prints, from `--size`
+    size: Option<usize>,
+    /// Seed for `gen`, from `--seed`
+    seed: Option<u64>,
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     // Parse command line arguments
-    let (day_filter, all_impls) = parse_args(&args);
+    let args = parse_args(&args);
 
+    match args.command {
+        Command::Run => run(&args),
+        Command::Fetch { day } => fetch(&args, day),
+        Command::Submit { day, part } => submit(&args, day, part),
+        Command::CheckInput { day, ref path } => check_input(&args, day, path.as_deref()),
+        Command::Viz { day } => visualize(&args, day),
+        Command::Gen { day } => generate(&args, day),
+    }
+}
+
+/// Run the selected days and print the results table
+fn run(args: &Args) {
     // Get days to run
-    let all_days = if all_impls {
+    let all_days = if args.all_impls {
         days::get_all_implementations()
     } else {
         days::get_days()
     };
-    let days_to_run: Vec<_> = match day_filter {
+    let days_to_run: Vec<_> = match args.day_filter {
         Some(n) => all_days.into_iter().filter(|d| d.number == n).collect(),
         None => all_days,
     };
 
     // Check if any days are available
     if days_to_run.is_empty() {
-        if let Some(n) = day_filter {
+        if let Some(n) = args.day_filter {
             eprintln!("Error: Day {} is not implemented yet.", n);
             process::exit(1);
         }
     }
 
-    // Run the selected days
-    let results = runner::run_all(&days_to_run);
+    check_params(&days_to_run, &args.params);
+
+    // Find each day's input before timing anything
+    let store = InputStore::from_env(args.inputs_dir.clone(), args.account.clone());
+    let sources: Vec<_> = days_to_run
+        .iter()
+        .map(|d| {
+            store.locate(d.number).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            })
+        })
+        .collect();
+
+    // Run the selected days; one whose input can't be solved is reported and
+    // left out of the table while the rest still run
+    let mut solved = Vec::new();
+    for (day, source) in days_to_run.iter().zip(&sources) {
+        match solve_day(day, source, &args.params) {
+            Ok(result) => solved.push((day, source, result)),
+            Err(report) => eprint!("{}", report),
+        }
+    }
+    if solved.is_empty() && !days_to_run.is_empty() {
+        process::exit(1);
+    }
+    let results: Vec<_> = solved.iter().map(|(_, _, result)| result.clone()).collect();
 
     // Display results
     println!("\nAdvent of Code 2025 - Results\n");
     println!("{}", table::format_results(&results));
+
+    if args.explain {
+        let days: Vec<_> = solved
+            .iter()
+            .map(|&(day, source, _)| (day, source))
+            .collect();
+        print_explanations(&days, &args.params);
+    }
+
+    if args.stats {
+        let days: Vec<_> = solved
+            .iter()
+            .map(|&(day, source, _)| (day, source))
+            .collect();
+        print_stats(&days, &args.params);
+    }
+
+    // Show where inputs came from when any were not the embedded copies
+    if sources.iter().any(|source| !source.is_embedded()) {
+        println!("Inputs (account '{}'):", store.account());
+        for (day, source, result) in &solved {
+            let streamed = source.path().is_some() && day.solver.streaming().is_some();
+            println!(
+                "  Day {:02}  {:016x}  {}{}",
+                result.day_number,
+                result.input_hash,
+                source,
+                if streamed { " (streamed)" } else { "" }
+            );
+        }
+    }
+
+    if solved.len() < days_to_run.len() {
+        process::exit(1);
+    }
 }
 
-/// Parse command line arguments
+/// Solve one day, streaming its input if it is a file and the day can take a
+/// stream
 ///
-/// Returns:
-/// - A tuple of (day_filter, all_impls)
-/// - day_filter: `None` to run all days, `Some(n)` to run day n
-/// - all_impls: `true` to run all implementations, `false` for default only
-fn parse_args(args: &[String]) -> (Option<usize>, bool) {
-    let mut day_filter = None;
-    let mut all_impls = false;
+/// Other inputs are checked with `Day::validate_with` first. Returns the
+/// report to print if the input can't be read, doesn't suit the day or has
+/// no answer.
+fn solve_day(
+    day: &runner::DayInfo,
+    source: &InputSource,
+    params: &[(String, String)],
+) -> Result<runner::DayResult, String> {
+    let error = |err: InputError| match err {
+        // The file was read, but its contents don't suit the day
+        InputError::Io { path, source } if source.kind() == io::ErrorKind::InvalidData => {
+            format!("Error: {}: {}\n", path.display(), source)
+        }
+        err => format!("Error: {}\n", err),
+    };
+    if let (Some(path), Some(solver)) = (source.path(), day.solver.streaming()) {
+        let file = open_input(path).map_err(|source| {
+            error(InputError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        })?;
+        return runner::run_day_streaming(day, solver, &file, params).map_err(error);
+    }
+
+    let input = InputStore::load(source.clone(), day.input).map_err(error)?;
+    runner::check_day_input(day, &input.text, params)
+        .map_err(|diagnostic| diagnostic.render(&input_origin(source, day.number)))?;
+    runner::run_day_with_input(day, &input.text, params)
+        .map_err(|err| format!("Error: {}: {}\n", day_label(day), err))
+}
+
+/// Open an input file for streaming, memory-mapped with the `mmap` feature
+fn open_input(path: &Path) -> io::Result<InputFile> {
+    // SAFETY: inputs are puzzle files that nothing else writes while a run
+    // reads them; one truncated mid-run would crash the run with `SIGBUS`
+    #[cfg(feature = "mmap")]
+    return unsafe { InputFile::map(path) };
+    #[cfg(not(feature = "mmap"))]
+    InputFile::open(path)
+}
+
+/// Print the witnesses behind each day's answers
+fn print_explanations(days: &[(&runner::DayInfo, &InputSource)], overrides: &[(String, String)]) {
+    println!("Explanations:");
+    for &(day, source) in days {
+        let name = day_label(day);
+        let input = match InputStore::load(source.clone(), day.input) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("  {}  {}", name, err);
+                continue;
+            }
+        };
+        match runner::explain_day(day, &input.text, overrides) {
+            Some(witnesses) => {
+                for witness in witnesses {
+                    println!("  {}  {}", name, witness);
+                }
+            }
+            None => println!("  {}  no explanation available", name),
+        }
+    }
+    println!();
+}
+
+/// Print the counters behind each day's answers, skipping days that keep none
+fn print_stats(days: &[(&runner::DayInfo, &InputSource)], overrides: &[(String, String)]) {
+    println!("Statistics:");
+    for &(day, source) in days {
+        let name = day_label(day);
+        let input = match InputStore::load(source.clone(), day.input) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("  {}  {}", name, err);
+                continue;
+            }
+        };
+        if let Some(stats) = runner::day_stats(day, &input.text, overrides) {
+            let stats: Vec<String> = stats
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            println!("  {}  {}", name, stats.join(" "));
+        }
+    }
+    println!();
+}
+
+/// A day's name with its implementation, e.g. `Day 12 (dlx)`
+fn day_label(day: &runner::DayInfo) -> String {
+    match &day.impl_name {
+        Some(name) => format!("Day {:02} ({})", day.number, name),
+        None => format!("Day {:02}", day.number),
+    }
+}
+
+/// Download a day's input and puzzle text into the account directory
+fn fetch(args: &Args, day: usize) {
+    let store = InputStore::from_env(args.inputs_dir.clone(), args.account.clone());
+    let account_dir = account_dir(&store);
+    let client = client(args, &account_dir);
+
+    match remote::fetch(&client, &account_dir, day, args.force) {
+        Ok(outcome) => {
+            for (path, downloaded) in [
+                (&outcome.input_path, outcome.input_downloaded),
+                (&outcome.puzzle_path, outcome.puzzle_downloaded),
+            ] {
+                let status = if downloaded { "Downloaded" } else { "Cached" };
+                println!("{:>10} {}", status, path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Compute one part's answer and submit it
+fn submit(args: &Args, day: usize, part: u8) {
+    let Some(info) = days::get_day(day) else {
+        eprintln!("Error: Day {} is not implemented yet.", day);
+        process::exit(1);
+    };
+
+    let store = InputStore::from_env(args.inputs_dir.clone(), args.account.clone());
+    let input = store.resolve(day, info.input).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+
+    check_params(std::slice::from_ref(&info), &args.params);
+    if let Err(diagnostic) = runner::check_day_input(&info, &input.text, &args.params) {
+        eprint!("{}", diagnostic.render(&input_origin(&input.source, day)));
+        process::exit(1);
+    }
+
+    let ctx = SolveContext::with_overrides(info.solver.params(), &args.params);
+    let text = runner::prepare_input(info.solver.as_ref(), &input.text);
+    let answer = match part {
+        1 => info.solver.try_part1(&text, &ctx),
+        _ => info.solver.try_part2(&text, &ctx),
+    };
+    let answer = answer.unwrap_or_else(|err| {
+        eprintln!("Error: Day {:02} part {}: {}", day, part, err);
+        process::exit(1);
+    });
+    println!("Day {:02} part {} answer: {}", day, part, answer);
+
+    let account_dir = account_dir(&store);
+    let client = client(args, &account_dir);
+    match remote::submit(&client, &account_dir, day, part, &answer) {
+        Ok(Ok(verdict)) => println!("{}", verdict),
+        Ok(Err(reason)) => println!("Not submitted: {}", reason),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Validate a day's input and print a diagnostic for the first problem
+fn check_input(args: &Args, day: usize, path: Option<&Path>) {
+    let Some(info) = days::get_day(day) else {
+        eprintln!("Error: Day {} is not implemented yet.", day);
+        process::exit(1);
+    };
+
+    let (text, origin) = match path {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => (text, path.display().to_string()),
+            Err(err) => {
+                eprintln!("Error: Failed to read {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        },
+        None => {
+            let store = InputStore::from_env(args.inputs_dir.clone(), args.account.clone());
+            let input = store.resolve(day, info.input).unwrap_or_else(|err| {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            });
+            let origin = input_origin(&input.source, day);
+            (input.text.into_owned(), origin)
+        }
+    };
+
+    check_params(std::slice::from_ref(&info), &args.params);
+    match runner::check_day_input(&info, &text, &args.params) {
+        Ok(()) => println!("Day {:02}: {} is valid", day, origin),
+        Err(diagnostic) => {
+            eprint!("{}", diagnostic.render(&origin));
+            process::exit(1);
+        }
+    }
+}
+
+/// Where an input came from, as a path for diagnostics
+fn input_origin(source: &InputSource, day: usize) -> String {
+    match source {
+        InputSource::Env(path) | InputSource::InputsDir(path) | InputSource::DataHome(path) => {
+            path.display().to_string()
+        }
+        InputSource::Embedded => format!("src/days/day{:02}/input/input.txt", day),
+    }
+}
+
+/// Draw a day's solve as frames and print or save them
+fn visualize(args: &Args, day: usize) {
+    let Some(info) = days::get_day(day) else {
+        eprintln!("Error: Day {} is not implemented yet.", day);
+        process::exit(1);
+    };
+
+    let store = InputStore::from_env(args.inputs_dir.clone(), args.account.clone());
+    let input = store.resolve(day, info.input).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+    let text = runner::prepare_input(info.solver.as_ref(), &input.text);
+
+    if let VizOutput::Svg(path) = &args.viz_output {
+        let Some(svg) = info.solver.draw_svg(&text, args.checked) else {
+            eprintln!("Error: Day {} has nothing to draw as SVG", day);
+            process::exit(1);
+        };
+        if let Err(err) = fs::write(path, svg) {
+            eprintln!("Error: could not write {}: {}", path.display(), err);
+            process::exit(1);
+        }
+        println!("Wrote {}", path.display());
+        return;
+    }
+
+    if args.viz_output == VizOutput::Dot {
+        check_params(std::slice::from_ref(&info), &args.params);
+        let ctx = SolveContext::with_overrides(info.solver.params(), &args.params);
+        let Some(dot) = info.solver.draw_dot(&text, &ctx) else {
+            eprintln!("Error: Day {} has no graph to draw", day);
+            process::exit(1);
+        };
+        print!("{}", dot);
+        return;
+    }
+
+    let mut recorder = Recorder::with_limit(args.max_frames.unwrap_or(DEFAULT_MAX_FRAMES));
+    if !info.solver.visualize(&text, &mut recorder) {
+        eprintln!("Error: Day {} has nothing to draw", day);
+        process::exit(1);
+    }
+
+    let scale = args.scale.unwrap_or(DEFAULT_SCALE);
+    let scaled = || -> Vec<_> {
+        recorder
+            .frames
+            .iter()
+            .map(|frame| viz::upscale(frame, scale))
+            .collect()
+    };
+    let written = match &args.viz_output {
+        VizOutput::Ansi | VizOutput::Svg(_) | VizOutput::Dot => {
+            for frame in &recorder.frames {
+                println!("{}", viz::ansi(frame));
+            }
+            return;
+        }
+        VizOutput::Ppm(dir) => viz::save_frames(dir, &scaled(), ImageFormat::Ppm).map(|_| dir),
+        VizOutput::Png(dir) => viz::save_frames(dir, &scaled(), ImageFormat::Png).map(|_| dir),
+        VizOutput::Gif(path) => File::create(path)
+            .and_then(|file| {
+                let mut out = BufWriter::new(file);
+                viz::write_gif(&mut out, &scaled(), 10)?;
+                out.flush()
+            })
+            .map(|_| path),
+    };
+    match written {
+        Ok(path) => println!(
+            "Wrote {} frames to {}",
+            recorder.frames.len(),
+            path.display()
+        ),
+        Err(err) => {
+            eprintln!("Error: Failed to write {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Print a random input for a day, the same for the same size and seed
+fn generate(args: &Args, day: usize) {
+    let Some(info) = days::get_day(day) else {
+        eprintln!("Error: Day {} is not implemented yet.", day);
+        process::exit(1);
+    };
+    let Some(generator) = info.solver.generator() else {
+        eprintln!("Error: Day {} has no input generator", day);
+        process::exit(1);
+    };
+
+    let size = args.size.unwrap_or_else(|| generator.default_size());
+    let mut rng = Rng::new(args.seed.unwrap_or(0));
+    let input = generator.generate(size, &mut rng);
+    let mut out = io::stdout().lock();
+    if let Err(err) = out.write_all(input.as_bytes()).and_then(|_| out.flush()) {
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("Error: Failed to write input: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Per-account data directory, exiting if it cannot be determined
+fn account_dir(store: &InputStore) -> PathBuf {
+    store.account_dir().unwrap_or_else(|| {
+        eprintln!("Error: Cannot determine data directory (set XDG_DATA_HOME or HOME)");
+        process::exit(1);
+    })
+}
+
+/// Build a client for the configured server, exiting if no session is set
+fn client(args: &Args, account_dir: &Path) -> remote::Client {
+    let session = remote::load_session(account_dir).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    });
+    let base_url = args
+        .base_url
+        .clone()
+        .unwrap_or_else(remote::base_url_from_env);
+    remote::Client::new(&base_url, &session)
+}
+
+/// Parse a day number argument, exiting if it is out of range
+fn parse_day(value: &str) -> usize {
+    match value.parse::<usize>() {
+        Ok(n) if (1..=25).contains(&n) => n,
+        Ok(_) => {
+            eprintln!("Error: Day must be between 1 and 25");
+            process::exit(1);
+        }
+        Err(_) => {
+            eprintln!("Error: Invalid day number: {}", value);
+            process::exit(1);
+        }
+    }
+}
+
+/// Interpret positional arguments as a subcommand
+fn parse_command(positionals: &[&str]) -> Command {
+    match positionals {
+        [] | ["run"] => Command::Run,
+        ["fetch", day] => Command::Fetch {
+            day: parse_day(day),
+        },
+        ["submit", day, part] => {
+            let part = match *part {
+                "1" => 1,
+                "2" => 2,
+                other => {
+                    eprintln!("Error: Part must be 1 or 2, got: {}", other);
+                    process::exit(1);
+                }
+            };
+            Command::Submit {
+                day: parse_day(day),
+                part,
+            }
+        }
+        ["check-input", day] => Command::CheckInput {
+            day: parse_day(day),
+            path: None,
+        },
+        ["check-input", day, path] => Command::CheckInput {
+            day: parse_day(day),
+            path: Some(PathBuf::from(path)),
+        },
+        ["viz", day] => Command::Viz {
+            day: parse_day(day),
+        },
+        ["gen", day] => Command::Gen {
+            day: parse_day(day),
+        },
+        ["gen", ..] => {
+            eprintln!("Error: gen requires a day number");
+            print_usage();
+            process::exit(1);
+        }
+        ["viz", ..] => {
+            eprintln!("Error: viz requires a day number");
+            print_usage();
+            process::exit(1);
+        }
+        ["check-input", ..] => {
+            eprintln!("Error: check-input requires a day number and an optional path");
+            print_usage();
+            process::exit(1);
+        }
+        ["fetch", ..] => {
+            eprintln!("Error: fetch requires a day number");
+            print_usage();
+            process::exit(1);
+        }
+        ["submit", ..] => {
+            eprintln!("Error: submit requires a day number and a part");
+            print_usage();
+            process::exit(1);
+        }
+        [arg, ..] => {
+            eprintln!("Error: Unknown argument: {}", arg);
+            print_usage();
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse command line arguments
+fn parse_args(args: &[String]) -> Args {
+    let mut parsed = Args::default();
+    let mut positionals = Vec::new();
     let mut i = 1; // Skip program name
 
     while i < args.len() {
@@ -67,38 +651,179 @@ fn parse_args(args: &[String]) -> (Option<usize>, bool) {
                     print_usage();
                     process::exit(1);
                 }
-                match args[i + 1].parse::<usize>() {
-                    Ok(n) if (1..=25).contains(&n) => {
-                        day_filter = Some(n);
+                parsed.day_filter = Some(parse_day(&args[i + 1]));
+                i += 2;
+            }
+            "--all-impls" | "-a" => {
+                parsed.all_impls = true;
+                i += 1;
+            }
+            "--inputs-dir" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --inputs-dir requires a directory");
+                    print_usage();
+                    process::exit(1);
+                }
+                parsed.inputs_dir = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--account" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --account requires an account name");
+                    print_usage();
+                    process::exit(1);
+                }
+                parsed.account = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--param" | "-p" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --param requires a key=value pair");
+                    print_usage();
+                    process::exit(1);
+                }
+                match args[i + 1].split_once('=') {
+                    Some((key, value)) if !key.is_empty() => {
+                        parsed.params.push((key.to_string(), value.to_string()));
                         i += 2;
                     }
-                    Ok(_) => {
-                        eprintln!("Error: Day must be between 1 and 25");
-                        process::exit(1);
-                    }
-                    Err(_) => {
-                        eprintln!("Error: Invalid day number: {}", args[i + 1]);
+                    _ => {
+                        eprintln!("Error: Invalid parameter: {}", args[i + 1]);
+                        eprintln!("Parameters must be given as key=value");
                         process::exit(1);
                     }
                 }
             }
-            "--all-impls" | "-a" => {
-                all_impls = true;
+            "--base-url" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --base-url requires a URL");
+                    print_usage();
+                    process::exit(1);
+                }
+                parsed.base_url = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--force" => {
+                parsed.force = true;
                 i += 1;
             }
-            arg => {
+            "--explain" => {
+                parsed.explain = true;
+                i += 1;
+            }
+            "--stats" => {
+                parsed.stats = true;
+                i += 1;
+            }
+            "--ansi" => {
+                parsed.viz_output = VizOutput::Ansi;
+                i += 1;
+            }
+            "--dot" => {
+                parsed.viz_output = VizOutput::Dot;
+                i += 1;
+            }
+            "--checked" => {
+                parsed.checked = true;
+                i += 1;
+            }
+            "--ppm" | "--png" | "--gif" | "--svg" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: {} requires a path", args[i]);
+                    print_usage();
+                    process::exit(1);
+                }
+                let path = PathBuf::from(&args[i + 1]);
+                parsed.viz_output = match args[i].as_str() {
+                    "--ppm" => VizOutput::Ppm(path),
+                    "--png" => VizOutput::Png(path),
+                    "--gif" => VizOutput::Gif(path),
+                    _ => VizOutput::Svg(path),
+                };
+                i += 2;
+            }
+            "--scale" | "--max-frames" | "--size" => {
+                let value = args.get(i + 1).and_then(|v| v.parse::<usize>().ok());
+                let Some(value) = value.filter(|&v| v > 0) else {
+                    eprintln!("Error: {} requires a positive number", args[i]);
+                    print_usage();
+                    process::exit(1);
+                };
+                match args[i].as_str() {
+                    "--scale" => parsed.scale = Some(value),
+                    "--max-frames" => parsed.max_frames = Some(value),
+                    _ => parsed.size = Some(value),
+                }
+                i += 2;
+            }
+            "--seed" => {
+                let Some(seed) = args.get(i + 1).and_then(|v| v.parse::<u64>().ok()) else {
+                    eprintln!("Error: --seed requires a number");
+                    print_usage();
+                    process::exit(1);
+                };
+                parsed.seed = Some(seed);
+                i += 2;
+            }
+            arg if arg.starts_with('-') => {
                 eprintln!("Error: Unknown argument: {}", arg);
                 print_usage();
                 process::exit(1);
             }
+            arg => {
+                positionals.push(arg);
+                i += 1;
+            }
         }
     }
 
-    (day_filter, all_impls)
+    parsed.command = parse_command(&positionals);
+    parsed
+}
+
+/// Exit unless every override is declared by one of `days`, with a value
+/// every day declaring it accepts
+fn check_params(days: &[runner::DayInfo], overrides: &[(String, String)]) {
+    for (key, value) in overrides {
+        let declared: Vec<_> = days
+            .iter()
+            .flat_map(|day| day.solver.params())
+            .filter(|param| param.name == key)
+            .collect();
+        if declared.is_empty() {
+            eprintln!("Error: Unknown parameter: {}", key);
+            print_available_params(days);
+            process::exit(1);
+        }
+        for param in declared {
+            if let Err(expected) = param.check(value) {
+                eprintln!(
+                    "Error: Invalid value for parameter {}: {} ({})",
+                    key, value, expected
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Print the parameters declared by each of the given days
+fn print_available_params(days: &[runner::DayInfo]) {
+    eprintln!("Available parameters:");
+    for day in days {
+        for param in day.solver.params() {
+            eprintln!(
+                "    Day {:02}  {}={}  {}",
+                day.number, param.name, param.default, param.description
+            );
+        }
+    }
 }
 
 fn print_usage() {
-    eprintln!("Usage: aoc2025 [OPTIONS]");
+    eprintln!(
+        "Usage: aoc2025 [run | fetch <DAY> | submit <DAY> <PART> | check-input <DAY> [PATH] | viz <DAY> | gen <DAY>] [OPTIONS]"
+    );
     eprintln!("Try 'aoc2025 --help' for more information.");
 }
 
@@ -106,19 +831,97 @@ fn print_help() {
     println!("Advent of Code 2025 - Solution Runner");
     println!();
     println!("USAGE:");
-    println!("    aoc2025 [OPTIONS]");
+    println!("    aoc2025 [run] [OPTIONS]");
+    println!("    aoc2025 fetch <DAY> [--force] [OPTIONS]");
+    println!("    aoc2025 submit <DAY> <PART> [OPTIONS]");
+    println!("    aoc2025 check-input <DAY> [PATH] [OPTIONS]");
+    println!("    aoc2025 viz <DAY> [OPTIONS]");
+    println!("    aoc2025 gen <DAY> [--size <N>] [--seed <S>]");
+    println!();
+    println!("COMMANDS:");
+    println!("    run               Run solutions and print timings (default)");
+    println!("    fetch <DAY>       Download the input and puzzle text for DAY");
+    println!("                      into $XDG_DATA_HOME/aoc2025/<account>/");
+    println!("    submit <DAY> <PART>");
+    println!("                      Compute and submit the answer for one part,");
+    println!("                      skipping answers already known to be wrong");
+    println!("    check-input <DAY> [PATH]");
+    println!("                      Check the input format (from PATH, or the");
+    println!("                      usual input sources) and report the first error");
+    println!("    viz <DAY>         Draw the steps of the solve (days 4, 7 and 12),");
+    println!("                      the answer as SVG (day 9) or the graph as");
+    println!("                      DOT (days 8 and 11)");
+    println!("    gen <DAY>         Print a random input of the puzzle's format");
+    println!("                      (days 1, 2, 4, 8, 9, 11 and 12)");
     println!();
     println!("OPTIONS:");
     println!("    -d, --day <N>     Run only day N (1-25)");
     println!("    -a, --all-impls   Run all implementations for each day");
+    println!("    -p, --param <K=V> Override a day parameter (repeatable)");
+    println!("    --inputs-dir <DIR>");
+    println!("                      Read inputs from DIR/dayNN.txt");
+    println!("    --account <NAME>  Read inputs for account NAME from");
+    println!("                      $XDG_DATA_HOME/aoc2025/NAME/dayNN.txt");
+    println!(
+        "    --base-url <URL>  Server for fetch/submit (default {})",
+        remote::DEFAULT_BASE_URL
+    );
+    println!("    --force           Re-download files that are already cached");
+    println!("    --explain         Show the choices behind each answer (digits,");
+    println!("                      corners, button presses, placements)");
+    println!("    --stats           Show the work behind each answer (search nodes");
+    println!("                      and backtracks), to compare implementations");
+    println!("    --ansi            viz: print frames in the terminal (default)");
+    println!("    --ppm <DIR>       viz: write frames as DIR/frameNNNN.ppm");
+    println!("    --png <DIR>       viz: write frames as DIR/frameNNNN.png");
+    println!("    --gif <FILE>      viz: write an animated GIF");
+    println!("    --svg <FILE>      viz: write an SVG drawing of the answer");
+    println!("    --checked         viz --svg: also draw what the solver checked");
+    println!("    --dot             viz: print the graph as Graphviz DOT");
+    println!(
+        "    --scale <N>       viz: pixels per cell in images (default {})",
+        DEFAULT_SCALE
+    );
+    println!(
+        "    --max-frames <N>  viz: stop drawing after N frames (default {})",
+        DEFAULT_MAX_FRAMES
+    );
+    println!("    --size <N>        gen: size of the input (lines, ranges, grid side");
+    println!("                      or points, depending on the day)");
+    println!("    --seed <S>        gen: random seed (default 0)");
     println!("    -h, --help        Print help information");
     println!();
+    println!("ENVIRONMENT:");
+    println!("    AOC2025_INPUT_DAYNN   Path to the input file for day NN");
+    println!("                          (takes precedence over other sources)");
+    println!("    AOC2025_SESSION       Session cookie (otherwise read from");
+    println!("                          $XDG_DATA_HOME/aoc2025/<account>/session)");
+    println!("    AOC2025_BASE_URL      Server for fetch/submit");
+    println!();
     println!("EXAMPLES:");
     println!("    aoc2025               Run all implemented days");
     println!("    aoc2025 --day 1       Run only day 1");
     println!("    aoc2025 -d 5          Run only day 5");
     println!("    aoc2025 --all-impls   Run all implementations (e.g., math and string)");
     println!("    aoc2025 -d 2 -a       Run all implementations for day 2");
+    println!("    aoc2025 -d 8 -p pairs=10");
+    println!("                          Run day 8 connecting only 10 pairs");
+    println!("    aoc2025 -d 3 --explain");
+    println!("                          Show the batteries chosen in every bank");
+    println!("    aoc2025 -d 12 -a --stats");
+    println!("                          Compare the searches of day 12's solvers");
+    println!("    aoc2025 fetch 5       Download day 5");
+    println!("    aoc2025 submit 5 1    Submit day 5 part 1");
+    println!("    aoc2025 check-input 10 my-input.txt");
+    println!("                          Check a day 10 input file for format errors");
+    println!("    aoc2025 viz 4 --gif rolls.gif");
+    println!("                          Animate day 4's roll removal");
+    println!("    aoc2025 viz 9 --svg tiles.svg --checked");
+    println!("                          Draw day 9's rectangles and the grid probes");
+    println!("    aoc2025 viz 11 --dot | dot -Tsvg > reactor.svg");
+    println!("                          Draw day 11's devices with Graphviz");
+    println!("    aoc2025 gen 9 --size 2000 --seed 7 > tiles.txt");
+    println!("                          Generate a day 9 polygon of 2000 red tiles");
 }
 
 #[cfg(test)]
@@ -128,25 +931,29 @@ mod tests {
     #[test]
     fn test_parse_args_no_filter() {
         let args = vec!["aoc2025".to_string()];
-        assert_eq!(parse_args(&args), (None, false));
+        assert_eq!(parse_args(&args), Args::default());
     }
 
     #[test]
     fn test_parse_args_with_day() {
         let args = vec!["aoc2025".to_string(), "--day".to_string(), "5".to_string()];
-        assert_eq!(parse_args(&args), (Some(5), false));
+        let parsed = parse_args(&args);
+        assert_eq!((parsed.day_filter, parsed.all_impls), (Some(5), false));
 
         let args = vec!["aoc2025".to_string(), "-d".to_string(), "1".to_string()];
-        assert_eq!(parse_args(&args), (Some(1), false));
+        let parsed = parse_args(&args);
+        assert_eq!((parsed.day_filter, parsed.all_impls), (Some(1), false));
     }
 
     #[test]
     fn test_parse_args_with_all_impls() {
         let args = vec!["aoc2025".to_string(), "--all-impls".to_string()];
-        assert_eq!(parse_args(&args), (None, true));
+        let parsed = parse_args(&args);
+        assert_eq!((parsed.day_filter, parsed.all_impls), (None, true));
 
         let args = vec!["aoc2025".to_string(), "-a".to_string()];
-        assert_eq!(parse_args(&args), (None, true));
+        let parsed = parse_args(&args);
+        assert_eq!((parsed.day_filter, parsed.all_impls), (None, true));
     }
 
     #[test]
@@ -157,6 +964,152 @@ mod tests {
             "2".to_string(),
             "--all-impls".to_string(),
         ];
-        assert_eq!(parse_args(&args), (Some(2), true));
+        let parsed = parse_args(&args);
+        assert_eq!((parsed.day_filter, parsed.all_impls), (Some(2), true));
+    }
+
+    #[test]
+    fn test_parse_args_with_params() {
+        let args = vec![
+            "aoc2025".to_string(),
+            "--param".to_string(),
+            "pairs=10".to_string(),
+            "-p".to_string(),
+            "waypoints=dac,fft".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(
+            parsed.params,
+            vec![
+                ("pairs".to_string(), "10".to_string()),
+                ("waypoints".to_string(), "dac,fft".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_with_inputs() {
+        let args = vec![
+            "aoc2025".to_string(),
+            "--inputs-dir".to_string(),
+            "/tmp/inputs".to_string(),
+            "--account".to_string(),
+            "alice".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.inputs_dir, Some(PathBuf::from("/tmp/inputs")));
+        assert_eq!(parsed.account, Some("alice".to_string()));
+    }
+
+    #[test]
+    fn test_parse_args_commands() {
+        let args = vec!["aoc2025".to_string(), "run".to_string()];
+        assert_eq!(parse_args(&args).command, Command::Run);
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "run".to_string(),
+            "--explain".to_string(),
+            "--stats".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.command, Command::Run);
+        assert!(parsed.explain);
+        assert!(parsed.stats);
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "fetch".to_string(),
+            "7".to_string(),
+            "--force".to_string(),
+            "--base-url".to_string(),
+            "http://127.0.0.1:8080".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.command, Command::Fetch { day: 7 });
+        assert!(parsed.force);
+        assert_eq!(parsed.base_url.as_deref(), Some("http://127.0.0.1:8080"));
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "submit".to_string(),
+            "3".to_string(),
+            "2".to_string(),
+        ];
+        assert_eq!(
+            parse_args(&args).command,
+            Command::Submit { day: 3, part: 2 }
+        );
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "viz".to_string(),
+            "7".to_string(),
+            "--gif".to_string(),
+            "beams.gif".to_string(),
+            "--scale".to_string(),
+            "2".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.command, Command::Viz { day: 7 });
+        assert_eq!(
+            parsed.viz_output,
+            VizOutput::Gif(PathBuf::from("beams.gif"))
+        );
+        assert_eq!((parsed.scale, parsed.max_frames), (Some(2), None));
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "viz".to_string(),
+            "9".to_string(),
+            "--checked".to_string(),
+            "--svg".to_string(),
+            "tiles.svg".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(
+            parsed.viz_output,
+            VizOutput::Svg(PathBuf::from("tiles.svg"))
+        );
+        assert!(parsed.checked);
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "viz".to_string(),
+            "8".to_string(),
+            "--dot".to_string(),
+            "-p".to_string(),
+            "pairs=10".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.viz_output, VizOutput::Dot);
+        assert_eq!(parsed.params, vec![("pairs".to_string(), "10".to_string())]);
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "gen".to_string(),
+            "9".to_string(),
+            "--size".to_string(),
+            "2000".to_string(),
+            "--seed".to_string(),
+            "7".to_string(),
+        ];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.command, Command::Gen { day: 9 });
+        assert_eq!((parsed.size, parsed.seed), (Some(2000), Some(7)));
+
+        let args = vec![
+            "aoc2025".to_string(),
+            "check-input".to_string(),
+            "10".to_string(),
+            "input.txt".to_string(),
+        ];
+        assert_eq!(
+            parse_args(&args).command,
+            Command::CheckInput {
+                day: 10,
+                path: Some(PathBuf::from("input.txt"))
+            }
+        );
     }
 }
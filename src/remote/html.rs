@@ -0,0 +1,222 @@
+//! Conversion of puzzle pages to the `puzzle.txt` text format
+//!
+//! Produces the same layout as copying the puzzle from a browser: the
+//! `--- Day N: Title ---` heading, paragraphs separated by blank lines, and
+//! `<pre>` blocks reproduced verbatim. Inline markup is dropped.
+
+/// Convert every `<article>` on a puzzle page to plain text
+///
+/// Returns `None` if the page has no articles (e.g. a login page returned
+/// for an expired session).
+pub fn articles_to_text(html: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut rest = html;
+    let mut found = false;
+
+    while let Some(start) = rest.find("<article") {
+        let after_open = &rest[start..];
+        let content_start = after_open.find('>')? + 1;
+        let content_end = after_open.find("</article>")?;
+        text.push_str(&to_text(&after_open[content_start..content_end]));
+        rest = &after_open[content_end + "</article>".len()..];
+        found = true;
+    }
+
+    if !found {
+        return None;
+    }
+
+    let mut text = text.trim_end().to_string();
+    text.push('\n');
+    Some(text)
+}
+
+/// Extract the text of the first `<article>`, flattened to a single line
+///
+/// Used to read the verdict returned after submitting an answer.
+pub fn first_article_text(html: &str) -> Option<String> {
+    let start = html.find("<article")?;
+    let after_open = &html[start..];
+    let content_start = after_open.find('>')? + 1;
+    let content_end = after_open.find("</article>")?;
+    let text = to_text(&after_open[content_start..content_end]);
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Convert an HTML fragment to text
+fn to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag_body) = rest.strip_prefix('<') {
+            let Some(end) = tag_body.find('>') else {
+                break;
+            };
+            let tag = &tag_body[..end];
+            rest = &tag_body[end + 1..];
+
+            let closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("")
+                .to_ascii_lowercase();
+
+            match (name.as_str(), closing) {
+                ("pre", false) => in_pre = true,
+                ("pre", true) => {
+                    in_pre = false;
+                    end_line(&mut out);
+                }
+                ("h2", true) | ("li", true) => end_line(&mut out),
+                ("p", true) | ("ul", true) => end_paragraph(&mut out),
+                ("br", _) => out.push('\n'),
+                _ => {}
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let raw = decode_entities(&rest[..end]);
+            rest = &rest[end..];
+
+            if in_pre {
+                out.push_str(&raw);
+            } else {
+                let collapsed = raw.replace(['\n', '\r', '\t'], " ");
+                let at_line_start = out.is_empty() || out.ends_with('\n');
+                if at_line_start {
+                    out.push_str(collapsed.trim_start());
+                } else {
+                    out.push_str(&collapsed);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Terminate the current line if it is not already terminated
+fn end_line(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Terminate the current paragraph with exactly one blank line
+fn end_paragraph(out: &mut String) {
+    while out.ends_with('\n') {
+        out.pop();
+    }
+    out.push_str("\n\n");
+}
+
+/// Decode the HTML entities that appear in puzzle pages
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let ch = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((ch, semi + 1))
+        });
+
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 7: Laboratories ---</h2><p>You thank the <em>cephalopods</em>
+for the help.</p>
+<p>For example:</p>
+<pre><code>.......S.......
+.......^.......
+</code></pre>
+<p>Splitters (<code>^</code>) &amp; beams &lt;3</p>
+<ul>
+<li>One</li>
+<li>Two</li>
+</ul>
+</article>
+<p>Answer: <input type="text" name="answer"/></p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>More&#39;s coming.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_articles_to_text() {
+        let expected = "\
+--- Day 7: Laboratories ---
+You thank the cephalopods for the help.
+
+For example:
+
+.......S.......
+.......^.......
+Splitters (^) & beams <3
+
+One
+Two
+
+--- Part Two ---
+More's coming.
+";
+        assert_eq!(articles_to_text(PAGE).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_no_articles() {
+        assert_eq!(articles_to_text("<html><p>Log in</p></html>"), None);
+    }
+
+    #[test]
+    fn test_first_article_text() {
+        let html = "<main><article><p>That's the <em>right answer</em>!\n  You are one gold star closer.</p></article></main>";
+        assert_eq!(
+            first_article_text(html).unwrap(),
+            "That's the right answer! You are one gold star closer."
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt; b &amp;&amp; c"), "a < b && c");
+        assert_eq!(decode_entities("&#x27;&#39;"), "''");
+        assert_eq!(decode_entities("AT&T; &bogus"), "AT&T; &bogus");
+    }
+}
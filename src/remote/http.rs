@@ -0,0 +1,245 @@
+//! Minimal HTTP client
+//!
+//! Plain `http://` URLs are handled in-crate over a `TcpStream`, which is all
+//! a local stub server needs. `https://` URLs are delegated to the system
+//! `curl` binary so we avoid pulling a TLS stack into the dependency tree.
+
+use super::RemoteError;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// User agent sent with every request, as requested by the AoC maintainers
+pub const USER_AGENT: &str = "aoc2025 (github.com/jk563/aoc-2025-claude)";
+
+/// Timeout for connecting, reading and writing
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An HTTP response with a text body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// A parsed `http://host[:port]/path` URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Url<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+fn parse_http_url(url: &str) -> Result<Url<'_>, RemoteError> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| RemoteError::Url(url.to_string()))?;
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| RemoteError::Url(url.to_string()))?,
+        ),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(RemoteError::Url(url.to_string()));
+    }
+    Ok(Url { host, port, path })
+}
+
+/// Send a GET request with the given cookie
+pub fn get(url: &str, cookie: &str) -> Result<Response, RemoteError> {
+    send("GET", url, cookie, None)
+}
+
+/// Send a form-encoded POST request with the given cookie
+pub fn post_form(url: &str, cookie: &str, form: &str) -> Result<Response, RemoteError> {
+    send("POST", url, cookie, Some(form))
+}
+
+fn send(
+    method: &str,
+    url: &str,
+    cookie: &str,
+    form: Option<&str>,
+) -> Result<Response, RemoteError> {
+    if url.starts_with("https://") {
+        send_curl(url, cookie, form)
+    } else {
+        send_tcp(method, url, cookie, form)
+    }
+}
+
+/// Speak HTTP/1.1 directly over a TCP connection
+fn send_tcp(
+    method: &str,
+    url: &str,
+    cookie: &str,
+    form: Option<&str>,
+) -> Result<Response, RemoteError> {
+    let url = parse_http_url(url)?;
+    let mut stream = TcpStream::connect((url.host, url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: {}\r\nConnection: close\r\n",
+        method, url.path, url.host, USER_AGENT, cookie
+    );
+    if let Some(body) = form {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    if let Some(body) = form {
+        request.push_str(body);
+    }
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    parse_response(&raw)
+}
+
+/// Parse a raw HTTP/1.x response, decoding chunked bodies
+fn parse_response(raw: &[u8]) -> Result<Response, RemoteError> {
+    let malformed = || RemoteError::Malformed("invalid HTTP response".to_string());
+
+    let header_end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(malformed)?;
+    let head = std::str::from_utf8(&raw[..header_end]).map_err(|_| malformed())?;
+    let body = &raw[header_end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(malformed)?;
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+
+    let body = if chunked {
+        decode_chunked(body).ok_or_else(malformed)?
+    } else {
+        body.to_vec()
+    };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Decode a `Transfer-Encoding: chunked` body
+fn decode_chunked(mut data: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = data.windows(2).position(|w| w == b"\r\n")?;
+        let size_str = std::str::from_utf8(&data[..line_end]).ok()?;
+        let size_str = size_str.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_str, 16).ok()?;
+        data = &data[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(data.get(..size)?);
+        data = data.get(size + 2..)?;
+    }
+}
+
+/// Delegate an HTTPS request to `curl`
+///
+/// The cookie is passed on stdin rather than the command line so it does not
+/// show up in the process list.
+fn send_curl(url: &str, cookie: &str, form: Option<&str>) -> Result<Response, RemoteError> {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--max-time", "30"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--header", "@-"])
+        .args(["--write-out", "\n%{http_code}"]);
+    if let Some(body) = form {
+        command.args(["--data", body]);
+    }
+    command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| RemoteError::Curl(format!("failed to run curl: {}", err)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: {}", cookie)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(RemoteError::Curl(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| RemoteError::Malformed("missing curl status".to_string()))?;
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| RemoteError::Malformed(format!("bad curl status: {}", status)))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_url() {
+        let url = parse_http_url("http://127.0.0.1:8080/2025/day/1/input").unwrap();
+        assert_eq!(url.host, "127.0.0.1");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.path, "/2025/day/1/input");
+
+        let url = parse_http_url("http://localhost").unwrap();
+        assert_eq!((url.host, url.port, url.path), ("localhost", 80, "/"));
+
+        assert!(parse_http_url("ftp://example.com").is_err());
+        assert!(parse_http_url("http://host:notaport/").is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello";
+        let response = parse_response(raw).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "hello");
+    }
+
+    #[test]
+    fn test_parse_chunked_response() {
+        let raw = b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        let response = parse_response(raw).unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "Wikipedia");
+    }
+}
@@ -0,0 +1,744 @@
+//! Downloading inputs and submitting answers
+//!
+//! Talks to the Advent of Code site (or any server speaking the same URLs,
+//! such as a local stub) using a session cookie:
+//!
+//! - `GET  {base}/2025/day/{day}/input` - puzzle input
+//! - `GET  {base}/2025/day/{day}` - puzzle page, converted to `puzzle.txt` text
+//! - `POST {base}/2025/day/{day}/answer` - answer submission (`level`, `answer`)
+//!
+//! Everything is cached per account next to the inputs resolved by
+//! [`crate::input::InputStore`]:
+//!
+//! ```text
+//! $XDG_DATA_HOME/aoc2025/<account>/
+//! ├── session              # Session cookie value (or AOC2025_SESSION)
+//! ├── dayNN.txt            # Downloaded input
+//! ├── dayNN.puzzle.txt     # Downloaded puzzle text
+//! ├── submissions.txt      # Verdicts for submitted answers
+//! └── next_submit          # Unix time before which we must not submit
+//! ```
+
+pub mod html;
+pub mod http;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Puzzle year
+pub const YEAR: u32 = 2025;
+
+/// Default server, overridden by `--base-url` or `AOC2025_BASE_URL`
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Seconds to wait after a wrong answer when the server does not say
+const DEFAULT_WRONG_WAIT: u64 = 60;
+
+/// Errors raised while talking to the server or caching results
+#[derive(Debug)]
+pub enum RemoteError {
+    Io(io::Error),
+    /// URL that is neither `http://` nor `https://`, or is malformed
+    Url(String),
+    /// Server answered with a non-success status
+    Status {
+        status: u16,
+        url: String,
+    },
+    /// Response could not be parsed
+    Malformed(String),
+    /// `curl` failed for an HTTPS request
+    Curl(String),
+    /// No session cookie configured
+    NoSession(PathBuf),
+    /// Local rate limit still in force
+    RateLimited {
+        wait_secs: u64,
+    },
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteError::Io(err) => write!(f, "I/O error: {}", err),
+            RemoteError::Url(url) => write!(f, "Unsupported URL: {}", url),
+            RemoteError::Status { status, url } => {
+                write!(f, "Server returned HTTP {} for {}", status, url)
+            }
+            RemoteError::Malformed(msg) => write!(f, "Malformed response: {}", msg),
+            RemoteError::Curl(msg) => write!(f, "curl: {}", msg),
+            RemoteError::NoSession(path) => write!(
+                f,
+                "No session cookie: set AOC2025_SESSION or write it to {}",
+                path.display()
+            ),
+            RemoteError::RateLimited { wait_secs } => write!(
+                f,
+                "Rate limited: wait {}m {}s before submitting again",
+                wait_secs / 60,
+                wait_secs % 60
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+impl From<io::Error> for RemoteError {
+    fn from(err: io::Error) -> Self {
+        RemoteError::Io(err)
+    }
+}
+
+/// Client for the puzzle server
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    /// Create a client for `base_url` authenticating with `session`
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}{}", self.base_url, YEAR, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, RemoteError> {
+        let response = http::get(url, &self.cookie())?;
+        if response.status != 200 {
+            return Err(RemoteError::Status {
+                status: response.status,
+                url: url.to_string(),
+            });
+        }
+        Ok(response.body)
+    }
+
+    /// Download a day's input
+    pub fn fetch_input(&self, day: usize) -> Result<String, RemoteError> {
+        self.get(&self.url(&format!("/day/{}/input", day)))
+    }
+
+    /// Download a day's puzzle page and convert it to text
+    pub fn fetch_puzzle(&self, day: usize) -> Result<String, RemoteError> {
+        let page = self.get(&self.url(&format!("/day/{}", day)))?;
+        html::articles_to_text(&page)
+            .ok_or_else(|| RemoteError::Malformed("puzzle page has no <article>".to_string()))
+    }
+
+    /// Submit an answer and interpret the server's reply
+    pub fn submit(&self, day: usize, part: u8, answer: &str) -> Result<Verdict, RemoteError> {
+        let url = self.url(&format!("/day/{}/answer", day));
+        let form = format!("level={}&answer={}", part, form_encode(answer));
+        let response = http::post_form(&url, &self.cookie(), &form)?;
+        if response.status != 200 {
+            return Err(RemoteError::Status {
+                status: response.status,
+                url,
+            });
+        }
+        let text = html::first_article_text(&response.body)
+            .ok_or_else(|| RemoteError::Malformed("reply has no <article>".to_string()))?;
+        Ok(Verdict::parse(&text))
+    }
+}
+
+/// Percent-encode a form value
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Server's response to a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong answer, with the server's hint and how long to wait
+    Wrong {
+        hint: Option<Hint>,
+        wait_secs: u64,
+    },
+    /// Submitted before the server's cooldown expired
+    TooSoon {
+        wait_secs: u64,
+    },
+    /// The part was already solved (or is not unlocked yet)
+    AlreadySolved,
+    /// Anything else, with the reply text
+    Unknown(String),
+}
+
+/// Direction hint given for some wrong answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Interpret the text of the reply article
+    pub fn parse(text: &str) -> Self {
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Verdict::Wrong {
+                hint,
+                wait_secs: parse_wrong_wait(text).unwrap_or(DEFAULT_WRONG_WAIT),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::TooSoon {
+                wait_secs: parse_left_to_wait(text).unwrap_or(DEFAULT_WRONG_WAIT),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text.to_string())
+        }
+    }
+
+    /// Seconds before another submission is allowed
+    fn wait_secs(&self) -> u64 {
+        match self {
+            Verdict::Wrong { wait_secs, .. } | Verdict::TooSoon { wait_secs } => *wait_secs,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "Correct!"),
+            Verdict::Wrong { hint, wait_secs } => {
+                write!(f, "Wrong answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, " (too high)")?,
+                    Some(Hint::TooLow) => write!(f, " (too low)")?,
+                    None => {}
+                }
+                write!(f, "; wait {}s before trying again", wait_secs)
+            }
+            Verdict::TooSoon { wait_secs } => {
+                write!(f, "Answered too recently; wait {}s", wait_secs)
+            }
+            Verdict::AlreadySolved => write!(f, "Already solved (or not unlocked yet)"),
+            Verdict::Unknown(text) => write!(f, "Unrecognised reply: {}", text),
+        }
+    }
+}
+
+/// Parse "please wait one minute" / "Please wait 5 minutes" from a wrong-answer reply
+///
+/// Matches regardless of case and of how the words are spaced or wrapped.
+fn parse_wrong_wait(text: &str) -> Option<u64> {
+    let text = text.to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let at = words
+        .windows(2)
+        .position(|pair| pair == ["please", "wait"])?;
+    let (count, unit) = (*words.get(at + 2)?, *words.get(at + 3)?);
+    let count = match count {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        digits => digits.parse().ok()?,
+    };
+    let scale = if unit.starts_with("minute") {
+        60
+    } else if unit.starts_with("second") {
+        1
+    } else {
+        return None;
+    };
+    Some(count * scale)
+}
+
+/// Parse "You have 1m 23s left to wait" from a too-soon reply
+fn parse_left_to_wait(text: &str) -> Option<u64> {
+    let after = &text[text.find("You have ")? + "You have ".len()..];
+    let before = &after[..after.find(" left to wait")?];
+    before.split_whitespace().try_fold(0, |total, part| {
+        if let Some(minutes) = part.strip_suffix('m') {
+            Some(total + minutes.parse::<u64>().ok()? * 60)
+        } else {
+            Some(total + part.strip_suffix('s')?.parse::<u64>().ok()?)
+        }
+    })
+}
+
+/// Per-account record of submissions and the submission cooldown
+pub struct SubmissionLog {
+    dir: PathBuf,
+}
+
+impl SubmissionLog {
+    /// Open the log stored in an account directory
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.dir.join("submissions.txt")
+    }
+
+    fn wait_path(&self) -> PathBuf {
+        self.dir.join("next_submit")
+    }
+
+    /// All recorded `(day, part, verdict, answer)` entries
+    fn entries(&self) -> Vec<(usize, u8, String, String)> {
+        let Ok(text) = fs::read_to_string(self.log_path()) else {
+            return Vec::new();
+        };
+        text.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, ' ');
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let verdict = fields.next()?.to_string();
+                let answer = fields.next()?.to_string();
+                Some((day, part, verdict, answer))
+            })
+            .collect()
+    }
+
+    /// Explain why `answer` need not be submitted, if we already know its verdict
+    ///
+    /// Uses exact matches as well as "too high"/"too low" bounds for numeric answers.
+    pub fn known_verdict(&self, day: usize, part: u8, answer: &str) -> Option<String> {
+        let numeric: Option<i128> = answer.parse().ok();
+        for (d, p, verdict, previous) in self.entries() {
+            if d != day || p != part {
+                continue;
+            }
+            if verdict == "correct" {
+                return Some(if previous == answer {
+                    format!("{} was already accepted", previous)
+                } else {
+                    format!("part already solved with {}", previous)
+                });
+            }
+            if previous == answer {
+                return Some(format!("{} was already rejected", answer));
+            }
+            let bound: Option<i128> = previous.parse().ok();
+            match (verdict.as_str(), numeric, bound) {
+                ("high", Some(n), Some(b)) if n >= b => {
+                    return Some(format!("{} was too high, so {} is too", b, n))
+                }
+                ("low", Some(n), Some(b)) if n <= b => {
+                    return Some(format!("{} was too low, so {} is too", b, n))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Seconds remaining before another submission is allowed
+    pub fn wait_remaining(&self) -> u64 {
+        let next: u64 = fs::read_to_string(self.wait_path())
+            .ok()
+            .and_then(|text| text.trim().parse().ok())
+            .unwrap_or(0);
+        next.saturating_sub(now_secs())
+    }
+
+    /// Record a verdict and the resulting cooldown
+    pub fn record(
+        &self,
+        day: usize,
+        part: u8,
+        answer: &str,
+        verdict: &Verdict,
+    ) -> Result<(), RemoteError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let label = match verdict {
+            Verdict::Correct => Some("correct"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => Some("high"),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+                ..
+            } => Some("low"),
+            Verdict::Wrong { hint: None, .. } => Some("wrong"),
+            _ => None,
+        };
+        if let Some(label) = label {
+            let mut log = fs::read_to_string(self.log_path()).unwrap_or_default();
+            log.push_str(&format!("{} {} {} {}\n", day, part, label, answer));
+            fs::write(self.log_path(), log)?;
+        }
+
+        let wait = verdict.wait_secs();
+        if wait > 0 {
+            fs::write(self.wait_path(), (now_secs() + wait).to_string())?;
+        }
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Read the session cookie from `AOC2025_SESSION` or the account's `session` file
+pub fn load_session(account_dir: &Path) -> Result<String, RemoteError> {
+    if let Ok(session) = std::env::var("AOC2025_SESSION") {
+        if !session.trim().is_empty() {
+            return Ok(session);
+        }
+    }
+    let path = account_dir.join("session");
+    match fs::read_to_string(&path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session),
+        _ => Err(RemoteError::NoSession(path)),
+    }
+}
+
+/// Base URL from `AOC2025_BASE_URL`, or the real site
+pub fn base_url_from_env() -> String {
+    std::env::var("AOC2025_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+/// Files written by [`fetch`]
+#[derive(Debug)]
+pub struct FetchOutcome {
+    pub input_path: PathBuf,
+    pub puzzle_path: PathBuf,
+    /// Whether each file was downloaded (`false` means it was already cached)
+    pub input_downloaded: bool,
+    pub puzzle_downloaded: bool,
+}
+
+/// Download a day's input and puzzle text into an account directory
+///
+/// Existing files are kept unless `force` is set, so repeated runs do not
+/// hit the server.
+pub fn fetch(
+    client: &Client,
+    account_dir: &Path,
+    day: usize,
+    force: bool,
+) -> Result<FetchOutcome, RemoteError> {
+    fs::create_dir_all(account_dir)?;
+    let input_path = account_dir.join(crate::input::file_name(day));
+    let puzzle_path = account_dir.join(format!("day{:02}.puzzle.txt", day));
+
+    let input_downloaded = force || !input_path.is_file();
+    if input_downloaded {
+        fs::write(&input_path, client.fetch_input(day)?)?;
+    }
+
+    let puzzle_downloaded = force || !puzzle_path.is_file();
+    if puzzle_downloaded {
+        fs::write(&puzzle_path, client.fetch_puzzle(day)?)?;
+    }
+
+    Ok(FetchOutcome {
+        input_path,
+        puzzle_path,
+        input_downloaded,
+        puzzle_downloaded,
+    })
+}
+
+/// Submit an answer, honouring the local cooldown and the verdict cache
+///
+/// Returns `Ok(Err(reason))` without contacting the server when the verdict
+/// is already known.
+pub fn submit(
+    client: &Client,
+    account_dir: &Path,
+    day: usize,
+    part: u8,
+    answer: &str,
+) -> Result<Result<Verdict, String>, RemoteError> {
+    let log = SubmissionLog::new(account_dir);
+
+    if let Some(reason) = log.known_verdict(day, part, answer) {
+        return Ok(Err(reason));
+    }
+
+    let wait_secs = log.wait_remaining();
+    if wait_secs > 0 {
+        return Err(RemoteError::RateLimited { wait_secs });
+    }
+
+    let verdict = client.submit(day, part, answer)?;
+    log.record(day, part, answer, &verdict)?;
+    Ok(Ok(verdict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aoc2025-remote-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Serve canned responses for `requests` connections, returning the
+    /// base URL and a handle yielding the request lines received
+    fn stub_server(
+        responses: Vec<(&'static str, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                // Read headers, then any body
+                let mut content_length = 0;
+                let mut cookie = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(len) = line.strip_prefix("Content-Length: ") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if let Some(value) = line.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+                let mut body_in = vec![0; content_length];
+                reader.read_exact(&mut body_in).unwrap();
+
+                seen.push(format!(
+                    "{} [{}] {}",
+                    request_line.trim(),
+                    cookie,
+                    String::from_utf8(body_in).unwrap()
+                ));
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            seen
+        });
+        (base, handle)
+    }
+
+    #[test]
+    fn test_fetch_against_stub() {
+        let (base, server) = stub_server(vec![
+            ("200 OK", "1,2,3\n"),
+            (
+                "200 OK",
+                "<main><article><h2>--- Day 3: Test ---</h2><p>Hello.</p></article></main>",
+            ),
+        ]);
+        let dir = scratch_dir("fetch");
+        let client = Client::new(&format!("{}/", base), "abc123");
+
+        let outcome = fetch(&client, &dir, 3, false).unwrap();
+        assert!(outcome.input_downloaded && outcome.puzzle_downloaded);
+        assert_eq!(fs::read_to_string(&outcome.input_path).unwrap(), "1,2,3\n");
+        assert_eq!(
+            fs::read_to_string(&outcome.puzzle_path).unwrap(),
+            "--- Day 3: Test ---\nHello.\n"
+        );
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen[0], "GET /2025/day/3/input HTTP/1.1 [session=abc123] ");
+        assert_eq!(seen[1], "GET /2025/day/3 HTTP/1.1 [session=abc123] ");
+
+        // Second fetch is served from the cache without contacting the server
+        let outcome = fetch(&client, &dir, 3, false).unwrap();
+        assert!(!outcome.input_downloaded && !outcome.puzzle_downloaded);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fetch_error_status() {
+        let (base, server) = stub_server(vec![("404 Not Found", "not yet")]);
+        let dir = scratch_dir("fetch-404");
+        let client = Client::new(&base, "abc123");
+
+        let err = fetch(&client, &dir, 25, false).unwrap_err();
+        assert!(matches!(err, RemoteError::Status { status: 404, .. }));
+
+        server.join().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_submit_against_stub() {
+        let (base, server) = stub_server(vec![(
+            "200 OK",
+            "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article></main>",
+        )]);
+        let dir = scratch_dir("submit");
+        let client = Client::new(&base, "abc123");
+
+        let verdict = submit(&client, &dir, 1, 2, "500").unwrap().unwrap();
+        assert_eq!(
+            verdict,
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait_secs: 60
+            }
+        );
+
+        let seen = server.join().unwrap();
+        assert_eq!(
+            seen[0],
+            "POST /2025/day/1/answer HTTP/1.1 [session=abc123] level=2&answer=500"
+        );
+
+        // Known-wrong answers are rejected locally, before the rate limit check
+        let reason = submit(&client, &dir, 1, 2, "600").unwrap().unwrap_err();
+        assert!(reason.contains("too high"));
+
+        // Anything else is held back by the cooldown
+        let err = submit(&client, &dir, 1, 2, "400").unwrap_err();
+        assert!(matches!(err, RemoteError::RateLimited { .. }));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_verdict_parse() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, ... please wait 5 minutes before trying again."),
+            Verdict::Wrong {
+                hint: None,
+                wait_secs: 300
+            }
+        );
+        assert_eq!(
+            Verdict::parse(
+                "That's not the right answer; your answer is too low. Please wait one minute."
+            ),
+            Verdict::Wrong {
+                hint: Some(Hint::TooLow),
+                wait_secs: 60
+            }
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 23s left to wait."),
+            Verdict::TooSoon { wait_secs: 83 }
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert!(matches!(Verdict::parse("Huh?"), Verdict::Unknown(_)));
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wrong_wait("Please wait one minute."), Some(60));
+        assert_eq!(parse_wrong_wait("PLEASE  WAIT\n10 Seconds"), Some(10));
+        assert_eq!(parse_wrong_wait("please wait a while"), None);
+        assert_eq!(parse_wrong_wait("please wait"), None);
+
+        assert_eq!(
+            parse_left_to_wait("You have 2m 5s left to wait."),
+            Some(125)
+        );
+        assert_eq!(parse_left_to_wait("You have 45s left to wait."), Some(45));
+        // A multibyte last character is rejected rather than split
+        assert_eq!(parse_left_to_wait("You have 1é left to wait."), None);
+        assert_eq!(parse_left_to_wait("You have ms left to wait."), None);
+    }
+
+    #[test]
+    fn test_known_verdict() {
+        let dir = scratch_dir("known");
+        let log = SubmissionLog::new(&dir);
+        let wrong = |hint| Verdict::Wrong { hint, wait_secs: 0 };
+        log.record(4, 1, "100", &wrong(Some(Hint::TooLow))).unwrap();
+        log.record(4, 1, "900", &wrong(Some(Hint::TooHigh)))
+            .unwrap();
+        log.record(4, 1, "321", &wrong(None)).unwrap();
+
+        assert!(log.known_verdict(4, 1, "50").is_some());
+        assert!(log.known_verdict(4, 1, "950").is_some());
+        assert!(log.known_verdict(4, 1, "321").is_some());
+        assert!(log.known_verdict(4, 1, "500").is_none());
+        assert!(log.known_verdict(4, 2, "50").is_none());
+
+        log.record(4, 1, "500", &Verdict::Correct).unwrap();
+        assert!(log
+            .known_verdict(4, 1, "501")
+            .unwrap()
+            .contains("already solved"));
+        assert_eq!(log.wait_remaining(), 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_form_encode() {
+        assert_eq!(form_encode("123"), "123");
+        assert_eq!(form_encode("a b&c"), "a%20b%26c");
+    }
+}
@@ -1,5 +1,15 @@
 //! Day execution and timing logic
 
+use crate::common::rng::Rng;
+use crate::common::validate::Diagnostic;
+use crate::common::viz::Observer;
+use crate::input::{hash_input, hash_stream, normalize, InputError};
+use crate::stream::InputFile;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 /// Trait that all day solutions must implement
@@ -9,6 +19,317 @@ pub trait Day: Send + Sync {
 
     /// Solve part 2 of the puzzle
     fn part2(&self, input: &str) -> String;
+
+    /// Tunable parameters this day accepts, with their defaults
+    ///
+    /// Days that hard-code puzzle constants (dial sizes, pair counts, node names)
+    /// declare them here so examples and unusual inputs can override them.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Solve part 1 with an explicit parameter context
+    ///
+    /// Defaults to `part1`, ignoring the context. Days that declare `params`
+    /// override this and implement `part1` in terms of it.
+    fn part1_with(&self, input: &str, _ctx: &SolveContext) -> String {
+        self.part1(input)
+    }
+
+    /// Solve part 2 with an explicit parameter context
+    fn part2_with(&self, input: &str, _ctx: &SolveContext) -> String {
+        self.part2(input)
+    }
+
+    /// Solve part 1, or report why the input has no answer
+    ///
+    /// Defaults to `part1_with`. Days whose valid inputs can still lack an
+    /// answer (a day 10 machine no presses can set up) override this, and the
+    /// runner reports the error instead of the day's results.
+    fn try_part1(&self, input: &str, ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(self.part1_with(input, ctx))
+    }
+
+    /// Solve part 2, or report why the input has no answer
+    fn try_part2(&self, input: &str, ctx: &SolveContext) -> Result<String, SolveError> {
+        Ok(self.part2_with(input, ctx))
+    }
+
+    /// Check that the input matches the format the parser expects
+    ///
+    /// Reports the first problem found. The default accepts any input.
+    fn validate(&self, _input: &str) -> Result<(), Diagnostic> {
+        Ok(())
+    }
+
+    /// Check the input against the format and the parameter values together
+    ///
+    /// Defaults to `validate`. Days whose parameters limit which inputs they
+    /// can solve (a bank shorter than the batteries to turn on) override this.
+    fn validate_with(&self, input: &str, _ctx: &SolveContext) -> Result<(), Diagnostic> {
+        self.validate(input)
+    }
+
+    /// Whether trailing whitespace on each line is significant
+    ///
+    /// Inputs are normalised before solving, which strips trailing spaces.
+    /// Days whose parsing depends on column alignment return `true`.
+    fn preserve_whitespace(&self) -> bool {
+        false
+    }
+
+    /// Show why the answers are what they are
+    ///
+    /// Returns the choices behind each answer (digits picked, corners, button
+    /// presses), or `None` if the day has nothing to show.
+    fn explain(&self, _input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+        None
+    }
+
+    /// Count the work behind the answers, such as search nodes
+    ///
+    /// Solves again, untimed, so implementations of a day can be compared.
+    /// Returns `None` if the day keeps no counters.
+    fn stats(&self, _input: &str, _ctx: &SolveContext) -> Option<Vec<(&'static str, u64)>> {
+        None
+    }
+
+    /// Draw the steps of the solve as frames for `observer`
+    ///
+    /// Returns `false` if the day has nothing to draw.
+    fn visualize(&self, _input: &str, _observer: &mut dyn Observer) -> bool {
+        false
+    }
+
+    /// Draw the puzzle and its answers as an SVG document
+    ///
+    /// With `checked`, also draws what the solver had to check to get there.
+    /// Returns `None` if the day has no vector drawing.
+    fn draw_svg(&self, _input: &str, _checked: bool) -> Option<String> {
+        None
+    }
+
+    /// Draw the puzzle's graph as Graphviz DOT source
+    ///
+    /// Returns `None` if the day has no graph to draw.
+    fn draw_dot(&self, _input: &str, _ctx: &SolveContext) -> Option<String> {
+        None
+    }
+
+    /// This day as a [`StreamingDay`], if it can solve from a stream
+    ///
+    /// Days that implement it return `Some(self)`, and the runner streams
+    /// their input when it comes from a file.
+    fn streaming(&self) -> Option<&dyn StreamingDay> {
+        None
+    }
+
+    /// This day as an [`InputGenerator`], if it can make random inputs
+    fn generator(&self) -> Option<&dyn InputGenerator> {
+        None
+    }
+}
+
+/// A day that can solve its input in one pass over the lines
+///
+/// For inputs too large to hold in memory as one string. The reader yields
+/// the raw file, so implementations read it through
+/// [`LineReader`](crate::stream::LineReader) to see lines normalised as
+/// [`Day::part1`] would. Answers must match the `&str` parts.
+pub trait StreamingDay: Day {
+    /// Solve part 1 from a reader
+    fn part1_stream(&self, input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<String>;
+
+    /// Solve part 2 from a reader
+    fn part2_stream(&self, input: &mut dyn BufRead, ctx: &SolveContext) -> io::Result<String>;
+}
+
+/// One piece of evidence behind an answer, from [`Day::explain`]
+///
+/// Names the item it is about, such as a bank or a machine, and lists the
+/// values that produced its contribution to the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    pub part: u8,
+    pub label: String,
+    pub facts: Vec<(&'static str, String)>,
+}
+
+impl Witness {
+    /// Create a witness for `part` about the item `label`
+    pub fn new(part: u8, label: impl Into<String>) -> Self {
+        Self {
+            part,
+            label: label.into(),
+            facts: Vec::new(),
+        }
+    }
+
+    /// Return this witness with the fact `key` set to `value`
+    pub fn with(mut self, key: &'static str, value: impl fmt::Display) -> Self {
+        self.facts.push((key, value.to_string()));
+        self
+    }
+
+    /// Value of the fact `key`, if recorded
+    pub fn fact(&self, key: &str) -> Option<&str> {
+        self.facts
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part {}  {}:", self.part, self.label)?;
+        for (key, value) in &self.facts {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Produces random valid inputs for a day, for stress and scaling tests
+///
+/// Generated inputs keep the invariants the real ones have, so every solver
+/// for the day can run on them. What `size` counts (rotations, grid side,
+/// regions, ...) is up to the day and documented on its implementation.
+pub trait InputGenerator {
+    /// Size of the real puzzle input, used when no size is given
+    fn default_size(&self) -> usize;
+
+    /// Generate an input of the given size, one line per `\n`-terminated row
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
+
+/// Normalise an input the way `solver` expects it
+///
+/// Every path that hands input to a solver goes through this, so line
+/// endings and stray whitespace never reach the parsers.
+pub fn prepare_input<'a>(solver: &dyn Day, input: &'a str) -> Cow<'a, str> {
+    normalize(input, solver.preserve_whitespace())
+}
+
+/// A tunable parameter declared by a day
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+    pub kind: ParamKind,
+}
+
+/// Values a [`Param`] accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// Any text, such as a node name
+    Text,
+    /// A whole number in `min..=max`
+    Integer { min: u64, max: u64 },
+    /// A comma-separated list of at most `max` names
+    List { max: usize },
+}
+
+impl Param {
+    /// Check that `value` is one this parameter accepts
+    ///
+    /// Overrides are checked before any solver runs, so `get` never sees a
+    /// value it can't parse.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self.kind {
+            ParamKind::Text => Ok(()),
+            ParamKind::Integer { min, max } => match value.parse::<u64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(()),
+                _ => Err(format!("expected a whole number from {} to {}", min, max)),
+            },
+            ParamKind::List { max } => {
+                let names = value.split(',').filter(|name| !name.trim().is_empty());
+                if names.count() <= max {
+                    Ok(())
+                } else {
+                    Err(format!("expected at most {} comma-separated names", max))
+                }
+            }
+        }
+    }
+}
+
+/// Parameter values passed to a solver
+///
+/// Built from a day's declared defaults, optionally overridden on the
+/// command line with `--param key=value`.
+#[derive(Debug, Clone, Default)]
+pub struct SolveContext {
+    values: HashMap<String, String>,
+}
+
+impl SolveContext {
+    /// Create a context holding the default value of every declared parameter
+    pub fn from_defaults(params: &[Param]) -> Self {
+        let values = params
+            .iter()
+            .map(|p| (p.name.to_string(), p.default.to_string()))
+            .collect();
+        Self { values }
+    }
+
+    /// Create a context from declared defaults, applying any overrides for
+    /// parameters the day declares. Overrides for other keys are ignored.
+    pub fn with_overrides(params: &[Param], overrides: &[(String, String)]) -> Self {
+        let mut ctx = Self::from_defaults(params);
+        for (key, value) in overrides {
+            if ctx.values.contains_key(key) {
+                ctx.values.insert(key.clone(), value.clone());
+            }
+        }
+        ctx
+    }
+
+    /// Return a copy of this context with `key` set to `value`
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.values.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Get the raw string value of a parameter
+    pub fn get_str(&self, key: &str) -> &str {
+        self.values
+            .get(key)
+            .unwrap_or_else(|| panic!("Unknown parameter: {}", key))
+    }
+
+    /// Get a parameter parsed as `T`
+    ///
+    /// Panics if the parameter is not declared or its value does not parse.
+    pub fn get<T: FromStr>(&self, key: &str) -> T {
+        let value = self.get_str(key);
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid value for parameter {}: {}", key, value))
+    }
+}
+
+/// Why a part has no answer for an input that passed validation
+pub type SolveError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A part that had no answer for the input it was run against
+#[derive(Debug)]
+pub struct PartError {
+    pub part: u8,
+    pub source: SolveError,
+}
+
+impl fmt::Display for PartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Part {}: {}", self.part, self.source)
+    }
+}
+
+impl std::error::Error for PartError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
 }
 
 /// Result of running a single day's solution
@@ -20,6 +341,8 @@ pub struct DayResult {
     pub part2_result: String,
     pub part1_time: Duration,
     pub part2_time: Duration,
+    /// FNV-1a hash of the normalised input the day was run against
+    pub input_hash: u64,
 }
 
 impl DayResult {
@@ -38,27 +361,143 @@ pub struct DayInfo {
 }
 
 /// Run a single day's solution and measure timing
-pub fn run_day(info: &DayInfo) -> DayResult {
+pub fn run_day(info: &DayInfo) -> Result<DayResult, PartError> {
+    run_day_with_params(info, &[])
+}
+
+/// Run a single day's solution with parameter overrides and measure timing
+pub fn run_day_with_params(
+    info: &DayInfo,
+    overrides: &[(String, String)],
+) -> Result<DayResult, PartError> {
+    run_day_with_input(info, info.input, overrides)
+}
+
+/// Run a single day's solution against a resolved input and measure timing
+///
+/// Used when the input comes from the input store rather than the
+/// embedded file registered with the day. Fails with the first part that
+/// has no answer.
+pub fn run_day_with_input(
+    info: &DayInfo,
+    input: &str,
+    overrides: &[(String, String)],
+) -> Result<DayResult, PartError> {
+    let ctx = SolveContext::with_overrides(info.solver.params(), overrides);
+    let input = prepare_input(info.solver.as_ref(), input);
+    let input = input.as_ref();
+
     let start = Instant::now();
-    let part1_result = info.solver.part1(info.input);
+    let part1_result = info
+        .solver
+        .try_part1(input, &ctx)
+        .map_err(|source| PartError { part: 1, source })?;
     let part1_time = start.elapsed();
 
     let start = Instant::now();
-    let part2_result = info.solver.part2(info.input);
+    let part2_result = info
+        .solver
+        .try_part2(input, &ctx)
+        .map_err(|source| PartError { part: 2, source })?;
     let part2_time = start.elapsed();
 
-    DayResult {
+    Ok(DayResult {
         day_number: info.number,
         impl_name: info.impl_name.clone(),
         part1_result,
         part2_result,
         part1_time,
         part2_time,
-    }
+        input_hash: hash_input(input),
+    })
+}
+
+/// Run a streaming day against an input file without reading it into memory
+///
+/// Each part makes its own pass over the file, through its memory map if it
+/// was opened with [`InputFile::map`]. The hash is of the normalised input,
+/// as for [`run_day_with_input`], and is taken in an untimed pass of its own.
+pub fn run_day_streaming(
+    info: &DayInfo,
+    solver: &dyn StreamingDay,
+    file: &InputFile,
+    overrides: &[(String, String)],
+) -> Result<DayResult, InputError> {
+    let io_error = |source| InputError::Io {
+        path: file.path().to_path_buf(),
+        source,
+    };
+    let ctx = SolveContext::with_overrides(info.solver.params(), overrides);
+    let input_hash = hash_stream(file.reader().map_err(io_error)?).map_err(io_error)?;
+
+    let start = Instant::now();
+    let part1_result = file
+        .reader()
+        .and_then(|mut reader| solver.part1_stream(&mut reader, &ctx))
+        .map_err(io_error)?;
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2_result = file
+        .reader()
+        .and_then(|mut reader| solver.part2_stream(&mut reader, &ctx))
+        .map_err(io_error)?;
+    let part2_time = start.elapsed();
+
+    Ok(DayResult {
+        day_number: info.number,
+        impl_name: info.impl_name.clone(),
+        part1_result,
+        part2_result,
+        part1_time,
+        part2_time,
+        input_hash,
+    })
+}
+
+/// Check a resolved input against what a day can solve with the given
+/// parameters
+///
+/// Untimed; run before [`run_day_with_input`] so a bad input is reported
+/// instead of failing inside the solver.
+pub fn check_day_input(
+    info: &DayInfo,
+    input: &str,
+    overrides: &[(String, String)],
+) -> Result<(), Diagnostic> {
+    let ctx = SolveContext::with_overrides(info.solver.params(), overrides);
+    let input = prepare_input(info.solver.as_ref(), input);
+    info.solver.validate_with(&input, &ctx)
+}
+
+/// Explain a day's answers for a resolved input
+///
+/// Uses the same input preparation and parameters as [`run_day_with_input`].
+pub fn explain_day(
+    info: &DayInfo,
+    input: &str,
+    overrides: &[(String, String)],
+) -> Option<Vec<Witness>> {
+    let ctx = SolveContext::with_overrides(info.solver.params(), overrides);
+    let input = prepare_input(info.solver.as_ref(), input);
+    info.solver.explain(&input, &ctx)
+}
+
+/// Count the work behind a day's answers for a resolved input
+///
+/// Uses the same input preparation and parameters as [`run_day_with_input`].
+pub fn day_stats(
+    info: &DayInfo,
+    input: &str,
+    overrides: &[(String, String)],
+) -> Option<Vec<(&'static str, u64)>> {
+    let ctx = SolveContext::with_overrides(info.solver.params(), overrides);
+    let input = prepare_input(info.solver.as_ref(), input);
+    info.solver.stats(&input, &ctx)
 }
 
 /// Run all registered days and return their results
-pub fn run_all(days: &[DayInfo]) -> Vec<DayResult> {
+pub fn run_all(days: &[DayInfo]) -> Vec<Result<DayResult, PartError>> {
     days.iter().map(run_day).collect()
 }
 
@@ -100,11 +539,318 @@ mod tests {
             input: "test123",
         };
 
-        let result = run_day(&info);
+        let result = run_day(&info).unwrap();
         assert_eq!(result.day_number, 1);
         assert_eq!(result.impl_name, None);
         assert_eq!(result.part1_result, "7");
         assert_eq!(result.part2_result, "3");
+        assert_eq!(result.input_hash, hash_input("test123"));
+    }
+
+    #[test]
+    fn test_run_day_with_input() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(TestDay),
+            input: "test123",
+        };
+
+        let result = run_day_with_input(&info, "override99", &[]).unwrap();
+        assert_eq!(result.part1_result, "10");
+        assert_eq!(result.part2_result, "2");
+        assert_eq!(result.input_hash, hash_input("override99"));
+    }
+
+    #[test]
+    fn test_run_day_normalizes_input() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(TestDay),
+            input: "",
+        };
+
+        let result = run_day_with_input(&info, "\u{feff}test\r\n123 \r\n\r\n", &[]).unwrap();
+        assert_eq!(result.part1_result, "8");
+        assert_eq!(result.input_hash, hash_input("test\n123"));
+    }
+
+    /// Counts non-blank lines and their total length, from a string or stream
+    struct LinesDay;
+
+    impl Day for LinesDay {
+        fn part1(&self, input: &str) -> String {
+            input.lines().filter(|l| !l.is_empty()).count().to_string()
+        }
+
+        fn part2(&self, input: &str) -> String {
+            input.lines().map(str::len).sum::<usize>().to_string()
+        }
+
+        fn streaming(&self) -> Option<&dyn StreamingDay> {
+            Some(self)
+        }
+    }
+
+    impl StreamingDay for LinesDay {
+        fn part1_stream(&self, input: &mut dyn BufRead, _ctx: &SolveContext) -> io::Result<String> {
+            let mut lines = crate::stream::LineReader::new(input);
+            let mut count = 0;
+            while let Some((_, line)) = lines.next_line()? {
+                count += usize::from(!line.is_empty());
+            }
+            Ok(count.to_string())
+        }
+
+        fn part2_stream(&self, input: &mut dyn BufRead, _ctx: &SolveContext) -> io::Result<String> {
+            let mut lines = crate::stream::LineReader::new(input);
+            let mut total = 0;
+            while let Some((_, line)) = lines.next_line()? {
+                total += line.len();
+            }
+            Ok(total.to_string())
+        }
+    }
+
+    #[test]
+    fn test_run_day_streaming() {
+        let text = "\u{feff}ab \r\n\r\ncde\r\n\r\n";
+        let path = std::env::temp_dir().join(format!("aoc2025-runner-{}.txt", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(LinesDay),
+            input: "",
+        };
+
+        let solver = info.solver.streaming().unwrap();
+        let file = InputFile::open(&path).unwrap();
+        let streamed = run_day_streaming(&info, solver, &file, &[]).unwrap();
+        let loaded = run_day_with_input(&info, text, &[]).unwrap();
+        assert_eq!(
+            (
+                streamed.part1_result.as_str(),
+                streamed.part2_result.as_str()
+            ),
+            ("2", "5")
+        );
+        assert_eq!(streamed.part1_result, loaded.part1_result);
+        assert_eq!(streamed.part2_result, loaded.part2_result);
+        assert_eq!(streamed.input_hash, loaded.input_hash);
+
+        // Buffered passes reopen the file, so one removed since is an error
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(
+            run_day_streaming(&info, solver, &file, &[]),
+            Err(InputError::Io { .. })
+        ));
+    }
+
+    struct AlignedDay;
+
+    impl Day for AlignedDay {
+        fn part1(&self, input: &str) -> String {
+            input.len().to_string()
+        }
+
+        fn part2(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn preserve_whitespace(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_prepare_input_preserves_whitespace() {
+        assert_eq!(prepare_input(&AlignedDay, "1 \r\n+ \r\n"), "1 \n+ ");
+        assert_eq!(prepare_input(&TestDay, "1 \r\n+ \r\n"), "1\n+");
+    }
+
+    struct ParamDay;
+
+    impl Day for ParamDay {
+        fn part1(&self, input: &str) -> String {
+            self.part1_with(input, &SolveContext::from_defaults(self.params()))
+        }
+
+        fn part2(&self, input: &str) -> String {
+            self.part2_with(input, &SolveContext::from_defaults(self.params()))
+        }
+
+        fn params(&self) -> &'static [Param] {
+            &[Param {
+                name: "scale",
+                default: "2",
+                description: "Multiplier applied to the input length",
+                kind: ParamKind::Integer { min: 1, max: 9 },
+            }]
+        }
+
+        fn part1_with(&self, input: &str, ctx: &SolveContext) -> String {
+            (input.len() * ctx.get::<usize>("scale")).to_string()
+        }
+
+        fn part2_with(&self, input: &str, ctx: &SolveContext) -> String {
+            ctx.get_str("scale").repeat(input.len())
+        }
+    }
+
+    #[test]
+    fn test_run_day_with_params() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(ParamDay),
+            input: "abc",
+        };
+
+        let result = run_day(&info).unwrap();
+        assert_eq!(result.part1_result, "6");
+
+        let overrides = vec![
+            ("scale".to_string(), "5".to_string()),
+            ("unrelated".to_string(), "1".to_string()),
+        ];
+        let result = run_day_with_params(&info, &overrides).unwrap();
+        assert_eq!(result.part1_result, "15");
+        assert_eq!(result.part2_result, "555");
+    }
+
+    #[test]
+    fn test_solve_context_with() {
+        let ctx = SolveContext::from_defaults(ParamDay.params()).with("scale", "7");
+        assert_eq!(ctx.get::<u32>("scale"), 7);
+    }
+
+    #[test]
+    fn test_param_check() {
+        let scale = ParamDay.params()[0];
+        assert!(scale.check("9").is_ok());
+        for bad in ["0", "10", "-1", "abc", ""] {
+            assert_eq!(
+                scale.check(bad),
+                Err("expected a whole number from 1 to 9".to_string()),
+                "{:?}",
+                bad
+            );
+        }
+
+        let name = Param {
+            kind: ParamKind::Text,
+            ..scale
+        };
+        assert!(name.check("abc").is_ok());
+
+        let names = Param {
+            kind: ParamKind::List { max: 2 },
+            ..scale
+        };
+        assert!(names.check("a, b,").is_ok());
+        assert!(names.check("").is_ok());
+        assert_eq!(
+            names.check("a,b,c"),
+            Err("expected at most 2 comma-separated names".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown parameter")]
+    fn test_solve_context_unknown_key() {
+        let ctx = SolveContext::default();
+        ctx.get_str("missing");
+    }
+
+    struct UnanswerableDay;
+
+    impl Day for UnanswerableDay {
+        fn part1(&self, input: &str) -> String {
+            input.len().to_string()
+        }
+
+        fn part2(&self, _input: &str) -> String {
+            unreachable!("try_part2 answers instead")
+        }
+
+        fn try_part2(&self, _input: &str, _ctx: &SolveContext) -> Result<String, SolveError> {
+            Err("no answer".into())
+        }
+    }
+
+    #[test]
+    fn test_run_day_part_error() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(UnanswerableDay),
+            input: "abc",
+        };
+        let err = run_day(&info).unwrap_err();
+        assert_eq!(err.part, 2);
+        assert_eq!(err.to_string(), "Part 2: no answer");
+    }
+
+    struct ExplainedDay;
+
+    impl Day for ExplainedDay {
+        fn part1(&self, input: &str) -> String {
+            input.len().to_string()
+        }
+
+        fn part2(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn explain(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<Witness>> {
+            Some(vec![Witness::new(1, "input")
+                .with("bytes", input.len())
+                .with("text", input)])
+        }
+
+        fn stats(&self, input: &str, _ctx: &SolveContext) -> Option<Vec<(&'static str, u64)>> {
+            Some(vec![("lines", input.lines().count() as u64)])
+        }
+    }
+
+    #[test]
+    fn test_explain_day() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(ExplainedDay),
+            input: "",
+        };
+
+        let witnesses = explain_day(&info, "abc \r\n", &[]).unwrap();
+        assert_eq!(witnesses[0].fact("bytes"), Some("3"));
+        assert_eq!(witnesses[0].fact("missing"), None);
+        assert_eq!(witnesses[0].to_string(), "part 1  input: bytes=3 text=abc");
+
+        let info = DayInfo {
+            solver: Box::new(TestDay),
+            ..info
+        };
+        assert_eq!(explain_day(&info, "abc", &[]), None);
+    }
+
+    #[test]
+    fn test_day_stats() {
+        let info = DayInfo {
+            number: 1,
+            impl_name: None,
+            solver: Box::new(ExplainedDay),
+            input: "",
+        };
+        assert_eq!(day_stats(&info, "a\nb\n\n", &[]), Some(vec![("lines", 2)]));
+
+        let info = DayInfo {
+            solver: Box::new(TestDay),
+            ..info
+        };
+        assert_eq!(day_stats(&info, "abc", &[]), None);
     }
 
     #[test]
@@ -0,0 +1,211 @@
+//! Streaming access to input files
+//!
+//! Days that implement [`StreamingDay`](crate::runner::StreamingDay) read
+//! their input one line at a time instead of as one string, so inputs larger
+//! than memory can still be solved. [`InputFile`] opens a file for repeated
+//! passes, memory-mapped with the `mmap` feature if the caller can promise the
+//! file won't change, and [`LineReader`] yields its lines normalised the way
+//! [`normalize`](crate::input::normalize) would.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc2025::stream::LineReader;
+//!
+//! let mut lines = LineReader::new("\u{feff}L68 \r\nR48\n".as_bytes());
+//! assert_eq!(lines.next_line().unwrap(), Some((1, "L68")));
+//! assert_eq!(lines.next_line().unwrap(), Some((2, "R48")));
+//! assert_eq!(lines.next_line().unwrap(), None);
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Reads normalised lines from a [`BufRead`], reusing one buffer
+///
+/// Each line has its line ending (`\n` or `\r\n`) and trailing spaces and
+/// tabs removed, and a byte order mark at the start of the input is skipped.
+/// Blank lines are returned; trailing ones are for the caller to ignore.
+pub struct LineReader<R> {
+    reader: R,
+    line: String,
+    line_no: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// Read lines from `reader`
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_no: 0,
+        }
+    }
+
+    /// The next line with its 1-based line number, or `None` at the end
+    ///
+    /// Fails if reading fails or the line is not valid UTF-8.
+    pub fn next_line(&mut self) -> io::Result<Option<(usize, &str)>> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+        self.line_no += 1;
+
+        let mut line = self.line.as_str();
+        if self.line_no == 1 {
+            line = line.strip_prefix('\u{feff}').unwrap_or(line);
+        }
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        Ok(Some((self.line_no, line.trim_end_matches([' ', '\t']))))
+    }
+}
+
+/// A file opened for streaming, read through a memory map or a buffered reader
+///
+/// Buffered files are reopened for every pass, so a file that changes
+/// between passes is read as it is at the start of each.
+pub struct InputFile {
+    path: PathBuf,
+    #[cfg(feature = "mmap")]
+    map: Option<memmap2::Mmap>,
+}
+
+impl InputFile {
+    /// Open `path` to be read through a buffered reader, failing only if it
+    /// can't be read at all
+    pub fn open(path: &Path) -> io::Result<Self> {
+        File::open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            #[cfg(feature = "mmap")]
+            map: None,
+        })
+    }
+
+    /// Open `path` through a read-only memory map, or a buffered reader if
+    /// it can't be mapped (a pipe, say)
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or written to while this `InputFile`
+    /// or any reader from it is alive. Truncating a mapped file faults
+    /// (`SIGBUS`) on the next read of the missing pages, and writes change
+    /// bytes behind the `&[u8]` the readers hold.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees the file is left alone while mapped
+        let map = unsafe { memmap2::Mmap::map(&file) }.ok();
+        Ok(Self {
+            path: path.to_path_buf(),
+            map,
+        })
+    }
+
+    /// The path the file was opened from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether passes read through a memory map
+    pub fn is_mapped(&self) -> bool {
+        #[cfg(feature = "mmap")]
+        return self.map.is_some();
+        #[cfg(not(feature = "mmap"))]
+        false
+    }
+
+    /// A reader positioned at the start of the file
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        #[cfg(feature = "mmap")]
+        if let Some(map) = &self.map {
+            return Ok(Box::new(&map[..]));
+        }
+        Ok(Box::new(BufReader::new(File::open(&self.path)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn read_all<R: BufRead>(reader: R) -> Vec<(usize, String)> {
+        let mut lines = LineReader::new(reader);
+        let mut out = Vec::new();
+        while let Some((line_no, line)) = lines.next_line().unwrap() {
+            out.push((line_no, line.to_string()));
+        }
+        out
+    }
+
+    #[test]
+    fn test_line_reader_normalises() {
+        let lines = read_all("\u{feff}1-3 \r\n\t\n4\t\r\n\n".as_bytes());
+        let expected = [(1, "1-3"), (2, ""), (3, "4"), (4, "")];
+        assert_eq!(lines.len(), expected.len());
+        for ((line_no, line), (want_no, want)) in lines.iter().zip(expected) {
+            assert_eq!((*line_no, line.as_str()), (want_no, want));
+        }
+
+        // Only a mark at the very start is skipped
+        let lines = read_all("a\n\u{feff}b".as_bytes());
+        assert_eq!(lines[1].1, "\u{feff}b");
+    }
+
+    #[test]
+    fn test_line_reader_rejects_invalid_utf8() {
+        let mut lines = LineReader::new(&b"ok\n\xff\n"[..]);
+        assert!(lines.next_line().is_ok());
+        let err = lines.next_line().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_input_file_passes() {
+        let dir = env::temp_dir().join(format!("aoc2025-stream-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+        fs::write(&path, "L68\nR48\n").unwrap();
+        let empty = dir.join("empty.txt");
+        fs::write(&empty, "").unwrap();
+
+        let files = [
+            InputFile::open(&path).unwrap(),
+            // SAFETY: nothing writes to the test's files until they are removed
+            #[cfg(feature = "mmap")]
+            unsafe { InputFile::map(&path) }.unwrap(),
+        ];
+        assert!(!files[0].is_mapped());
+        assert_eq!(files[0].path(), path);
+        if cfg!(all(feature = "mmap", unix)) {
+            assert!(files[1..].iter().all(InputFile::is_mapped));
+        }
+
+        // Every reader starts from the beginning
+        for file in &files {
+            for _ in 0..2 {
+                let lines = read_all(file.reader().unwrap());
+                assert_eq!(lines.len(), 2);
+                assert_eq!(lines[1].1, "R48");
+            }
+        }
+        drop(files);
+
+        let file = InputFile::open(&empty).unwrap();
+        assert!(read_all(file.reader().unwrap()).is_empty());
+        // SAFETY: as above
+        #[cfg(feature = "mmap")]
+        {
+            let file = unsafe { InputFile::map(&empty) }.unwrap();
+            assert!(read_all(file.reader().unwrap()).is_empty());
+        }
+
+        assert!(InputFile::open(&dir.join("missing.txt")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
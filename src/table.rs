@@ -166,6 +166,7 @@ mod tests {
             part2_result: "100".to_string(),
             part1_time: Duration::from_micros(500),
             part2_time: Duration::from_millis(2),
+            input_hash: 0,
         }];
 
         let output = format_results(&results);
@@ -186,6 +187,7 @@ mod tests {
                 part2_result: "100".to_string(),
                 part1_time: Duration::from_micros(500),
                 part2_time: Duration::from_millis(2),
+                input_hash: 0,
             },
             DayResult {
                 day_number: 2,
@@ -194,6 +196,7 @@ mod tests {
                 part2_result: "5678".to_string(),
                 part1_time: Duration::from_millis(5),
                 part2_time: Duration::from_millis(10),
+                input_hash: 0,
             },
         ];
 
@@ -213,6 +216,7 @@ mod tests {
                 part2_result: "5678".to_string(),
                 part1_time: Duration::from_millis(5),
                 part2_time: Duration::from_millis(10),
+                input_hash: 0,
             },
             DayResult {
                 day_number: 2,
@@ -221,6 +225,7 @@ mod tests {
                 part2_result: "5678".to_string(),
                 part1_time: Duration::from_millis(8),
                 part2_time: Duration::from_millis(15),
+                input_hash: 0,
             },
         ];
 
@@ -27,3 +27,19 @@ fn test_get_specific_day_nonexistent() {
     let day = days::get_day(99);
     assert!(day.is_none());
 }
+
+#[test]
+fn test_embedded_inputs_validate() {
+    // Every registered implementation must accept its own puzzle input
+    for info in days::get_all_implementations() {
+        let input = runner::prepare_input(info.solver.as_ref(), info.input);
+        if let Err(err) = info.solver.validate(&input) {
+            panic!(
+                "Day {:02} ({}) rejected its input:\n{}",
+                info.number,
+                info.impl_name.as_deref().unwrap_or("default"),
+                err.render("input.txt")
+            );
+        }
+    }
+}